@@ -13,28 +13,42 @@
 /// - **ボール所持状況**: 連続フェーズ数
 /// - **その他**: ペナルティ数、イエローカード等
 use std::time::Duration;
-use tokio::time::sleep;
 
 // =============================================================================
 // ゲーム状態の型定義
 // =============================================================================
 
 /// フィールド上の位置
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FieldPosition {
     /// 自陣22mライン内（危険地帯）
+    #[cfg_attr(feature = "serde", serde(rename = "Own22"))]
     Own22,
     /// 自陣22m～ハーフライン
+    #[cfg_attr(feature = "serde", serde(rename = "OwnHalf"))]
     OwnHalf,
     /// 中盤（ハーフライン付近）
+    #[cfg_attr(feature = "serde", serde(rename = "Midfield"))]
     Midfield,
     /// 敵陣ハーフ
+    #[cfg_attr(feature = "serde", serde(rename = "OppositionHalf"))]
     OppositionHalf,
     /// 敵陣22mライン内（得点圏内）
+    #[cfg_attr(feature = "serde", serde(rename = "Opposition22"))]
     Opposition22,
 }
 
 impl FieldPosition {
+    /// 自陣から敵陣への順のすべての位置
+    pub const ALL: [FieldPosition; 5] = [
+        FieldPosition::Own22,
+        FieldPosition::OwnHalf,
+        FieldPosition::Midfield,
+        FieldPosition::OppositionHalf,
+        FieldPosition::Opposition22,
+    ];
+
     /// この位置からのリスク評価（0.0-1.0）
     pub fn risk_level(&self) -> f32 {
         match self {
@@ -56,20 +70,75 @@ impl FieldPosition {
             FieldPosition::Opposition22 => 0.0, // ランプレー推奨
         }
     }
+
+    /// ドロップゴールの範囲係数（0.0-1.0、0.0は圏外）
+    ///
+    /// 敵陣22m内はゴール正面から狙える1.0、敵陣は距離があるため0.5です。
+    pub fn drop_goal_range_factor(&self) -> f32 {
+        match self {
+            FieldPosition::Opposition22 => 1.0,
+            FieldPosition::OppositionHalf => 0.5,
+            _ => 0.0,
+        }
+    }
+
+    /// この位置が占める範囲（自陣ゴールラインからのメートル）
+    ///
+    /// 各範囲は隣の範囲と境界を共有し、合わせて0-100mを覆います。
+    ///
+    /// - `Own22`: 0-22m
+    /// - `OwnHalf`: 22-40m
+    /// - `Midfield`: 40-60m
+    /// - `OppositionHalf`: 60-78m
+    /// - `Opposition22`: 78-100m
+    pub fn meter_range(&self) -> std::ops::RangeInclusive<f32> {
+        match self {
+            FieldPosition::Own22 => 0.0..=22.0,
+            FieldPosition::OwnHalf => 22.0..=40.0,
+            FieldPosition::Midfield => 40.0..=60.0,
+            FieldPosition::OppositionHalf => 60.0..=78.0,
+            FieldPosition::Opposition22 => 78.0..=100.0,
+        }
+    }
+
+    /// 自陣ゴールラインからの距離（m）が含まれる位置
+    ///
+    /// 0-100mの外側は端の位置に丸め、境界上の値は前方の位置に含めます。
+    pub fn from_meters(meters: f32) -> FieldPosition {
+        match meters {
+            m if m < 22.0 => FieldPosition::Own22,
+            m if m < 40.0 => FieldPosition::OwnHalf,
+            m if m < 60.0 => FieldPosition::Midfield,
+            m if m < 78.0 => FieldPosition::OppositionHalf,
+            _ => FieldPosition::Opposition22,
+        }
+    }
+
+    /// 範囲の中央（自陣ゴールラインからのメートル）
+    pub fn midpoint(&self) -> f32 {
+        let range = self.meter_range();
+        (range.start() + range.end()) / 2.0
+    }
 }
 
 /// 天候の状態
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Weather {
     /// 晴天（理想的）
+    #[cfg_attr(feature = "serde", serde(rename = "Sunny"))]
     Sunny,
     /// 曇り
+    #[cfg_attr(feature = "serde", serde(rename = "Cloudy"))]
     Cloudy,
     /// 雨天（滑りやすい）
+    #[cfg_attr(feature = "serde", serde(rename = "Rainy"))]
     Rainy,
     /// 強風
+    #[cfg_attr(feature = "serde", serde(rename = "Windy"))]
     Windy,
     /// 雨+風（最悪条件）
+    #[cfg_attr(feature = "serde", serde(rename = "StormyRain"))]
     StormyRain,
 }
 
@@ -95,15 +164,146 @@ impl Weather {
             Weather::StormyRain => 0.1,
         }
     }
+
+    /// 次の天候への遷移確率
+    ///
+    /// 遷移は「隣接する」天候の間でのみ起こります
+    /// （晴天↔曇り↔雨/強風↔雨+風）。
+    ///
+    /// | 現在 \ 次  | 晴天 | 曇り | 雨天 | 強風 | 雨+風 |
+    /// |------------|------|------|------|------|-------|
+    /// | 晴天       | 0.8  | 0.2  |      |      |       |
+    /// | 曇り       | 0.2  | 0.5  | 0.2  | 0.1  |       |
+    /// | 雨天       |      | 0.2  | 0.6  |      | 0.2   |
+    /// | 強風       |      | 0.3  |      | 0.5  | 0.2   |
+    /// | 雨+風      |      |      | 0.3  | 0.1  | 0.6   |
+    pub fn transition_probabilities(&self) -> &'static [(Weather, f32)] {
+        match self {
+            Weather::Sunny => &[(Weather::Sunny, 0.8), (Weather::Cloudy, 0.2)],
+            Weather::Cloudy => &[
+                (Weather::Sunny, 0.2),
+                (Weather::Cloudy, 0.5),
+                (Weather::Rainy, 0.2),
+                (Weather::Windy, 0.1),
+            ],
+            Weather::Rainy => &[
+                (Weather::Cloudy, 0.2),
+                (Weather::Rainy, 0.6),
+                (Weather::StormyRain, 0.2),
+            ],
+            Weather::Windy => &[
+                (Weather::Cloudy, 0.3),
+                (Weather::Windy, 0.5),
+                (Weather::StormyRain, 0.2),
+            ],
+            Weather::StormyRain => &[
+                (Weather::Rainy, 0.3),
+                (Weather::Windy, 0.1),
+                (Weather::StormyRain, 0.6),
+            ],
+        }
+    }
+
+    /// 遷移確率に従って次の天候を決定
+    pub fn next(&self, rng: &mut SimRng) -> Weather {
+        let roll = rng.next_f32();
+        let mut cumulative = 0.0;
+        for &(weather, probability) in self.transition_probabilities() {
+            cumulative += probability;
+            if roll < cumulative {
+                return weather;
+            }
+        }
+        *self
+    }
 }
 
 /// 風の状態
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Wind {
     /// 風速（m/s）
     pub speed: f32,
     /// 風向き（度、0=北、90=東、180=南、270=西）
     pub direction: f32,
+    /// 突風時の最大風速（m/s、`speed`未満なら突風なしとみなす。シナリオファイルでは省略可）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gust: f32,
+}
+
+impl Wind {
+    /// 瞬間風速をサンプリング（m/s）
+    ///
+    /// `speed`から`gust`までの値を返します。一様乱数`u`に対して
+    /// `speed + (gust - speed) * u²`とするため、平均風速に近い値が出やすく、
+    /// 最大風速に近い突風はまれです。
+    pub fn sample_instantaneous(&self, rng: &mut SimRng) -> f32 {
+        let peak = self.gust.max(self.speed);
+        let u = rng.next_f32();
+        self.speed + (peak - self.speed) * u * u
+    }
+
+    /// 横風成分（m/s）
+    ///
+    /// 攻撃方向を北（0°）とした場合の、攻撃方向に直交する風速です。
+    pub fn crosswind(&self) -> f32 {
+        self.crosswind_toward(0.0)
+    }
+
+    /// 向かい風成分（m/s、負の値は追い風）
+    ///
+    /// 攻撃方向を北（0°）とし、風向きは風が吹いてくる方角とみなします。
+    pub fn headwind(&self) -> f32 {
+        self.headwind_toward(0.0)
+    }
+
+    /// 指定した方角へ攻撃する場合の横風成分（m/s）
+    pub fn crosswind_toward(&self, attacking_bearing: f32) -> f32 {
+        (self.speed * (self.direction - attacking_bearing).to_radians().sin()).abs()
+    }
+
+    /// 指定した方角へ攻撃する場合の向かい風成分（m/s、負の値は追い風）
+    ///
+    /// 風が攻撃方向から吹いてくると向かい風になります。
+    pub fn headwind_toward(&self, attacking_bearing: f32) -> f32 {
+        self.speed * (self.direction - attacking_bearing).to_radians().cos()
+    }
+}
+
+/// ハンドリングが最も難しくなる横風の強さ（m/s）
+pub const CROSSWIND_FULL_DIFFICULTY_MS: f32 = 15.0;
+
+/// 天候と風を合わせたハンドリングの難しさ（0.0-1.0）
+///
+/// 天候による難しさ`w = weather.pass_difficulty()`と、北（0°）へ攻撃する場合の
+/// 横風による難しさ`c = crosswind / CROSSWIND_FULL_DIFFICULTY_MS`（1.0で頭打ち）を
+/// `1 - (1 - w)(1 - c)`で合成します。どちらか一方だけが厳しい日も、
+/// 両方が重なる日も同じ尺度で比べられます。
+pub fn handling_difficulty(weather: Weather, wind: &Wind) -> f32 {
+    let wet = weather.pass_difficulty();
+    let cross = (wind.crosswind() / CROSSWIND_FULL_DIFFICULTY_MS).clamp(0.0, 1.0);
+    (1.0 - (1.0 - wet) * (1.0 - cross)).clamp(0.0, 1.0)
+}
+
+/// フォワード中心のプレーに切り替えるハンドリングの難しさ（`GameState::handling_difficulty`）
+///
+/// 無風の雨天（0.3）と同じ難しさです。乾いた日でも横風が強ければ同じように切り替えます。
+pub const POOR_HANDLING_DIFFICULTY: f32 = 0.3;
+
+/// キックの推奨度を最も下げる風速（m/s）
+pub const KICK_WIND_FULL_PENALTY_MS: f32 = 20.0;
+
+/// フィールドポジション、濡れたボール、風を合わせたキックの推奨度（0.0-1.0）
+///
+/// フィールドポジションの推奨度`position.kick_preference()`に、晴天を基準とした
+/// 天候の推奨度の差`weather.kick_preference() - Weather::Sunny.kick_preference()`
+/// （雨天はハンドリングミスを避けるため+0.3、強風は-0.1、雨+風は-0.2）を加え、
+/// 風速による精度の低下`0.2 × speed / KICK_WIND_FULL_PENALTY_MS`（0.2で頭打ち）を
+/// 引いて0.0-1.0に収めます。晴天の無風ではフィールドポジションの推奨度と同じです。
+pub fn combined_kick_preference(position: FieldPosition, weather: Weather, wind: &Wind) -> f32 {
+    let wet_ball = weather.kick_preference() - Weather::Sunny.kick_preference();
+    let wind_penalty = 0.2 * (wind.speed / KICK_WIND_FULL_PENALTY_MS).clamp(0.0, 1.0);
+    (position.kick_preference() + wet_ball - wind_penalty).clamp(0.0, 1.0)
 }
 
 /// 疲労度レベル
@@ -142,17 +342,37 @@ impl FatigueLevel {
             FatigueLevel::Exhausted => 0.4,
         }
     }
+
+    /// レベルを代表する疲労度（各範囲の中央、0.0-1.0）
+    ///
+    /// `Fresh`=0.1、`Moderate`=0.3、`Tired`=0.55、`Exhausted`=0.85で、
+    /// `from_percentage`に渡すと元のレベルに戻ります。
+    pub fn representative_fraction(&self) -> f32 {
+        match self {
+            FatigueLevel::Fresh => 0.1,
+            FatigueLevel::Moderate => 0.3,
+            FatigueLevel::Tired => 0.55,
+            FatigueLevel::Exhausted => 0.85,
+        }
+    }
 }
 
 /// ゲームルール
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameRules {
     /// 15人制ラグビー（80分）
+    #[cfg_attr(feature = "serde", serde(rename = "Fifteens"))]
     Fifteens,
     /// 7人制ラグビー（14分）
+    #[cfg_attr(feature = "serde", serde(rename = "Sevens"))]
     Sevens,
     /// 10人制ラグビー
+    #[cfg_attr(feature = "serde", serde(rename = "Tens"))]
     Tens,
+    /// `RuleSetRegistry`に登録したカスタムルール
+    #[cfg_attr(feature = "serde", serde(rename = "Custom"))]
+    Custom(std::sync::Arc<RuleSet>),
 }
 
 impl GameRules {
@@ -162,6 +382,46 @@ impl GameRules {
             GameRules::Fifteens => 80 * 60,
             GameRules::Sevens => 14 * 60,
             GameRules::Tens => 60 * 60,
+            GameRules::Custom(rule_set) => rule_set.duration_secs,
+        }
+    }
+
+    /// 全体の疲労度を求めるときのフォワードの重み
+    ///
+    /// バックスが走る距離の長い少人数制ほど小さくなります。
+    /// カスタムルールは15人制と同じ0.6です。
+    pub fn forward_fatigue_weight(&self) -> f32 {
+        match self {
+            GameRules::Fifteens | GameRules::Custom(_) => 0.6,
+            GameRules::Tens => 0.5,
+            GameRules::Sevens => 0.4,
+        }
+    }
+
+    /// 1チームの出場人数
+    pub fn players(&self) -> u32 {
+        match self {
+            GameRules::Fifteens => 15,
+            GameRules::Sevens => 7,
+            GameRules::Tens => 10,
+            GameRules::Custom(rule_set) => rule_set.players,
+        }
+    }
+
+    /// 交代できる人数
+    pub fn substitutions(&self) -> u32 {
+        match self {
+            GameRules::Fifteens => 8,
+            GameRules::Sevens | GameRules::Tens => 5,
+            GameRules::Custom(rule_set) => rule_set.substitutions,
+        }
+    }
+
+    /// 得点の種類ごとの点数（組み込みルールはWorld Rugbyの規定）
+    pub fn scoring(&self) -> ScoringValues {
+        match self {
+            GameRules::Fifteens | GameRules::Sevens | GameRules::Tens => ScoringValues::default(),
+            GameRules::Custom(rule_set) => rule_set.scoring,
         }
     }
 
@@ -171,12 +431,200 @@ impl GameRules {
             GameRules::Fifteens => 0.0125, // 80分で100%
             GameRules::Sevens => 0.05,     // 14分でも高強度
             GameRules::Tens => 0.0167,
+            GameRules::Custom(rule_set) => rule_set.fatigue_rate,
+        }
+    }
+}
+
+/// 得点の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScoreKind {
+    /// トライ
+    Try,
+    /// トライ後のコンバージョン
+    Conversion,
+    /// ペナルティゴール
+    PenaltyGoal,
+    /// ドロップゴール
+    DropGoal,
+}
+
+/// 得点の種類ごとの点数
+///
+/// 既定値はWorld Rugbyの規定（トライ5点、コンバージョン2点、
+/// ペナルティゴール3点、ドロップゴール3点）です。コンバージョンのない
+/// 形式では`conversion_points`を0にします。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoringValues {
+    /// トライ
+    pub try_points: u32,
+    /// コンバージョン（0ならコンバージョンなし）
+    pub conversion_points: u32,
+    /// ペナルティゴール
+    pub penalty_points: u32,
+    /// ドロップゴール
+    pub drop_points: u32,
+}
+
+impl Default for ScoringValues {
+    fn default() -> Self {
+        Self {
+            try_points: 5,
+            conversion_points: 2,
+            penalty_points: 3,
+            drop_points: 3,
+        }
+    }
+}
+
+impl ScoringValues {
+    /// 得点の種類の点数
+    pub fn points(&self, kind: ScoreKind) -> u32 {
+        match kind {
+            ScoreKind::Try => self.try_points,
+            ScoreKind::Conversion => self.conversion_points,
+            ScoreKind::PenaltyGoal => self.penalty_points,
+            ScoreKind::DropGoal => self.drop_points,
+        }
+    }
+
+    /// コンバージョン成功のトライの点数
+    pub fn converted_try(&self) -> u32 {
+        self.try_points + self.conversion_points
+    }
+
+    /// キック（ペナルティゴールまたはドロップゴール）1本の点数
+    pub fn kick_points(&self) -> u32 {
+        self.penalty_points.max(self.drop_points)
+    }
+}
+
+/// 実行時に登録できるルールセット
+///
+/// 名前を含むすべての項目が等しい場合に同一とみなします
+/// （`fatigue_rate`はビット列で比較するため、`NaN`同士も等しくなります）。
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleSet {
+    /// ルールセット名（例: "beach", "touch"）
+    pub name: String,
+    /// 試合時間（秒）
+    pub duration_secs: u32,
+    /// 疲労の蓄積速度（1分あたりの疲労度増加）
+    pub fatigue_rate: f32,
+    /// 1チームの出場人数
+    pub players: u32,
+    /// 交代できる人数
+    pub substitutions: u32,
+    /// 得点の種類ごとの点数（シナリオファイルでは省略可で、省略時はWorld Rugbyの規定）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scoring: ScoringValues,
+}
+
+impl PartialEq for RuleSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.duration_secs == other.duration_secs
+            && self.fatigue_rate.to_bits() == other.fatigue_rate.to_bits()
+            && self.players == other.players
+            && self.substitutions == other.substitutions
+            && self.scoring == other.scoring
+    }
+}
+
+impl Eq for RuleSet {}
+
+impl std::hash::Hash for RuleSet {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.duration_secs.hash(state);
+        self.fatigue_rate.to_bits().hash(state);
+        self.players.hash(state);
+        self.substitutions.hash(state);
+        self.scoring.hash(state);
+    }
+}
+
+/// 組み込みルールの名前（`RuleSetRegistry`には登録できない）
+pub const BUILTIN_RULE_NAMES: &[&str] = &["fifteens", "sevens", "tens"];
+
+/// 組み込みルールと同じ名前のルールセットを登録しようとしたときのエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservedRuleSetName {
+    /// 指定されたルールセット名
+    pub name: String,
+}
+
+impl std::fmt::Display for ReservedRuleSetName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "組み込みルールの名前は登録できません: {} (予約済み: {})",
+            self.name,
+            BUILTIN_RULE_NAMES.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ReservedRuleSetName {}
+
+/// ルールセットの登録簿
+///
+/// 組み込みの`fifteens`、`sevens`、`tens`に加えて、設定から読み込んだ
+/// ルールセットを名前で登録・検索できます。組み込みの名前は予約されており、
+/// 同じ名前での登録は`ReservedRuleSetName`エラーになります。登録したルールセットは
+/// `GameRules::Custom`と`Arc`で共有されるため、登録簿を破棄した後も
+/// そのルールを使っている状態がある間は保持され、使われなくなった時点で解放されます。
+/// 同じ名前で再登録すると以降の検索は新しい定義を返します
+/// （既に取得した`GameRules`は古い定義のままです）。
+#[derive(Debug, Default)]
+pub struct RuleSetRegistry {
+    custom: std::collections::HashMap<String, std::sync::Arc<RuleSet>>,
+}
+
+impl RuleSetRegistry {
+    /// 組み込みルールのみを持つ登録簿を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ルールセットを登録し、対応する`GameRules`を返す
+    ///
+    /// 名前が組み込みルールと同じ場合は登録せずにエラーを返します。
+    pub fn register(&mut self, rule_set: RuleSet) -> Result<GameRules, ReservedRuleSetName> {
+        if BUILTIN_RULE_NAMES.contains(&rule_set.name.as_str()) {
+            return Err(ReservedRuleSetName {
+                name: rule_set.name,
+            });
+        }
+        let rule_set = std::sync::Arc::new(rule_set);
+        self.custom
+            .insert(rule_set.name.clone(), std::sync::Arc::clone(&rule_set));
+        Ok(GameRules::Custom(rule_set))
+    }
+
+    /// 名前からルールを検索
+    pub fn get(&self, name: &str) -> Option<GameRules> {
+        match name {
+            "fifteens" => Some(GameRules::Fifteens),
+            "sevens" => Some(GameRules::Sevens),
+            "tens" => Some(GameRules::Tens),
+            _ => self.custom.get(name).cloned().map(GameRules::Custom),
         }
     }
 }
 
+impl From<GameRules> for Duration {
+    /// 試合時間
+    fn from(rules: GameRules) -> Self {
+        Duration::from_secs(rules.match_duration_secs() as u64)
+    }
+}
+
 /// スコア状況
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Score {
     /// 自チームの得点
     pub own: u32,
@@ -185,37 +633,237 @@ pub struct Score {
 }
 
 impl Score {
+    /// リードしているチーム（同点なら`None`）
+    pub fn leader(&self) -> Option<Team> {
+        match self.own.cmp(&self.opposition) {
+            std::cmp::Ordering::Greater => Some(Team::Own),
+            std::cmp::Ordering::Less => Some(Team::Opposition),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
     /// 点差（正の値=リード、負の値=ビハインド）
     pub fn difference(&self) -> i32 {
         self.own as i32 - self.opposition as i32
     }
 
     /// 緊急性評価（0.0-1.0）
+    ///
+    /// `state`によるスコア状況の分類ごとに決まります。
     pub fn urgency(&self, time_remaining_secs: u32) -> f32 {
-        let diff = self.difference();
-        let minutes_left = time_remaining_secs as f32 / 60.0;
-
-        if diff > 14 {
+        match self.state(time_remaining_secs) {
             // 大量リード：守りのプレー
-            0.2
-        } else if diff > 7 {
+            ScoreState::CommandingLead => 0.2,
             // リード：安定したプレー
-            0.4
-        } else if diff.abs() <= 7 {
+            ScoreState::Lead => 0.4,
             // 接戦：バランス
-            0.6
-        } else if diff < -7 && minutes_left < 10.0 {
+            ScoreState::Close => 0.6,
+            // ビハインド：やや緊急
+            ScoreState::Behind => 0.7,
             // ビハインド＆残り時間少ない：緊急
-            0.9
+            ScoreState::DesperateBehind => 0.9,
+        }
+    }
+
+    /// 逆転に必要な得点の組み立て（World Rugbyの点数）
+    ///
+    /// 1回の得点に平均3分かかるとし、ペナルティゴール/ドロップゴールを3点、
+    /// コンバージョン成功のトライを7点として必要な得点回数を求めます。
+    ///
+    /// - 0-3点差: ペナルティゴール1本（同点なら勝ち越し）
+    /// - 4-7点差: コンバージョン成功のトライ1本（5点差ならトライのみで同点）
+    /// - 8-10点差: トライとペナルティゴール
+    /// - 11-14点差: トライ2本
+    /// - 15点差以上: 3回以上の得点
+    ///
+    /// リードしている場合は`Comfortable`、必要な得点回数に残り時間が
+    /// 足りない場合は`OutOfReach`を返します。
+    pub fn scores_needed(&self, time_remaining_secs: u32) -> ScorePlan {
+        self.scores_needed_with(time_remaining_secs, &ScoringValues::default())
+    }
+
+    /// 指定した点数での逆転に必要な得点の組み立て
+    ///
+    /// `scores_needed`の点差の区切りを、キック1本（`kick_points`）と
+    /// コンバージョン成功のトライ（`converted_try`）の点数から求めます。
+    pub fn scores_needed_with(
+        &self,
+        time_remaining_secs: u32,
+        scoring: &ScoringValues,
+    ) -> ScorePlan {
+        const SECS_PER_SCORE: u32 = 3 * 60;
+
+        let deficit = -self.difference();
+        let kick = scoring.kick_points() as i32;
+        let converted_try = scoring.converted_try() as i32;
+        let plan = if deficit < 0 {
+            return ScorePlan::Comfortable;
+        } else if deficit <= kick {
+            ScorePlan::PenaltyGoal
+        } else if deficit <= converted_try {
+            ScorePlan::OneTry
+        } else if deficit <= converted_try + kick {
+            ScorePlan::TryAndPenalty
+        } else if deficit <= 2 * converted_try {
+            ScorePlan::TwoTries
         } else {
-            // ビハインド：やや緊急
-            0.7
+            ScorePlan::ThreeOrMoreScores
+        };
+
+        if plan.scores() * SECS_PER_SCORE > time_remaining_secs.max(SECS_PER_SCORE) {
+            ScorePlan::OutOfReach
+        } else {
+            plan
+        }
+    }
+
+    /// 点差と残り時間によるスコア状況の分類
+    ///
+    /// - `CommandingLead`: 15点差以上のリード
+    /// - `Lead`: 8-14点差のリード
+    /// - `Close`: 7点差以内（同点を含む）
+    /// - `DesperateBehind`: 8点差以上のビハインドで残り10分未満
+    /// - `Behind`: 8点差以上のビハインド（残り10分以上）
+    ///
+    /// 境界は`ScoreState::LEAD_FROM`、`ScoreState::COMMANDING_LEAD_FROM`、
+    /// `ScoreState::LATE_UNDER_SECS`で決まります。
+    pub fn state(&self, time_remaining_secs: u32) -> ScoreState {
+        let diff = self.difference();
+        if diff >= ScoreState::COMMANDING_LEAD_FROM {
+            ScoreState::CommandingLead
+        } else if diff >= ScoreState::LEAD_FROM {
+            ScoreState::Lead
+        } else if diff > -ScoreState::LEAD_FROM {
+            ScoreState::Close
+        } else if time_remaining_secs < ScoreState::LATE_UNDER_SECS {
+            ScoreState::DesperateBehind
+        } else {
+            ScoreState::Behind
+        }
+    }
+
+    /// 僅差のリードで試合の最終盤か
+    ///
+    /// 境界は`ScoreState::NARROW_LEAD_UP_TO`と`ScoreState::CLOSING_UNDER_SECS`で決まります。
+    pub fn is_narrow_lead_closing(&self, time_remaining_secs: u32) -> bool {
+        (1..=ScoreState::NARROW_LEAD_UP_TO).contains(&self.difference())
+            && time_remaining_secs < ScoreState::CLOSING_UNDER_SECS
+    }
+
+    /// フィールドポジションを考慮した緊急性評価（0.0-1.0）
+    ///
+    /// 終盤（残りが`ScoreState::LATE_UNDER_SECS`未満）にビハインドの場合、
+    /// 点差が`Close`の範囲でも得点圏に近いほど緊急性を高め、
+    /// 自陣深くでは無理なプレーで失点しないよう緊急性を抑えます。
+    /// それ以外は`urgency`と同じ値です。
+    pub fn contextual_urgency(&self, time_remaining_secs: u32, position: FieldPosition) -> f32 {
+        let base = self.urgency(time_remaining_secs);
+        let trailing_late =
+            self.difference() < 0 && time_remaining_secs < ScoreState::LATE_UNDER_SECS;
+        if !trailing_late {
+            return base;
+        }
+
+        let factor = match position {
+            FieldPosition::Own22 => 0.7,
+            FieldPosition::OwnHalf => 0.85,
+            FieldPosition::Midfield => 1.0,
+            FieldPosition::OppositionHalf => 1.1,
+            FieldPosition::Opposition22 => 1.2,
+        };
+        (base * factor).min(1.0)
+    }
+}
+
+/// チームごとに得点を足し合わせる（`own`同士、`opposition`同士）
+impl std::ops::Add for Score {
+    type Output = Score;
+
+    fn add(self, other: Score) -> Score {
+        Score {
+            own: self.own + other.own,
+            opposition: self.opposition + other.opposition,
+        }
+    }
+}
+
+impl std::ops::AddAssign for Score {
+    fn add_assign(&mut self, other: Score) {
+        *self = *self + other;
+    }
+}
+
+/// スコア状況の分類（境界は`Score::state`を参照）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScoreState {
+    /// 大量リード
+    CommandingLead,
+    /// リード
+    Lead,
+    /// 接戦
+    Close,
+    /// ビハインド
+    Behind,
+    /// 残り時間の少ないビハインド
+    DesperateBehind,
+}
+
+impl ScoreState {
+    /// `Lead`になる最小のリード（同じ点差のビハインドから`Behind`）
+    pub const LEAD_FROM: i32 = 8;
+    /// `CommandingLead`になる最小のリード
+    pub const COMMANDING_LEAD_FROM: i32 = 15;
+    /// 終盤とみなす残り時間（秒、未満）。ビハインドは`DesperateBehind`になる
+    pub const LATE_UNDER_SECS: u32 = 600;
+    /// 僅差のリードとみなす最大の点差（1点以上、この値以下）
+    pub const NARROW_LEAD_UP_TO: i32 = 6;
+    /// 僅差のリードを時間を使って守る残り時間（秒、未満）
+    pub const CLOSING_UNDER_SECS: u32 = 180;
+
+    /// リードの大きい順のすべての分類
+    pub const ALL: [ScoreState; 5] = [
+        ScoreState::CommandingLead,
+        ScoreState::Lead,
+        ScoreState::Close,
+        ScoreState::Behind,
+        ScoreState::DesperateBehind,
+    ];
+}
+
+/// 逆転に向けた得点の組み立て
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScorePlan {
+    /// リードしている
+    Comfortable,
+    /// ペナルティゴール（またはドロップゴール）1本で足りる
+    PenaltyGoal,
+    /// トライ1本が必要
+    OneTry,
+    /// トライとペナルティゴールが必要
+    TryAndPenalty,
+    /// トライ2本が必要
+    TwoTries,
+    /// 3回以上の得点が必要
+    ThreeOrMoreScores,
+    /// 残り時間では必要な得点回数に届かない
+    OutOfReach,
+}
+
+impl ScorePlan {
+    /// 必要な得点回数
+    pub fn scores(&self) -> u32 {
+        match self {
+            ScorePlan::Comfortable | ScorePlan::OutOfReach => 0,
+            ScorePlan::PenaltyGoal | ScorePlan::OneTry => 1,
+            ScorePlan::TryAndPenalty | ScorePlan::TwoTries => 2,
+            ScorePlan::ThreeOrMoreScores => 3,
         }
     }
 }
 
 /// チーム全体の疲労状態
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TeamFatigue {
     /// フォワードの平均疲労度（0.0-1.0）
     pub forwards: f32,
@@ -224,25 +872,138 @@ pub struct TeamFatigue {
 }
 
 impl TeamFatigue {
-    /// 全体の疲労度
+    /// 全体の疲労度（フォワード0.6、バックス0.4の重み）
     pub fn overall(&self) -> f32 {
-        self.forwards * 0.6 + self.backs * 0.4 // フォワードの方が重要
+        self.overall_weighted(0.6) // フォワードの方が重要
     }
 
-    /// 疲労度レベル
+    /// フォワードの重みを指定した全体の疲労度（バックスの重みは`1 - forward_weight`）
+    pub fn overall_weighted(&self, forward_weight: f32) -> f32 {
+        self.forwards * forward_weight + self.backs * (1.0 - forward_weight)
+    }
+
+    /// 疲労度レベル（`overall`と同じ既定の重み）
     pub fn level(&self) -> FatigueLevel {
-        FatigueLevel::from_percentage(self.overall())
+        self.level_weighted(0.6)
+    }
+
+    /// フォワードの重みを指定した疲労度レベル
+    pub fn level_weighted(&self, forward_weight: f32) -> FatigueLevel {
+        FatigueLevel::from_percentage(self.overall_weighted(forward_weight))
     }
+
+    /// フォワードとバックスの疲労の偏り（正の値=フォワードの方が疲れている）
+    pub fn imbalance(&self) -> f32 {
+        self.forwards - self.backs
+    }
+}
+
+/// 選手のユニット
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayerRole {
+    /// フォワード
+    #[cfg_attr(feature = "serde", serde(rename = "Forward"))]
+    Forward,
+    /// バックス
+    #[cfg_attr(feature = "serde", serde(rename = "Back"))]
+    Back,
+}
+
+/// 一時的に退いている選手（出血による交代やHIAなど）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TempOff {
+    /// 退いている選手のユニット
+    pub position: PlayerRole,
+    /// 復帰する時刻（経過秒）
+    pub returns_at_secs: u32,
+}
+
+/// レッドカードの扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CardLaw {
+    /// 退場した選手は試合終了まで補充されない
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "PermanentRed"))]
+    PermanentRed,
+    /// 退場から20分経過すると別の選手で補充できる
+    #[cfg_attr(feature = "serde", serde(rename = "ReplaceableRed20"))]
+    ReplaceableRed20,
+}
+
+impl CardLaw {
+    /// 補充可能になるまでの時間（秒、`None`は補充不可）
+    pub fn replacement_after_secs(&self) -> Option<u32> {
+        match self {
+            CardLaw::PermanentRed => None,
+            CardLaw::ReplaceableRed20 => Some(20 * 60),
+        }
+    }
+}
+
+/// 試合をしている2チームのどちらか
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Team {
+    /// 自チーム
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "Own"))]
+    Own,
+    /// 相手チーム
+    #[cfg_attr(feature = "serde", serde(rename = "Opposition"))]
+    Opposition,
+}
+
+/// ボールを持っているチーム
+pub type Possession = Team;
+
+/// 得点のチャンスの度合い（基準は`GameState::scoring_opportunity`を参照）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScoringOpportunity {
+    /// すぐに得点を狙える
+    Immediate,
+    /// 敵陣で攻撃を継続中
+    Building,
+    /// チャンスなし
+    None,
 }
 
 /// ゲーム全体の状態
-#[derive(Debug, Clone)]
+///
+/// `serde`フィーチャーを有効にするとシナリオファイルとして読み書きできます。
+/// 手書きしやすいように、スコアと疲労度はトップレベルのフィールドに展開されます:
+///
+/// ```json
+/// {
+///   "rules": "Fifteens",
+///   "elapsed_time_secs": 4500,
+///   "own_score": 21,
+///   "opp_score": 24,
+///   "fw_fatigue": 0.65,
+///   "bk_fatigue": 0.5,
+///   "position": "OwnHalf",
+///   ...
+/// }
+/// ```
+///
+/// その他のフィールドは構造体と同じ名前・入れ子構造です。
+///
+/// 列挙型（`FieldPosition`、`Weather`、`GameRules`、`PlayerRole`、`CardLaw`、`Team`、
+/// `TacticalDecision`、`LineoutTarget`、`Direction`、`KickType`）の値は、各バリアントに
+/// `serde(rename)`で明示したタグ（`"OwnHalf"`、`"Rainy"`など）で読み書きします。
+/// これらのタグは保存したシナリオやリプレイとの互換性のための安定した識別子で、
+/// バリアントの並び替えや名前の変更では変わりません。
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameState {
     /// 試合ルール
     pub rules: GameRules,
     /// 経過時間（秒）
     pub elapsed_time_secs: u32,
-    /// スコア
+    /// スコア（シナリオファイルでは`own_score`/`opp_score`）
+    #[cfg_attr(feature = "serde", serde(flatten, with = "flat_score"))]
     pub score: Score,
     /// フィールドポジション
     pub position: FieldPosition,
@@ -250,20 +1011,110 @@ pub struct GameState {
     pub weather: Weather,
     /// 風
     pub wind: Wind,
-    /// チーム疲労度
+    /// 攻撃方向（相手ゴールラインの方角、度、0=北、シナリオファイルでは省略可）
+    ///
+    /// `wind.direction`と同じ方位で表し、ハーフタイムで180°入れ替わります。
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub attacking_bearing: f32,
+    /// チーム疲労度（シナリオファイルでは`fw_fatigue`/`bk_fatigue`）
+    #[cfg_attr(feature = "serde", serde(flatten, with = "flat_fatigue"))]
     pub fatigue: TeamFatigue,
+    /// 相手チームの疲労度
+    pub opposition_fatigue: TeamFatigue,
     /// 連続フェーズ数
     pub consecutive_phases: u32,
     /// ペナルティ数（自チーム）
     pub penalties_conceded: u32,
     /// イエローカード人数
     pub yellow_cards: u32,
+    /// 相手チームのイエローカード人数
+    pub opposition_yellow_cards: u32,
+    /// 一時的に退いている選手（シンビンとは別扱い、シナリオファイルでは省略可）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub temporary_off: Vec<TempOff>,
+    /// レッドカードの扱い（シナリオファイルでは省略可）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub card_law: CardLaw,
+    /// レッドカードを受けた時刻（経過秒、シナリオファイルでは省略可）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub red_cards_at_secs: Vec<u32>,
+    /// 自チームボールのラインアウトか（タッチキック後のリスタート）
+    pub lineout_awarded: bool,
+    /// ボールを持っているチーム（シナリオファイルでは省略可、省略時は自チーム）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub possession: Possession,
+    /// 相手のバックスリーによるカウンターアタックの脅威（0.0-1.0、シナリオファイルでは省略可）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub opposition_counterattack_threat: f32,
     /// ディフェンスライン
     pub defense: DefenseLine,
     /// チームメイト
     pub teammates: Teammates,
 }
 
+/// `GameState::score`を`own_score`/`opp_score`として読み書きする
+#[cfg(feature = "serde")]
+mod flat_score {
+    use super::Score;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Flat {
+        own_score: u32,
+        opp_score: u32,
+    }
+
+    pub fn serialize<S: Serializer>(score: &Score, serializer: S) -> Result<S::Ok, S::Error> {
+        Flat {
+            own_score: score.own,
+            opp_score: score.opposition,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Score, D::Error> {
+        let flat = Flat::deserialize(deserializer)?;
+        Ok(Score {
+            own: flat.own_score,
+            opposition: flat.opp_score,
+        })
+    }
+}
+
+/// `GameState::fatigue`を`fw_fatigue`/`bk_fatigue`として読み書きする
+#[cfg(feature = "serde")]
+mod flat_fatigue {
+    use super::TeamFatigue;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Flat {
+        fw_fatigue: f32,
+        bk_fatigue: f32,
+    }
+
+    pub fn serialize<S: Serializer>(
+        fatigue: &TeamFatigue,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Flat {
+            fw_fatigue: fatigue.forwards,
+            bk_fatigue: fatigue.backs,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<TeamFatigue, D::Error> {
+        let flat = Flat::deserialize(deserializer)?;
+        Ok(TeamFatigue {
+            forwards: flat.fw_fatigue,
+            backs: flat.bk_fatigue,
+        })
+    }
+}
+
 impl GameState {
     /// 残り時間（秒）
     pub fn time_remaining_secs(&self) -> u32 {
@@ -271,75 +1122,783 @@ impl GameState {
         total.saturating_sub(self.elapsed_time_secs)
     }
 
+    /// 現時点で一時的に退いている選手の人数
+    pub fn players_temporarily_off(&self) -> u32 {
+        self.temporary_off
+            .iter()
+            .filter(|off| off.returns_at_secs > self.elapsed_time_secs)
+            .count() as u32
+    }
+
+    /// 現時点でレッドカードにより欠けている人数
+    ///
+    /// `CardLaw::ReplaceableRed20`では退場から20分経過すると補充されます。
+    pub fn players_red_carded(&self) -> u32 {
+        self.red_cards_at_secs
+            .iter()
+            .filter(|&&at| match self.card_law.replacement_after_secs() {
+                Some(after) => self.elapsed_time_secs < at + after,
+                None => true,
+            })
+            .count() as u32
+    }
+
+    /// ピッチ上の自チームの人数
+    pub fn effective_players(&self) -> u32 {
+        self.rules.players().saturating_sub(
+            self.yellow_cards + self.players_red_carded() + self.players_temporarily_off(),
+        )
+    }
+
+    /// 数的優位（正の値=自チームが多い）
+    pub fn numbers_advantage(&self) -> i32 {
+        let opposition = self
+            .rules
+            .players()
+            .saturating_sub(self.opposition_yellow_cards);
+        self.effective_players() as i32 - opposition as i32
+    }
+
+    /// 試合時間
+    pub fn match_duration(&self) -> Duration {
+        Duration::from(self.rules.clone())
+    }
+
+    /// 経過時間
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs(self.elapsed_time_secs as u64)
+    }
+
+    /// 残り時間
+    pub fn remaining(&self) -> Duration {
+        Duration::from_secs(self.time_remaining_secs() as u64)
+    }
+
+    /// ルールに応じた重みで求めたチーム全体の疲労度
+    pub fn fatigue_overall(&self) -> f32 {
+        self.fatigue
+            .overall_weighted(self.rules.forward_fatigue_weight())
+    }
+
+    /// ルールに応じた重みで求めたチームの疲労度レベル
+    pub fn fatigue_level(&self) -> FatigueLevel {
+        self.fatigue
+            .level_weighted(self.rules.forward_fatigue_weight())
+    }
+
+    /// ルールに応じた重みで求めた相手チーム全体の疲労度
+    pub fn opposition_fatigue_overall(&self) -> f32 {
+        self.opposition_fatigue
+            .overall_weighted(self.rules.forward_fatigue_weight())
+    }
+
+    /// 得点のチャンスの度合い
+    ///
+    /// - `Immediate`: 敵陣22m内で、ディフェンスにギャップがあり、バックスの準備ができている
+    /// - `Building`: 敵陣（敵陣22m内を含む）で3フェーズ以上連続して攻めている
+    /// - `None`: それ以外
+    pub fn scoring_opportunity(&self) -> ScoringOpportunity {
+        let in_opposition_half = matches!(
+            self.position,
+            FieldPosition::OppositionHalf | FieldPosition::Opposition22
+        );
+        if self.position == FieldPosition::Opposition22
+            && self.defense.optimal_direction().is_some()
+            && self.teammates.backs_ready
+        {
+            ScoringOpportunity::Immediate
+        } else if in_opposition_half && self.consecutive_phases >= 3 {
+            ScoringOpportunity::Building
+        } else {
+            ScoringOpportunity::None
+        }
+    }
+
     /// 時間のプレッシャー（0.0-1.0）
     pub fn time_pressure(&self) -> f32 {
         let remaining = self.time_remaining_secs() as f32;
         let total = self.rules.match_duration_secs() as f32;
         1.0 - (remaining / total)
     }
-}
 
-/// ディフェンスラインの状態
-#[derive(Debug, Clone)]
-pub struct DefenseLine {
-    pub pressure: bool,
-    pub gap_on_left: bool,
-    pub gap_on_right: bool,
-    /// ディフェンスの整列度（0.0-1.0）
-    pub alignment: f32,
-}
+    /// 試合のルールの点数での逆転に必要な得点の組み立て
+    pub fn scores_needed(&self) -> ScorePlan {
+        self.score
+            .scores_needed_with(self.time_remaining_secs(), &self.rules.scoring())
+    }
 
-/// チームメイトの状態
-#[derive(Debug, Clone)]
-pub struct Teammates {
+    /// 攻撃方向に対する向かい風成分（m/s、負の値は追い風）
+    pub fn headwind(&self) -> f32 {
+        self.wind.headwind_toward(self.attacking_bearing)
+    }
+
+    /// 攻撃方向に対する横風成分（m/s）
+    pub fn crosswind(&self) -> f32 {
+        self.wind.crosswind_toward(self.attacking_bearing)
+    }
+
+    /// 攻撃方向に対するハンドリングの難しさ（`handling_difficulty`、0.0-1.0）
+    ///
+    /// パスに関わる判断や評価はすべてこの値を使います。
+    pub fn handling_difficulty(&self) -> f32 {
+        let relative = Wind {
+            direction: self.wind.direction - self.attacking_bearing,
+            ..self.wind
+        };
+        handling_difficulty(self.weather, &relative)
+    }
+
+    /// 判断の難しさ（0.0-1.0、シナリオを易しい順に並べるための目安）
+    ///
+    /// 判断の内容とは独立に、次の要素（各0.0-1.0）を重み付きで平均します。
+    ///
+    /// - フィールドポジションのリスク（`FieldPosition::risk_level`）: 25%
+    /// - ディフェンスのプレッシャー（プレッシャーがあれば1.0、なければ整列度の半分）: 20%
+    /// - 天候と横風によるハンドリングの難しさ（`handling_difficulty`）: 15%
+    /// - チームの疲労度（`fatigue_overall`）: 15%
+    /// - スコアの緊急性（`Score::urgency`）: 25%
+    pub fn difficulty_rating(&self) -> f32 {
+        let defensive_pressure = if self.defense.pressure() {
+            1.0
+        } else {
+            self.defense.alignment * 0.5
+        };
+        let rating = 0.25 * self.position.risk_level()
+            + 0.20 * defensive_pressure
+            + 0.15 * self.handling_difficulty()
+            + 0.15 * self.fatigue_overall()
+            + 0.25 * self.score.urgency(self.time_remaining_secs());
+        rating.clamp(0.0, 1.0)
+    }
+
+    /// 左右を反転した状態
+    ///
+    /// 左右のギャップとプレッシャーの側を入れ替え、風向きを攻撃方向の軸に対して反転します。
+    /// それ以外のフィールド（時間、スコア、位置、疲労度、人数など）は変わりません。
+    /// 左右のギャップが片側だけの状態では、反転した状態に対する判断は
+    /// 元の判断を`TacticalDecision::mirror`したものになります
+    /// （両側にギャップがある場合は`DefenseLine::optimal_direction`に従います）。
+    pub fn mirror(&self) -> GameState {
+        let mut mirrored = self.clone();
+        mirrored.defense.gap_on_left = self.defense.gap_on_right;
+        mirrored.defense.gap_on_right = self.defense.gap_on_left;
+        mirrored.defense.pressure_side = self.defense.pressure_side.map(|side| side.mirror());
+        mirrored.wind.direction =
+            (2.0 * self.attacking_bearing - self.wind.direction).rem_euclid(360.0);
+        mirrored
+    }
+
+    /// 2つの状態の間を補間した状態（アニメーション用）
+    ///
+    /// `t`は0.0-1.0に丸めます。連続的なフィールドは線形に補間し、
+    /// それ以外は近い方の状態（`t < 0.5`なら`self`、それ以外は`other`）の値を使います。
+    ///
+    /// - 線形補間: 経過時間（秒に丸める）、フィールドポジション（範囲の中点のメートルを
+    ///   補間して`FieldPosition::from_meters`）、両チームの疲労度、風速と最大風速、
+    ///   風向きと攻撃方向（近い回り方向）、カウンターアタックの脅威、ディフェンスの整列度
+    /// - 近い方の値: スコア（`t >= 0.5`で切り替わる）、天候、ルール、カード、
+    ///   連続フェーズ数、ボール保持、ディフェンスのギャップ、チームメイトなど
+    pub fn lerp(&self, other: &GameState, t: f32) -> GameState {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+
+        let mut state = if t < 0.5 { self.clone() } else { other.clone() };
+        state.elapsed_time_secs = mix(
+            self.elapsed_time_secs as f32,
+            other.elapsed_time_secs as f32,
+        )
+        .round() as u32;
+        state.position =
+            FieldPosition::from_meters(mix(self.position.midpoint(), other.position.midpoint()));
+        state.fatigue = TeamFatigue {
+            forwards: mix(self.fatigue.forwards, other.fatigue.forwards),
+            backs: mix(self.fatigue.backs, other.fatigue.backs),
+        };
+        state.opposition_fatigue = TeamFatigue {
+            forwards: mix(
+                self.opposition_fatigue.forwards,
+                other.opposition_fatigue.forwards,
+            ),
+            backs: mix(
+                self.opposition_fatigue.backs,
+                other.opposition_fatigue.backs,
+            ),
+        };
+        let mix_angle = |a: f32, b: f32| {
+            let turn = (b - a + 180.0).rem_euclid(360.0) - 180.0;
+            (a + turn * t).rem_euclid(360.0)
+        };
+        state.wind = Wind {
+            speed: mix(self.wind.speed, other.wind.speed),
+            direction: mix_angle(self.wind.direction, other.wind.direction),
+            gust: mix(self.wind.gust, other.wind.gust),
+        };
+        state.attacking_bearing = mix_angle(self.attacking_bearing, other.attacking_bearing);
+        state.opposition_counterattack_threat = mix(
+            self.opposition_counterattack_threat,
+            other.opposition_counterattack_threat,
+        );
+        state.defense.alignment = mix(self.defense.alignment, other.defense.alignment);
+        state
+    }
+
+    /// 判断に関わるフィールドを量子化したハッシュ値
+    ///
+    /// 判断が変わらない程度の揺らぎを無視して状態の変化を検出するために使います。
+    /// 対象フィールドと量子化の単位:
+    ///
+    /// - ルール、スコア、フィールドポジション、天候: そのまま
+    /// - 経過時間: 30秒単位
+    /// - 風速: 1m/s単位、風向きと攻撃方向: 10°単位
+    /// - 疲労度（自チーム/相手のFW/BK）、ディフェンスの整列度: 5%単位
+    ///
+    /// 風速、疲労度、整列度の単位は`QuantizationConfig`の既定値です。
+    /// - 連続フェーズ数、ペナルティ数、イエローカード人数（自チーム/相手）: そのまま
+    /// - ラインアウトの獲得、ボール保持: そのまま
+    /// - 相手のカウンターアタックの脅威: 5%単位
+    /// - 一時的に退いている選手とレッドカードで欠けている人数（現時点）: そのまま
+    /// - ディフェンスのプレッシャーの側/ギャップ、チームメイトの準備状態とサポート数: そのまま
+    ///
+    /// ハッシュ値は同一プロセス内での比較用で、永続化には向きません。
+    pub fn snapshot_hash(&self) -> u64 {
+        self.snapshot_hash_with(&QuantizationConfig::default())
+    }
+
+    /// 量子化の単位を指定した`snapshot_hash`
+    ///
+    /// 単位を細かくするとリプレイの差分のようにわずかな変化も区別でき、
+    /// 粗くすると近い状態が同じハッシュ値にまとまりキャッシュに当たりやすくなります。
+    /// 0以下（または`NaN`）の単位を指定した項目は量子化せず、値をそのまま区別します。
+    pub fn snapshot_hash_with(&self, quantization: &QuantizationConfig) -> u64 {
+        use std::hash::{Hash, Hasher};
+
+        // 0以下の単位で割ると全ての値が同じ区間（または未定義の値）になるため、量子化しない
+        let quantize = |value: f32, step: f32| {
+            if step > 0.0 {
+                (value / step).round() as i64
+            } else {
+                i64::from(value.to_bits())
+            }
+        };
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.rules.hash(&mut hasher);
+        (self.elapsed_time_secs / 30).hash(&mut hasher);
+        (self.score.own, self.score.opposition).hash(&mut hasher);
+        self.position.hash(&mut hasher);
+        self.weather.hash(&mut hasher);
+        quantize(self.wind.speed, quantization.wind_step).hash(&mut hasher);
+        quantize(self.wind.direction, 10.0).hash(&mut hasher);
+        quantize(self.attacking_bearing, 10.0).hash(&mut hasher);
+        quantize(self.fatigue.forwards, quantization.fatigue_step).hash(&mut hasher);
+        quantize(self.fatigue.backs, quantization.fatigue_step).hash(&mut hasher);
+        quantize(self.opposition_fatigue.forwards, quantization.fatigue_step).hash(&mut hasher);
+        quantize(self.opposition_fatigue.backs, quantization.fatigue_step).hash(&mut hasher);
+        self.consecutive_phases.hash(&mut hasher);
+        self.penalties_conceded.hash(&mut hasher);
+        self.yellow_cards.hash(&mut hasher);
+        self.opposition_yellow_cards.hash(&mut hasher);
+        self.players_temporarily_off().hash(&mut hasher);
+        self.players_red_carded().hash(&mut hasher);
+        self.lineout_awarded.hash(&mut hasher);
+        self.possession.hash(&mut hasher);
+        quantize(self.opposition_counterattack_threat, 0.05).hash(&mut hasher);
+        (
+            self.defense.pressure_side,
+            self.defense.gap_on_left,
+            self.defense.gap_on_right,
+            self.defense.space_behind_defense,
+        )
+            .hash(&mut hasher);
+        quantize(self.defense.alignment, quantization.alignment_step).hash(&mut hasher);
+        (
+            self.teammates.backs_ready,
+            self.teammates.forwards_ready,
+            self.teammates.support_count,
+        )
+            .hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// `GameState::snapshot_hash_with`の量子化の単位
+///
+/// 既定値は疲労度と整列度が0.05（5%）、風速が1.0m/sです。
+/// 0以下（または`NaN`）の単位はその項目を量子化しない指定として扱い、
+/// わずかな違いも別のハッシュ値になります。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuantizationConfig {
+    /// 疲労度（自チーム/相手のFW/BK）の単位
+    pub fatigue_step: f32,
+    /// ディフェンスの整列度の単位
+    pub alignment_step: f32,
+    /// 風速の単位（m/s）
+    pub wind_step: f32,
+}
+
+impl Default for QuantizationConfig {
+    fn default() -> Self {
+        Self {
+            fatigue_step: 0.05,
+            alignment_step: 0.05,
+            wind_step: 1.0,
+        }
+    }
+}
+
+/// ディフェンスラインの状態
+///
+/// `pressure_side`はディフェンスが激しく前に出てくる側です。`Direction::Center`は
+/// 特定の側に偏らないライン全体のプレッシャーを表します。
+///
+/// シナリオファイルでは旧形式の`"pressure": true`も読み込め、
+/// `pressure_side: Some(Direction::Center)`として扱います（`DefenseLineFormat`）。
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "DefenseLineFormat"))]
+pub struct DefenseLine {
+    /// プレッシャーを受けている側（`None`ならプレッシャーなし）
+    pub pressure_side: Option<Direction>,
+    pub gap_on_left: bool,
+    pub gap_on_right: bool,
+    /// ディフェンスの整列度（0.0-1.0）
+    pub alignment: f32,
+    /// ディフェンスラインの背後にスペースがあるか
+    ///
+    /// フルバックがラインに上がっている、またはラインがフラットな場合に`true`です。
+    /// 前に出てくるライン（`pressure`）と組み合わさると、`choose_kick_type`は
+    /// 敵陣でグラバーを選びます。
+    pub space_behind_defense: bool,
+}
+
+/// シナリオファイルから読み込む`DefenseLine`の形式
+///
+/// `pressure_side`を導入する前の`pressure: bool`も受け付けます。`pressure_side`が
+/// なく`"pressure": true`の場合はライン全体のプレッシャー（`Direction::Center`）、
+/// `false`の場合はプレッシャーなしとします。両方ある場合は`pressure_side`を使います。
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct DefenseLineFormat {
+    #[serde(default)]
+    pressure_side: Option<Direction>,
+    /// 旧形式のプレッシャーの有無
+    #[serde(default)]
+    pressure: Option<bool>,
+    gap_on_left: bool,
+    gap_on_right: bool,
+    alignment: f32,
+    #[serde(default)]
+    space_behind_defense: bool,
+}
+
+#[cfg(feature = "serde")]
+impl From<DefenseLineFormat> for DefenseLine {
+    fn from(format: DefenseLineFormat) -> Self {
+        let legacy = format
+            .pressure
+            .is_some_and(|pressure| pressure)
+            .then_some(Direction::Center);
+        DefenseLine {
+            pressure_side: format.pressure_side.or(legacy),
+            gap_on_left: format.gap_on_left,
+            gap_on_right: format.gap_on_right,
+            alignment: format.alignment,
+            space_behind_defense: format.space_behind_defense,
+        }
+    }
+}
+
+impl DefenseLine {
+    /// プレッシャーを受けているか（側を問わない）
+    pub fn pressure(&self) -> bool {
+        self.pressure_side.is_some()
+    }
+
+    /// 最適な攻撃方向を返す
+    ///
+    /// ギャップがある側の方向、なければ`None`。両側にギャップがある場合は
+    /// プレッシャーの側と反対（ドリフトしている柔らかい側）を選び、
+    /// 片側だけに偏ったプレッシャーがなければ左を優先します。
+    /// ギャップがプレッシャーの側にしかなければ、その側を返します。
+    pub fn optimal_direction(&self) -> Option<Direction> {
+        match (self.gap_on_left, self.gap_on_right) {
+            (true, true) if self.pressure_side == Some(Direction::Left) => Some(Direction::Right),
+            (true, _) => Some(Direction::Left),
+            (false, true) => Some(Direction::Right),
+            (false, false) => None,
+        }
+    }
+
+    /// ギャップとみなす守備者間（またはタッチラインとの間）の距離（m）
+    pub const TRACKING_GAP_THRESHOLD_M: f32 = 10.0;
+
+    /// トラッキングデータ（守備者の横方向の位置）からディフェンスラインを推定
+    ///
+    /// タッチライン（0と`field_width`）を含めた隣接する守備者間の間隔のうち、
+    /// `TRACKING_GAP_THRESHOLD_M`を超えるものをギャップとし、その中点がフィールドの
+    /// 左半分にあれば左、右半分にあれば右のギャップとします。
+    /// 整列度は守備者間の間隔の均一さ（1 - 変動係数）から求めます。
+    /// トラッキングデータからはプレッシャーを判断できないため`pressure_side`は`None`です。
+    pub fn from_tracking(defender_x: &[f32], field_width: f32) -> DefenseLine {
+        let mut xs: Vec<f32> = defender_x
+            .iter()
+            .map(|x| x.clamp(0.0, field_width))
+            .collect();
+        xs.sort_by(f32::total_cmp);
+
+        let mut edges = Vec::with_capacity(xs.len() + 2);
+        edges.push(0.0);
+        edges.extend(&xs);
+        edges.push(field_width);
+
+        let mut gap_on_left = false;
+        let mut gap_on_right = false;
+        for pair in edges.windows(2) {
+            if pair[1] - pair[0] > Self::TRACKING_GAP_THRESHOLD_M {
+                if (pair[0] + pair[1]) / 2.0 < field_width / 2.0 {
+                    gap_on_left = true;
+                } else {
+                    gap_on_right = true;
+                }
+            }
+        }
+
+        let spacings: Vec<f32> = xs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        let alignment = if spacings.is_empty() {
+            0.0
+        } else {
+            let mean = spacings.iter().sum::<f32>() / spacings.len() as f32;
+            let variance =
+                spacings.iter().map(|s| (s - mean).powi(2)).sum::<f32>() / spacings.len() as f32;
+            if mean > 0.0 {
+                (1.0 - variance.sqrt() / mean).clamp(0.0, 1.0)
+            } else {
+                0.0
+            }
+        };
+
+        DefenseLine {
+            pressure_side: None,
+            gap_on_left,
+            gap_on_right,
+            alignment,
+            space_behind_defense: false,
+        }
+    }
+}
+
+/// チームメイトの状態
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Teammates {
     pub backs_ready: bool,
     pub forwards_ready: bool,
     /// サポートプレーヤーの数
     pub support_count: u32,
 }
 
+/// モールを組むのに必要なサポートプレーヤーの数
+pub const MAUL_MIN_SUPPORT: u32 = 3;
+
+impl Teammates {
+    /// 判断を実行できるだけのサポートがあるか
+    ///
+    /// 判断ごとの最低限の条件:
+    ///
+    /// - `Maul`、モールを狙う`Lineout`: フォワードの準備完了かつサポート3人以上
+    /// - `PassSpread`: サポート2人以上（急ぎでないパス展開はバックスの準備完了も必要）
+    /// - `Crash`: フォワードの準備完了
+    /// - `QuickTap`: 走り込むサポートが1人以上
+    /// - `Kick`、`DropGoal`、`Scrum`、その他の`Lineout`: 条件なし
+    pub fn support_adequate_for(&self, decision: &TacticalDecision) -> bool {
+        match decision {
+            TacticalDecision::Maul
+            | TacticalDecision::Lineout {
+                throw_to: LineoutTarget::Maul,
+            } => self.forwards_ready && self.support_count >= MAUL_MIN_SUPPORT,
+            TacticalDecision::PassSpread { rushed, .. } => {
+                (*rushed || self.backs_ready) && self.support_count >= 2
+            }
+            TacticalDecision::Crash => self.forwards_ready,
+            TacticalDecision::QuickTap => self.support_count >= 1,
+            TacticalDecision::Kick { .. }
+            | TacticalDecision::DropGoal
+            | TacticalDecision::Scrum
+            | TacticalDecision::Lineout { .. } => true,
+        }
+    }
+}
+
 /// 攻撃判断の種類
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// 小さな`Copy`型なので、判断の受け渡しでヒープ確保は発生しません。
+///
+/// `#[must_use]`のため、判断を受け取らずに捨てると`unused_must_use`が警告します。
+/// 意図して捨てる場合は`let _ = engine.decide(&state);`と書きます
+/// （テストの`discarding_a_decision_triggers_unused_must_use`で検査しています）。
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TacticalDecision {
     /// パス展開
-    PassSpread { direction: Direction },
+    #[cfg_attr(feature = "serde", serde(rename = "PassSpread"))]
+    PassSpread {
+        direction: Direction,
+        /// バックスの準備が整わないまま急いで展開する（JSONでは省略可）
+        #[cfg_attr(feature = "serde", serde(default))]
+        rushed: bool,
+    },
     /// クラッシュボール
+    #[cfg_attr(feature = "serde", serde(rename = "Crash"))]
     Crash,
     /// ハイキック
+    #[cfg_attr(feature = "serde", serde(rename = "Kick"))]
     Kick { kick_type: KickType },
     /// クイックタップ
+    #[cfg_attr(feature = "serde", serde(rename = "QuickTap"))]
+    QuickTap,
+    /// モール形成
+    #[cfg_attr(feature = "serde", serde(rename = "Maul"))]
+    Maul,
+    /// スクラム
+    #[cfg_attr(feature = "serde", serde(rename = "Scrum"))]
+    Scrum,
+    /// ラインアウト
+    #[cfg_attr(feature = "serde", serde(rename = "Lineout"))]
+    Lineout { throw_to: LineoutTarget },
+    /// ドロップゴール
+    #[cfg_attr(feature = "serde", serde(rename = "DropGoal"))]
+    DropGoal,
+}
+
+/// パラメータを除いた判断の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecisionKind {
+    /// パス展開
+    PassSpread,
+    /// クラッシュボール
+    Crash,
+    /// キック（種類ごと）
+    Kick(KickType),
+    /// クイックタップ
     QuickTap,
     /// モール形成
     Maul,
     /// スクラム
     Scrum,
+    /// ラインアウト
+    Lineout,
+    /// ドロップゴール
+    DropGoal,
 }
 
+impl TacticalDecision {
+    /// 判断の種類
+    pub fn kind(&self) -> DecisionKind {
+        match self {
+            TacticalDecision::PassSpread { .. } => DecisionKind::PassSpread,
+            TacticalDecision::Crash => DecisionKind::Crash,
+            TacticalDecision::Kick { kick_type } => DecisionKind::Kick(*kick_type),
+            TacticalDecision::QuickTap => DecisionKind::QuickTap,
+            TacticalDecision::Maul => DecisionKind::Maul,
+            TacticalDecision::Scrum => DecisionKind::Scrum,
+            TacticalDecision::Lineout { .. } => DecisionKind::Lineout,
+            TacticalDecision::DropGoal => DecisionKind::DropGoal,
+        }
+    }
+
+    /// この判断のフェーズにかかる典型的な時間（秒）
+    ///
+    /// 準備やリセットを含めた、試合時計の進み方の目安です。
+    ///
+    /// - `Crash`: 8秒（当たってラックを作るまで）
+    /// - `QuickTap`: 10秒
+    /// - `PassSpread`: 15秒
+    /// - `Maul`: 25秒
+    /// - `DropGoal`: 30秒（蹴った後のリスタートまで）
+    /// - `Kick`: 40秒（チェイスと陣形の立て直しを含む）
+    /// - `Lineout`: 45秒（タッチに出てからの準備を含む）
+    /// - `Scrum`: 60秒（組み直しを含む）
+    pub fn typical_phase_secs(&self) -> u32 {
+        match self {
+            TacticalDecision::Crash => 8,
+            TacticalDecision::QuickTap => 10,
+            TacticalDecision::PassSpread { .. } => 15,
+            TacticalDecision::Maul => 25,
+            TacticalDecision::DropGoal => 30,
+            TacticalDecision::Kick { .. } => 40,
+            TacticalDecision::Lineout { .. } => 45,
+            TacticalDecision::Scrum => 60,
+        }
+    }
+
+    /// セットピース（リスタートを含む）から始まる判断か
+    ///
+    /// - `Scrum`: スクラム → `true`
+    /// - `Lineout`（`LineoutTarget::Maul`を含む）: ラインアウト → `true`
+    /// - `QuickTap`: ペナルティからのリスタート → `true`
+    /// - `Maul`: 密集から組むオープンプレーのモール → `false`
+    /// - `PassSpread`、`Crash`、`Kick`、`DropGoal`: オープンプレー → `false`
+    pub fn requires_set_piece(&self) -> bool {
+        match self {
+            TacticalDecision::Scrum
+            | TacticalDecision::Lineout { .. }
+            | TacticalDecision::QuickTap => true,
+            TacticalDecision::PassSpread { .. }
+            | TacticalDecision::Crash
+            | TacticalDecision::Kick { .. }
+            | TacticalDecision::Maul
+            | TacticalDecision::DropGoal => false,
+        }
+    }
+}
+
+/// ラインアウトの投入先
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineoutTarget {
+    /// 前方（短く確実なスロー）
+    #[cfg_attr(feature = "serde", serde(rename = "Front"))]
+    Front,
+    /// 中央
+    #[cfg_attr(feature = "serde", serde(rename = "Middle"))]
+    Middle,
+    /// 後方（バックスへ素早く展開）
+    #[cfg_attr(feature = "serde", serde(rename = "Back"))]
+    Back,
+    /// キャッチ後にモールを形成
+    #[cfg_attr(feature = "serde", serde(rename = "Maul"))]
+    Maul,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
+    #[cfg_attr(feature = "serde", serde(rename = "Left"))]
     Left,
+    #[cfg_attr(feature = "serde", serde(rename = "Right"))]
     Right,
+    #[cfg_attr(feature = "serde", serde(rename = "Center"))]
     Center,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl Direction {
+    /// 左右を反転した方向
+    pub fn mirror(&self) -> Direction {
+        match self {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Center => Direction::Center,
+        }
+    }
+}
+
+impl TacticalDecision {
+    /// 左右を反転した判断（方向を持たない判断はそのまま）
+    pub fn mirror(&self) -> TacticalDecision {
+        match self {
+            TacticalDecision::PassSpread { direction, rushed } => TacticalDecision::PassSpread {
+                direction: direction.mirror(),
+                rushed: *rushed,
+            },
+            other => *other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum KickType {
     /// ハイパント（高く蹴り上げる）
+    #[cfg_attr(feature = "serde", serde(rename = "HighPunt"))]
     HighPunt,
     /// タッチキック（タッチラインへ）
+    #[cfg_attr(feature = "serde", serde(rename = "Touch"))]
     Touch,
     /// グラバー（地面を転がす）
+    #[cfg_attr(feature = "serde", serde(rename = "Grubber"))]
     Grubber,
     /// クロスフィールド
+    #[cfg_attr(feature = "serde", serde(rename = "Crossfield"))]
     Crossfield,
+    /// ボム（アップ・アンド・アンダー、チェイスで再獲得を狙う）
+    #[cfg_attr(feature = "serde", serde(rename = "Bomb"))]
+    Bomb,
+}
+
+impl KickType {
+    /// すべてのキックの種類
+    pub const ALL: [KickType; 5] = [
+        KickType::HighPunt,
+        KickType::Touch,
+        KickType::Grubber,
+        KickType::Crossfield,
+        KickType::Bomb,
+    ];
+
+    /// 風の影響の受けやすさ（0.0-1.0、1.0が最も影響を受ける）
+    pub fn wind_sensitivity(&self) -> f32 {
+        match self {
+            KickType::HighPunt => 0.9, // 滞空時間が長く風に流される
+            KickType::Crossfield => 0.7,
+            KickType::Touch => 0.5,
+            KickType::Grubber => 0.1, // 地面を転がるのでほぼ無風
+            KickType::Bomb => 0.85,
+        }
+    }
+
+    /// 無風時の基本的な陣地獲得（m）
+    pub fn base_gain(&self) -> f32 {
+        match self {
+            KickType::HighPunt => 35.0,
+            KickType::Touch => 30.0,
+            KickType::Crossfield => 25.0,
+            KickType::Grubber => 15.0,
+            KickType::Bomb => 22.0, // 滞空時間を優先するため飛距離は短い
+        }
+    }
+
+    /// キッカーの疲労による精度低下の受けやすさ（0.0-1.0）
+    ///
+    /// 狙った位置に落とす精度が求められるキックほど大きく、
+    /// 距離や高さを優先するキックほど小さくなります。
+    pub fn fatigue_sensitivity(&self) -> f32 {
+        match self {
+            KickType::Touch => 1.0, // タッチラインぎりぎりを狙う
+            KickType::Crossfield => 0.9,
+            KickType::Grubber => 0.6,
+            KickType::HighPunt => 0.3,
+            KickType::Bomb => 0.3,
+        }
+    }
+
+    /// チェイスでボールを再獲得できる基本確率（0.0-1.0）
+    pub fn base_reclaim_probability(&self) -> f32 {
+        match self {
+            KickType::Bomb => 0.45,
+            KickType::Crossfield => 0.25,
+            KickType::Grubber => 0.2,
+            KickType::HighPunt => 0.15,
+            KickType::Touch => 0.0, // タッチに出すので再獲得はない
+        }
+    }
 }
 
 impl std::fmt::Display for TacticalDecision {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            TacticalDecision::PassSpread { direction } => {
+            TacticalDecision::PassSpread {
+                direction,
+                rushed: false,
+            } => {
                 write!(f, "{:?}サイドへパス展開", direction)
             }
+            TacticalDecision::PassSpread {
+                direction,
+                rushed: true,
+            } => {
+                write!(f, "{:?}サイドへ急ぎのパス展開", direction)
+            }
             TacticalDecision::Crash => write!(f, "クラッシュボール"),
             TacticalDecision::Kick { kick_type } => {
                 write!(f, "{:?}キック", kick_type)
@@ -347,239 +1906,9517 @@ impl std::fmt::Display for TacticalDecision {
             TacticalDecision::QuickTap => write!(f, "クイックタップ"),
             TacticalDecision::Maul => write!(f, "モール形成"),
             TacticalDecision::Scrum => write!(f, "スクラム"),
+            TacticalDecision::Lineout { throw_to } => {
+                write!(f, "ラインアウト（{:?}）", throw_to)
+            }
+            TacticalDecision::DropGoal => write!(f, "ドロップゴール"),
         }
     }
 }
 
 // =============================================================================
-// 複雑な意思決定ロジック
+// 近似比較ヘルパー（test-utilフィーチャー）
 // =============================================================================
 
-/// 複雑な状況分析を行う
-async fn analyze_game_state(state: &GameState) -> String {
-    println!("\n=== 詳細な状況分析 ===");
-    println!(
-        "⏱️  経過時間: {}分{}秒 / 残り: {}分{}秒",
-        state.elapsed_time_secs / 60,
-        state.elapsed_time_secs % 60,
-        state.time_remaining_secs() / 60,
-        state.time_remaining_secs() % 60
-    );
-    println!(
-        "📊 スコア: {} - {} (差: {:+}点)",
-        state.score.own,
-        state.score.opposition,
-        state.score.difference()
-    );
-    println!(
-        "📍 フィールド位置: {:?} (リスク: {:.0}%)",
-        state.position,
-        state.position.risk_level() * 100.0
-    );
-    println!("🌤️  天候: {:?}", state.weather);
-    println!(
-        "💨 風: {:.1}m/s 方向{:.0}°",
-        state.wind.speed, state.wind.direction
-    );
-    println!(
-        "😓 疲労度: FW {:.0}% / BK {:.0}% (全体: {:?})",
-        state.fatigue.forwards * 100.0,
-        state.fatigue.backs * 100.0,
-        state.fatigue.level()
-    );
-    println!("🔄 連続フェーズ: {}", state.consecutive_phases);
-    println!(
-        "⚠️  ペナルティ: {} / イエローカード: {}",
-        state.penalties_conceded, state.yellow_cards
+/// 近似比較のデフォルトの許容誤差
+///
+/// 評価値はいずれも0.0-1.0程度の`f32`なので、絶対誤差1e-4で比較します。
+#[cfg(any(test, feature = "test-util"))]
+pub const DEFAULT_EPSILON: f32 = 1e-4;
+
+/// 2つの`f32`が`DEFAULT_EPSILON`以内で等しいか
+#[cfg(any(test, feature = "test-util"))]
+pub fn approx_eq(a: f32, b: f32) -> bool {
+    (a - b).abs() <= DEFAULT_EPSILON
+}
+
+/// 2つの`f32`が`DEFAULT_EPSILON`以内で等しいことを検証
+#[cfg(any(test, feature = "test-util"))]
+#[track_caller]
+pub fn assert_approx_eq(actual: f32, expected: f32) {
+    assert!(
+        approx_eq(actual, expected),
+        "近似比較に失敗: actual={} expected={} (epsilon={})",
+        actual,
+        expected,
+        DEFAULT_EPSILON
     );
+}
 
-    sleep(Duration::from_millis(500)).await;
-    "分析完了".to_string()
+#[cfg(any(test, feature = "test-util"))]
+impl Wind {
+    /// 風速、風向き、最大風速が`DEFAULT_EPSILON`以内で等しいか
+    pub fn approx_eq(&self, other: &Wind) -> bool {
+        approx_eq(self.speed, other.speed)
+            && approx_eq(self.direction, other.direction)
+            && approx_eq(self.gust, other.gust)
+    }
 }
 
-/// 複雑な意思決定を行う
-pub async fn make_complex_decision(state: &GameState) -> TacticalDecision {
-    println!("\n🧠 複雑な状況判断を開始...\n");
+#[cfg(any(test, feature = "test-util"))]
+impl TeamFatigue {
+    /// フォワードとバックスの疲労度が`DEFAULT_EPSILON`以内で等しいか
+    pub fn approx_eq(&self, other: &TeamFatigue) -> bool {
+        approx_eq(self.forwards, other.forwards) && approx_eq(self.backs, other.backs)
+    }
+}
 
-    // 各要素の分析
-    let position_risk = state.position.risk_level();
-    let time_pressure = state.time_pressure();
-    let score_urgency = state.score.urgency(state.time_remaining_secs());
-    let fatigue_impact = 1.0 - state.fatigue.overall();
-    let weather_difficulty = state.weather.pass_difficulty();
+// =============================================================================
+// キックの結果予測
+// =============================================================================
 
-    println!("📐 リスク評価:");
-    println!("  - ポジションリスク: {:.0}%", position_risk * 100.0);
-    println!("  - 時間プレッシャー: {:.0}%", time_pressure * 100.0);
-    println!("  - スコア緊急性: {:.0}%", score_urgency * 100.0);
-    println!("  - 疲労影響: {:.0}%", (1.0 - fatigue_impact) * 100.0);
-    println!("  - 天候難易度: {:.0}%", weather_difficulty * 100.0);
+/// 再獲得1回を陣地に換算した価値（m）
+const RECLAIM_VALUE_M: f32 = 40.0;
 
-    sleep(Duration::from_millis(300)).await;
+/// キックによる陣地獲得の予測値（m）
+///
+/// 基本の獲得距離から、横風の強さとキック種別ごとの風の影響度に応じて
+/// 差し引きます。横風1m/sあたり最大で10%の減衰とします。
+pub fn estimate_kick_gain(kick_type: KickType, state: &GameState) -> f32 {
+    kick_gain_in_wind(kick_type, &state.wind, state.attacking_bearing)
+}
 
-    // ケース1: 危険地帯でのプレー
-    if matches!(state.position, FieldPosition::Own22) && state.defense.pressure {
-        println!("\n⚠️  危険！自陣22mでプレッシャー → タッチキック");
-        return TacticalDecision::Kick {
-            kick_type: KickType::Touch,
-        };
-    }
+/// 突風をサンプリングした瞬間風速でのキックによる陣地獲得（m）
+///
+/// 同じキックでも、蹴った瞬間の風によって結果が変わります。
+pub fn estimate_kick_gain_in_gust(kick_type: KickType, state: &GameState, rng: &mut SimRng) -> f32 {
+    let instantaneous = Wind {
+        speed: state.wind.sample_instantaneous(rng),
+        ..state.wind
+    };
+    kick_gain_in_wind(kick_type, &instantaneous, state.attacking_bearing)
+}
 
-    // ケース2: 点差が大きく時間が少ない
-    if state.score.difference() < -7 && state.time_remaining_secs() < 600 {
-        println!("\n🚨 ビハインド＆残り時間わずか → クイックタップで速攻");
-        return TacticalDecision::QuickTap;
-    }
+/// 指定した風と攻撃方向でのキックによる陣地獲得（m）
+fn kick_gain_in_wind(kick_type: KickType, wind: &Wind, attacking_bearing: f32) -> f32 {
+    let wind_penalty =
+        wind.crosswind_toward(attacking_bearing) * 0.1 * kick_type.wind_sensitivity();
+    kick_type.base_gain() * (1.0 - wind_penalty).max(0.0)
+}
 
-    // ケース3: 大量リードで守りたい
-    if state.score.difference() > 14 && time_pressure > 0.75 {
-        println!("\n🛡️  大量リード＆終盤 → 安全なキック");
-        return TacticalDecision::Kick {
-            kick_type: KickType::Touch,
-        };
-    }
+/// キック後にチェイスでボールを再獲得する確率（0.0-1.0）
+///
+/// チェイサーが整っている（バックスが準備完了でサポート4人以上）と基本確率のまま、
+/// 整っていなければ0.4倍になります。さらに相手バックスリーの疲労度に応じて
+/// 0.5倍（疲労なし）から1.5倍（疲労困憊）まで高まります。
+pub fn estimate_reclaim_probability(kick_type: KickType, state: &GameState) -> f32 {
+    let chasers_organized = state.teammates.backs_ready && state.teammates.support_count >= 4;
+    let chase_factor = if chasers_organized { 1.0 } else { 0.4 };
+    let receiver_factor = 0.5 + state.opposition_fatigue.backs;
+    (kick_type.base_reclaim_probability() * chase_factor * receiver_factor).clamp(0.0, 1.0)
+}
+
+/// 実際に蹴ったキックの結果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KickOutcome {
+    /// 陣地獲得（m）
+    pub gain_m: f32,
+    /// チェイスでボールを再獲得したか
+    pub retained: bool,
+}
+
+/// キックを1本蹴った結果をサンプリングする
+///
+/// 陣地獲得は`estimate_kick_gain_in_gust`、再獲得は`estimate_reclaim_probability`
+/// の確率で決まります。キックの種類ごとのボール保持の扱い:
+///
+/// - `Touch`: タッチに出すため必ず相手ボール（相手のラインアウト）
+/// - `Bomb`, `Crossfield`, `Grubber`, `HighPunt`: 競り合うキックで、
+///   再獲得できれば自チームボールのまま、できなければ相手ボール
+pub fn sample_kick_outcome(
+    kick_type: KickType,
+    state: &GameState,
+    rng: &mut SimRng,
+) -> KickOutcome {
+    let gain_m = estimate_kick_gain_in_gust(kick_type, state, rng);
+    let retained = rng.next_bool(estimate_reclaim_probability(kick_type, state));
+    KickOutcome { gain_m, retained }
+}
+
+/// キッカーの疲労を考慮したキックの精度（0.0-1.0）
+///
+/// キッカーはバックスの一員として、バックスの疲労度をキッカーの疲労度とみなします。
+/// 精度は`1 - 0.6 × 疲労度 × fatigue_sensitivity`で、疲労困憊のキッカーの
+/// タッチキックは0.4まで下がりますが、ハイパントやボムは0.82に留まります。
+pub fn estimate_kick_accuracy(kick_type: KickType, state: &GameState) -> f32 {
+    let kicker_fatigue = state.fatigue.backs.clamp(0.0, 1.0);
+    1.0 - 0.6 * kicker_fatigue * kick_type.fatigue_sensitivity()
+}
+
+/// キックの総合的な価値（m換算）
+///
+/// 陣地獲得の予測値に再獲得の確率を陣地に換算した価値を加え、
+/// キッカーの精度を掛けます。
+pub fn estimate_kick_value(kick_type: KickType, state: &GameState) -> f32 {
+    (estimate_kick_gain(kick_type, state)
+        + estimate_reclaim_probability(kick_type, state) * RECLAIM_VALUE_M)
+        * estimate_kick_accuracy(kick_type, state)
+}
 
-    // ケース4: 疲労が激しい
-    if matches!(state.fatigue.level(), FatigueLevel::Exhausted) && state.teammates.forwards_ready {
-        println!("\n😓 極度の疲労 → シンプルなクラッシュボール");
-        return TacticalDecision::Crash;
+/// 総合的な価値が最も大きいキックを選択
+///
+/// 敵陣（`OppositionHalf`、`Opposition22`）でディフェンスラインの背後にスペースがあり
+/// （`DefenseLine::space_behind_defense`）、ラインが前に出てくる（`DefenseLine::pressure`）
+/// 場合は、価値の比較をせずにグラバーでラインの裏へ転がします。背後にスペースがあっても
+/// プレッシャーがなければ、キッカーに時間があるため通常どおり価値で選びます。
+pub fn choose_kick_type(state: &GameState) -> KickType {
+    let in_opposition_half = matches!(
+        state.position,
+        FieldPosition::OppositionHalf | FieldPosition::Opposition22
+    );
+    if in_opposition_half && state.defense.space_behind_defense && state.defense.pressure() {
+        return KickType::Grubber;
     }
 
-    // ケース5: 悪天候
-    if matches!(state.weather, Weather::Rainy | Weather::StormyRain)
-        && state.teammates.forwards_ready
-    {
-        println!("\n🌧️  悪天候 → フォワード中心のプレー");
-        return TacticalDecision::Crash;
+    [
+        KickType::HighPunt,
+        KickType::Touch,
+        KickType::Crossfield,
+        KickType::Grubber,
+        KickType::Bomb,
+    ]
+    .into_iter()
+    .max_by(|a, b| estimate_kick_value(*a, state).total_cmp(&estimate_kick_value(*b, state)))
+    .unwrap_or(KickType::Touch)
+}
+
+// =============================================================================
+// 演出のタイミング
+// =============================================================================
+
+/// 非同期処理の待ち時間の設定
+///
+/// デモの演出として入れている`sleep`の長さをまとめたものです。
+/// `Timing::instant()`では全て0になり、実際には待ちません。
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    /// 状況分析の表示後の待ち時間
+    pub analysis: Duration,
+    /// リスク評価から判断までの待ち時間
+    pub decision: Duration,
+    /// フォワードのウォームアップにかかる時間
+    pub forwards_warm_up: Duration,
+    /// バックスのウォームアップにかかる時間
+    pub backs_warm_up: Duration,
+    /// 判断候補を1つ評価するごとの待ち時間
+    pub candidate_evaluation: Duration,
+}
+
+#[cfg(feature = "async")]
+impl Timing {
+    /// デモ用の現実的な待ち時間（デフォルト）
+    pub fn realistic() -> Self {
+        Timing {
+            analysis: Duration::from_millis(500),
+            decision: Duration::from_millis(300),
+            forwards_warm_up: Duration::from_millis(600),
+            backs_warm_up: Duration::from_millis(400),
+            candidate_evaluation: Duration::from_millis(100),
+        }
     }
 
-    // ケース6: 得点圏内
-    if matches!(state.position, FieldPosition::Opposition22)
-        && state.defense.gap_on_left
-        && state.teammates.backs_ready
-    {
-        println!("\n🎯 得点圏内でギャップ発見 → パス展開");
-        return TacticalDecision::PassSpread {
-            direction: Direction::Left,
-        };
+    /// 待ち時間なし
+    pub fn instant() -> Self {
+        Timing {
+            analysis: Duration::ZERO,
+            decision: Duration::ZERO,
+            forwards_warm_up: Duration::ZERO,
+            backs_warm_up: Duration::ZERO,
+            candidate_evaluation: Duration::ZERO,
+        }
     }
+}
 
-    // ケース7: 連続フェーズが多い
-    if state.consecutive_phases > 10 {
-        println!("\n🔄 長い連続フェーズ → キックでリセット");
-        return TacticalDecision::Kick {
-            kick_type: KickType::HighPunt,
-        };
+#[cfg(feature = "async")]
+impl Default for Timing {
+    fn default() -> Self {
+        Timing::realistic()
     }
+}
 
-    // デフォルト: バランスの取れた判断
-    if state.defense.gap_on_left && state.teammates.backs_ready {
-        println!("\n✅ 標準的状況 → パス展開");
-        TacticalDecision::PassSpread {
-            direction: Direction::Left,
-        }
-    } else if state.teammates.forwards_ready {
-        println!("\n💪 フォワードでゲイン");
-        TacticalDecision::Crash
-    } else {
-        println!("\n⚡ キックでフィールドポジション確保");
-        TacticalDecision::Kick {
-            kick_type: KickType::Touch,
-        }
+/// 指定時間だけ待つ（0の場合はタイマーを使わずに即座に戻る）
+#[cfg(feature = "async")]
+async fn pause(duration: Duration) {
+    if !duration.is_zero() {
+        tokio::time::sleep(duration).await;
     }
 }
 
 // =============================================================================
-// メイン実行
+// 複雑な意思決定ロジック
 // =============================================================================
 
-#[tokio::main]
+/// 分析結果の出力先
+///
+/// `std::io::Write`を実装する型（標準出力、ファイル、`Vec<u8>`など）は
+/// そのまま出力先として使えます。出力先への書き込みエラーは無視されます。
+pub trait AnalysisSink {
+    /// 1行分の分析結果を出力
+    fn report(&mut self, line: std::fmt::Arguments<'_>);
+}
+
+impl<W: std::io::Write> AnalysisSink for W {
+    fn report(&mut self, line: std::fmt::Arguments<'_>) {
+        let _ = writeln!(self, "{}", line);
+    }
+}
+
+/// 分析結果の表示言語
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// 日本語（デフォルト）
+    #[default]
+    Japanese,
+    /// 英語
+    English,
+}
+
+/// 複雑な状況分析を行う（標準出力へ出力）
+#[cfg(feature = "async")]
+async fn analyze_game_state(state: &GameState, timing: &Timing) -> String {
+    analyze_game_state_to(state, &mut std::io::stdout(), Locale::default(), timing).await
+}
+
+/// 複雑な状況分析を行い、指定した出力先へ指定した言語で書き出す
+#[cfg(feature = "async")]
+pub async fn analyze_game_state_to<S: AnalysisSink + ?Sized>(
+    state: &GameState,
+    sink: &mut S,
+    locale: Locale,
+    timing: &Timing,
+) -> String {
+    report_game_state(state, sink, locale);
+    pause(timing.analysis).await;
+    "分析完了".to_string()
+}
+
+/// 状況分析を待ち時間なしで指定した出力先へ書き出す（tokio不要）
+pub fn report_game_state<S: AnalysisSink + ?Sized>(
+    state: &GameState,
+    sink: &mut S,
+    locale: Locale,
+) {
+    match locale {
+        Locale::Japanese => report_japanese(state, sink),
+        Locale::English => report_english(state, sink),
+    }
+}
+
+/// 日本語で状況分析を出力
+fn report_japanese<S: AnalysisSink + ?Sized>(state: &GameState, sink: &mut S) {
+    sink.report(format_args!("\n=== 詳細な状況分析 ==="));
+    sink.report(format_args!(
+        "⏱️  経過時間: {}分{}秒 / 残り: {}分{}秒",
+        state.elapsed_time_secs / 60,
+        state.elapsed_time_secs % 60,
+        state.time_remaining_secs() / 60,
+        state.time_remaining_secs() % 60
+    ));
+    sink.report(format_args!(
+        "📊 スコア: {} - {} (差: {:+}点)",
+        state.score.own,
+        state.score.opposition,
+        state.score.difference()
+    ));
+    sink.report(format_args!(
+        "📍 フィールド位置: {:?} (リスク: {:.0}%)",
+        state.position,
+        state.position.risk_level() * 100.0
+    ));
+    sink.report(format_args!("🌤️  天候: {:?}", state.weather));
+    sink.report(format_args!(
+        "💨 風: {:.1}m/s 方向{:.0}°",
+        state.wind.speed, state.wind.direction
+    ));
+    sink.report(format_args!(
+        "😓 疲労度: FW {:.0}% / BK {:.0}% (全体: {:?})",
+        state.fatigue.forwards * 100.0,
+        state.fatigue.backs * 100.0,
+        state.fatigue_level()
+    ));
+    sink.report(format_args!(
+        "🔄 連続フェーズ: {}",
+        state.consecutive_phases
+    ));
+    sink.report(format_args!(
+        "⚠️  ペナルティ: {} / イエローカード: {}",
+        state.penalties_conceded, state.yellow_cards
+    ));
+}
+
+/// 英語で状況分析を出力（数値の書式は日本語版と同じ）
+fn report_english<S: AnalysisSink + ?Sized>(state: &GameState, sink: &mut S) {
+    sink.report(format_args!("\n=== Detailed analysis ==="));
+    sink.report(format_args!(
+        "⏱️  Elapsed: {}m{:02}s / Remaining: {}m{:02}s",
+        state.elapsed_time_secs / 60,
+        state.elapsed_time_secs % 60,
+        state.time_remaining_secs() / 60,
+        state.time_remaining_secs() % 60
+    ));
+    sink.report(format_args!(
+        "📊 Score: {}–{} ({:+})",
+        state.score.own,
+        state.score.opposition,
+        state.score.difference()
+    ));
+    sink.report(format_args!(
+        "📍 Field position: {:?} (risk: {:.0}%)",
+        state.position,
+        state.position.risk_level() * 100.0
+    ));
+    sink.report(format_args!("🌤️  Weather: {:?}", state.weather));
+    sink.report(format_args!(
+        "💨 Wind: {:.1} m/s from {:.0}°",
+        state.wind.speed, state.wind.direction
+    ));
+    sink.report(format_args!(
+        "😓 Forwards fatigue: {:.0}% / Backs fatigue: {:.0}% (overall: {:?})",
+        state.fatigue.forwards * 100.0,
+        state.fatigue.backs * 100.0,
+        state.fatigue_level()
+    ));
+    sink.report(format_args!(
+        "🔄 Consecutive phases: {}",
+        state.consecutive_phases
+    ));
+    sink.report(format_args!(
+        "⚠️  Penalties: {} / Yellow cards: {}",
+        state.penalties_conceded, state.yellow_cards
+    ));
+}
+
+/// 数的不利の1人あたりに加えるキックの推奨度（`PhaseBudget::kick_preference`を参照）
+const SHORT_HANDED_KICK_BIAS: f32 = 0.2;
+
+/// キックで陣地をリセットするまでのフェーズ数の目安
+///
+/// ソフト上限を超えるとフェーズを重ねるごとにキックの推奨度が上がり、
+/// ハード上限を超えると他の要素に関わらずキックを選択します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseBudget {
+    /// キックを検討し始めるフェーズ数
+    pub soft_limit: u32,
+    /// キックを強制するフェーズ数
+    pub hard_limit: u32,
+}
+
+impl Default for PhaseBudget {
+    fn default() -> Self {
+        Self {
+            soft_limit: 10,
+            hard_limit: 15,
+        }
+    }
+}
+
+impl PhaseBudget {
+    /// ソフト上限超過と数的不利を考慮したキックの推奨度（0.0-1.0）
+    ///
+    /// `combined_kick_preference`によるフィールドポジションと天候、風を合わせた
+    /// 推奨度に、ソフト上限を超えた1フェーズごとに0.1を加算します。
+    /// シンビンなどで人数が少ない間は、1人少ないごとに`SHORT_HANDED_KICK_BIAS`を
+    /// 加算し、キックで陣地を取って人数が戻るまでの時間を使う判断に寄せます。
+    pub fn kick_preference(&self, state: &GameState) -> f32 {
+        let over = state.consecutive_phases.saturating_sub(self.soft_limit) as f32;
+        let short_handed = state.numbers_advantage().min(0).unsigned_abs() as f32;
+        let base = combined_kick_preference(state.position, state.weather, &state.wind);
+        (base + over * 0.1 + short_handed * SHORT_HANDED_KICK_BIAS).min(1.0)
+    }
+}
+
+/// 連続フェーズによるサポートプレーヤーの減少
+///
+/// フェーズを重ねるとサポートの選手が密集に巻き込まれて散らばるため、
+/// `grace_phases`を超えた`phases_per_player`フェーズごとに、
+/// 判断に使えるサポートを1人ずつ減らします。既定値は5フェーズまで減らさず、
+/// 以降3フェーズごとに1人です（15フェーズで3人減）。
+/// `phases_per_player`が0なら減らしません。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupportDecay {
+    /// サポートが減り始めるまでのフェーズ数
+    pub grace_phases: u32,
+    /// サポートが1人減るフェーズ数
+    pub phases_per_player: u32,
+}
+
+impl Default for SupportDecay {
+    fn default() -> Self {
+        Self {
+            grace_phases: 5,
+            phases_per_player: 3,
+        }
+    }
+}
+
+impl SupportDecay {
+    /// 連続フェーズ数を考慮した実際に使えるサポートの人数
+    pub fn effective_support(&self, teammates: &Teammates, consecutive_phases: u32) -> u32 {
+        if self.phases_per_player == 0 {
+            return teammates.support_count;
+        }
+        let lost = consecutive_phases.saturating_sub(self.grace_phases) / self.phases_per_player;
+        teammates.support_count.saturating_sub(lost)
+    }
+
+    /// サポートを`effective_support`に減らしたチームメイトの状態
+    pub fn effective_teammates(&self, state: &GameState) -> Teammates {
+        Teammates {
+            support_count: self.effective_support(&state.teammates, state.consecutive_phases),
+            ..state.teammates.clone()
+        }
+    }
+}
+
+/// 終盤のビハインドで全力攻撃に切り替える閾値（パニックモード）
+///
+/// 点差が1点以上`max_deficit`点以下のビハインドで、残り時間が
+/// `remaining_under_secs`秒未満になると発動します。発動中はボールを
+/// 手放すキックを選ばず、クイックタップやパス展開でボールを生かし続けます。
+/// 発動中は自陣22mでプレッシャーを受けていてもタッチキックを蹴りません。
+/// 点差に応じた緊急性（`Score::urgency`）とは別の、判断の切り替えです。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanicMode {
+    /// 発動するビハインドの上限（点）
+    pub max_deficit: i32,
+    /// 発動する残り時間（秒、未満）
+    pub remaining_under_secs: u32,
+}
+
+impl Default for PanicMode {
+    fn default() -> Self {
+        Self {
+            max_deficit: 7,
+            remaining_under_secs: 180,
+        }
+    }
+}
+
+impl PanicMode {
+    /// 状況がパニックモードの発動条件を満たすか
+    pub fn is_active(&self, state: &GameState) -> bool {
+        let difference = state.score.difference();
+        difference < 0
+            && -difference <= self.max_deficit
+            && state.time_remaining_secs() < self.remaining_under_secs
+    }
+}
+
+/// 判断分岐の条件
+#[derive(Debug, Clone, PartialEq)]
+pub enum BranchCondition {
+    /// パニックモードの発動条件を満たす
+    PanicMode {
+        max_deficit: i32,
+        remaining_under_secs: u32,
+    },
+    /// 連続フェーズ数がハード上限を超えた
+    PhasesOverHardLimit { limit: u32 },
+    /// 自陣22mでプレッシャーを受けている（向かい風が閾値を超えるとボールを保持）
+    Own22UnderPressure { headwind_threshold: f32 },
+    /// 敵陣22m内で自チームボールのラインアウト
+    LineoutInOpposition22,
+    /// 終盤にドロップゴールの圏内で、ペナルティゴール1本で足りる点差
+    /// （判定は`DecisionEngine::drop_goal_reliability`を参照）
+    PenaltyGoalSufficesLate {
+        remaining_under_secs: u32,
+        drop_goal_reliability: Option<f32>,
+    },
+    /// スコア状況が`ScoreState::DesperateBehind`
+    TrailingLate,
+    /// スコア状況が`ScoreState::CommandingLead`で、時間プレッシャーが閾値を超える
+    LeadingLate { time_pressure_over: f32 },
+    /// 僅差のリードで残り時間が少なく、フォワードの準備ができている
+    NarrowLeadLate {
+        lead_up_to: i32,
+        remaining_under_secs: u32,
+    },
+    /// チームが極度に疲労し、フォワードの準備ができている
+    Exhausted,
+    /// フォワードの方が閾値以上に疲れていて、ギャップがありバックスの準備ができている
+    /// （ギャップがなければ次の分岐へ進む）
+    ForwardsMoreTired { threshold: f32 },
+    /// バックスの方が閾値以上に疲れていて、フォワードの準備ができている
+    BacksMoreTired { threshold: f32 },
+    /// 天候と横風によるハンドリングの難しさが閾値以上で、フォワードの準備ができている
+    PoorHandling { difficulty_at_least: f32 },
+    /// 敵陣22m内でギャップがあり、バックスで展開できる（両側ならプレッシャーと反対側、なければ左を優先）
+    GapInOpposition22,
+    /// 連続フェーズ数がソフト上限を超え、キックの推奨度が閾値以上
+    PhasesOverSoftLimit {
+        limit: u32,
+        kick_preference_at_least: f32,
+    },
+    /// ギャップがあり、バックスで展開できる（両側ならプレッシャーと反対側、なければ左を優先）
+    GapWithBacksReady,
+    /// ギャップがあり、バックスの準備は整っていないがサポートが足りる（急ぎのパス展開）
+    ///
+    /// 数的不利の間は`GapWithBacksReady`と同じく展開しません。
+    GapWithSupport {
+        min_support: u32,
+        support_decay: SupportDecay,
+    },
+    /// フォワードの準備ができている
+    ForwardsReady,
+    /// 上記のいずれにも当てはまらない
+    Otherwise,
+}
+
+impl BranchCondition {
+    /// 状況がこの条件に当てはまるか
+    pub fn matches(&self, state: &GameState) -> bool {
+        let remaining = state.time_remaining_secs();
+        let teammates = &state.teammates;
+        match *self {
+            BranchCondition::PanicMode {
+                max_deficit,
+                remaining_under_secs,
+            } => PanicMode {
+                max_deficit,
+                remaining_under_secs,
+            }
+            .is_active(state),
+            BranchCondition::PhasesOverHardLimit { limit } => state.consecutive_phases > limit,
+            BranchCondition::Own22UnderPressure { .. } => {
+                state.position == FieldPosition::Own22 && state.defense.pressure()
+            }
+            BranchCondition::LineoutInOpposition22 => {
+                state.lineout_awarded && state.position == FieldPosition::Opposition22
+            }
+            BranchCondition::PenaltyGoalSufficesLate {
+                remaining_under_secs,
+                drop_goal_reliability,
+            } => {
+                remaining < remaining_under_secs
+                    && drop_goal_beats_attack(state, drop_goal_reliability)
+            }
+            BranchCondition::TrailingLate => {
+                state.score.state(remaining) == ScoreState::DesperateBehind
+            }
+            BranchCondition::LeadingLate { time_pressure_over } => {
+                state.score.state(remaining) == ScoreState::CommandingLead
+                    && state.time_pressure() > time_pressure_over
+            }
+            BranchCondition::NarrowLeadLate {
+                lead_up_to,
+                remaining_under_secs,
+            } => {
+                (1..=lead_up_to).contains(&state.score.difference())
+                    && remaining < remaining_under_secs
+                    && teammates.forwards_ready
+            }
+            BranchCondition::Exhausted => {
+                state.fatigue_level() == FatigueLevel::Exhausted && teammates.forwards_ready
+            }
+            BranchCondition::ForwardsMoreTired { threshold } => {
+                state.fatigue.imbalance() > threshold
+                    && teammates.backs_ready
+                    && state.defense.optimal_direction().is_some()
+            }
+            BranchCondition::BacksMoreTired { threshold } => {
+                state.fatigue.imbalance() < -threshold && teammates.forwards_ready
+            }
+            BranchCondition::PoorHandling {
+                difficulty_at_least,
+            } => state.handling_difficulty() >= difficulty_at_least && teammates.forwards_ready,
+            BranchCondition::GapInOpposition22 => {
+                state.scoring_opportunity() == ScoringOpportunity::Immediate
+            }
+            BranchCondition::PhasesOverSoftLimit {
+                limit,
+                kick_preference_at_least,
+            } => {
+                let budget = PhaseBudget {
+                    soft_limit: limit,
+                    ..PhaseBudget::default()
+                };
+                state.consecutive_phases > limit
+                    && budget.kick_preference(state) >= kick_preference_at_least
+            }
+            BranchCondition::GapWithSupport {
+                min_support,
+                support_decay,
+            } => {
+                state.defense.optimal_direction().is_some()
+                    && support_decay.effective_support(teammates, state.consecutive_phases)
+                        >= min_support
+            }
+            BranchCondition::GapWithBacksReady => {
+                state.defense.optimal_direction().is_some() && teammates.backs_ready
+            }
+            BranchCondition::ForwardsReady => teammates.forwards_ready,
+            BranchCondition::Otherwise => true,
+        }
+    }
+
+    /// 条件の判定に使った状態の値
+    pub fn observed(&self, state: &GameState) -> String {
+        let remaining = state.time_remaining_secs();
+        let teammates = &state.teammates;
+        match self {
+            BranchCondition::PhasesOverHardLimit { .. }
+            | BranchCondition::PhasesOverSoftLimit { .. } => format!(
+                "連続フェーズ={}, 位置={:?}",
+                state.consecutive_phases, state.position
+            ),
+            BranchCondition::Own22UnderPressure { .. } => format!(
+                "位置={:?}, プレッシャー={}, 向かい風={:.1}m/s",
+                state.position,
+                state.defense.pressure(),
+                state.headwind()
+            ),
+            BranchCondition::LineoutInOpposition22 => format!(
+                "ラインアウト={}, 位置={:?}",
+                state.lineout_awarded, state.position
+            ),
+            BranchCondition::PenaltyGoalSufficesLate {
+                drop_goal_reliability,
+                ..
+            } => format!(
+                "残り={}秒, 位置={:?}, 必要な得点={:?}, ドロップゴール={:.2}, 攻撃継続={:.2}",
+                remaining,
+                state.position,
+                state.scores_needed(),
+                drop_goal_probability(state, drop_goal_reliability.unwrap_or(1.0)),
+                attack_scoring_probability(state)
+            ),
+            BranchCondition::PanicMode { .. } => format!(
+                "点差={:+}, 残り={}秒, 時間プレッシャー={:.2}",
+                state.score.difference(),
+                remaining,
+                state.time_pressure()
+            ),
+            BranchCondition::TrailingLate | BranchCondition::LeadingLate { .. } => format!(
+                "点差={:+}, 残り={}秒, スコア状況={:?}, 時間プレッシャー={:.2}",
+                state.score.difference(),
+                remaining,
+                state.score.state(remaining),
+                state.time_pressure()
+            ),
+            BranchCondition::NarrowLeadLate { .. } => format!(
+                "点差={:+}, 残り={}秒, フォワード準備={}",
+                state.score.difference(),
+                remaining,
+                teammates.forwards_ready
+            ),
+            BranchCondition::Exhausted => format!(
+                "疲労={:?}, フォワード準備={}",
+                state.fatigue_level(),
+                teammates.forwards_ready
+            ),
+            BranchCondition::ForwardsMoreTired { .. } | BranchCondition::BacksMoreTired { .. } => {
+                format!(
+                    "疲労の偏り={:+.2}, フォワード準備={}, バックス準備={}, ギャップ={:?}",
+                    state.fatigue.imbalance(),
+                    teammates.forwards_ready,
+                    teammates.backs_ready,
+                    state.defense.optimal_direction()
+                )
+            }
+            BranchCondition::PoorHandling { .. } => format!(
+                "天候={:?}, ハンドリングの難しさ={:.2}, フォワード準備={}",
+                state.weather,
+                state.handling_difficulty(),
+                teammates.forwards_ready
+            ),
+            BranchCondition::GapInOpposition22 | BranchCondition::GapWithBacksReady => format!(
+                "位置={:?}, ギャップ={:?}, バックス準備={}",
+                state.position,
+                state.defense.optimal_direction(),
+                teammates.backs_ready
+            ),
+            BranchCondition::ForwardsReady => {
+                format!("フォワード準備={}", teammates.forwards_ready)
+            }
+            BranchCondition::GapWithSupport { support_decay, .. } => format!(
+                "ギャップ={:?}, サポート={}人（連続フェーズ{}で{}人）",
+                state.defense.optimal_direction(),
+                teammates.support_count,
+                state.consecutive_phases,
+                support_decay.effective_support(teammates, state.consecutive_phases)
+            ),
+            BranchCondition::Otherwise => String::new(),
+        }
+    }
+}
+
+/// 判断分岐1つ分の評価結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchEval {
+    /// 分岐の条件
+    pub condition: BranchCondition,
+    /// 条件に当てはまったか
+    pub matched: bool,
+    /// 判定に使った状態の値
+    pub observed: String,
+}
+
+/// 判断分岐の仕様（条件と、その分岐が返しうる判断）
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchSpec {
+    /// 分岐の条件
+    pub condition: BranchCondition,
+    /// この分岐が返しうる判断の種類
+    pub yields: Vec<DecisionKind>,
+}
+
+/// 分岐が選んだ判断を`decide`が置き換えるガードの条件
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuardCondition {
+    /// 自陣（自陣22mを含む）で相手のカウンターアタックの脅威が閾値を超え、
+    /// 判断がフィールド内へのキック（タッチキック以外）
+    Counterattack { threshold: f32 },
+    /// 判断が禁止されている（パニックモード中のキックを含む）
+    Forbidden,
+    /// `support_decay`で減らしたサポートの人数では判断を実行できない
+    /// （サポートの足りる候補がなければ置き換えない）
+    SupportShortfall { support_decay: SupportDecay },
+}
+
+impl GuardCondition {
+    /// 条件の判定に使った状態の値（`decision`はガードが検査した判断）
+    pub fn observed(&self, state: &GameState, decision: &TacticalDecision) -> String {
+        match self {
+            GuardCondition::Counterattack { .. } => format!(
+                "判断={:?}, 位置={:?}, カウンターの脅威={:.2}",
+                decision, state.position, state.opposition_counterattack_threat
+            ),
+            GuardCondition::Forbidden => format!("判断={:?}", decision),
+            GuardCondition::SupportShortfall { support_decay } => format!(
+                "判断={:?}, サポート={}人（連続フェーズ{}で{}人）, フォワード準備={}, バックス準備={}",
+                decision,
+                state.teammates.support_count,
+                state.consecutive_phases,
+                support_decay.effective_support(&state.teammates, state.consecutive_phases),
+                state.teammates.forwards_ready,
+                state.teammates.backs_ready
+            ),
+        }
+    }
+}
+
+/// ガードの仕様（条件と、置き換え後の判断）
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuardSpec {
+    /// ガードの条件
+    pub condition: GuardCondition,
+    /// 置き換え後に返しうる判断の種類
+    pub yields: Vec<DecisionKind>,
+}
+
+/// ガード1つ分の評価結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuardEval {
+    /// ガードの条件
+    pub condition: GuardCondition,
+    /// 判断を置き換えたか
+    pub applied: bool,
+    /// 判定に使った状態の値
+    pub observed: String,
+}
+
+/// 意思決定エンジン
+///
+/// 判断の閾値などの設定を保持し、ゲーム状態から戦術を決定します。
+///
+/// # Invariants
+///
+/// `strategies::game_state`（`test-util`フィーチャー）で生成した任意の状態について、
+/// 次の性質が成り立ちます。テストではproptestでこれらを検査しています。
+///
+/// - 同じ状態に対する判断は常に同じ（決定的）
+/// - ギャップのない側へのパス展開は選択しない
+/// - 自陣22mでプレッシャーを受けている場合はパス展開を選択しない
+/// - 片側だけにギャップがある状態を`GameState::mirror`すると、判断も左右反転する
+/// - `forbid`で禁止した判断は選択しない
+/// - `audit`で最初に当てはまる分岐の`yields`に、判断の種類が含まれる
+///   （`audit_guards`のガードが判断を置き換えた場合は、そのガードの`yields`に含まれる）
+#[derive(Debug, Clone)]
+pub struct DecisionEngine {
+    /// 連続フェーズ数の上限
+    pub phase_budget: PhaseBudget,
+    /// 自陣22mからのタッチキックを避ける向かい風の強さ（m/s）
+    pub headwind_threshold: f32,
+    /// 元気なユニットを優先する疲労の偏りの大きさ
+    ///
+    /// フォワードの方が疲れている場合のワイドな展開は、ギャップがあるときだけ選びます。
+    pub fatigue_imbalance_threshold: f32,
+    /// 選択を禁止する判断（ユース向けの安全設定など）
+    ///
+    /// `forbid`で検証してから設定するため、`decide`が候補を失うことはありません。
+    forbidden: std::collections::HashSet<DecisionKind>,
+    /// 候補の評価に加える要素ごとの重み（`update_weights`で調整）
+    pub weights: FactorWeights,
+    /// 終盤のビハインドで全力攻撃に切り替える閾値（`None`で無効）
+    ///
+    /// 既定値は`None`です。使う場合は`Some(PanicMode::default())`などを設定します。
+    pub panic_mode: Option<PanicMode>,
+    /// 自陣でタッチに出さないキックを避ける、相手のカウンターアタックの脅威の閾値
+    ///
+    /// 自陣22mまたは自陣で`opposition_counterattack_threat`がこの値を超えると、
+    /// タッチキック以外のキックをボールを保持する判断に置き換えます。
+    /// 既定値は0.7です（`1.0`以上にすると無効）。
+    pub counterattack_threshold: f32,
+    /// `decide_sticky`で前回の判断から切り替えるのに必要な評価の差
+    pub hysteresis: f32,
+    /// ドロップゴールを蹴るキッカーの信頼度（平均的なキッカーを1.0とした倍率、`None`で無効）
+    ///
+    /// ペナルティゴール1本で足りる点差でドロップゴールの圏内にいる場合に、
+    /// ドロップゴールを選ぶかの判定を変えます（`drop_goal_beats_attack`）。
+    ///
+    /// - `None`（既定）: 敵陣22m内なら常にドロップゴールを選ぶ
+    /// - `Some(信頼度)`: `信頼度 × TacticalDecision::DropGoal.success_probability`が
+    ///   攻撃を続けて得点できる確率を上回るときだけ選ぶ（敵陣でも選びうる）
+    pub drop_goal_reliability: Option<f32>,
+    /// 連続フェーズによるサポートの減少
+    ///
+    /// サポートの足りる判断か（`Teammates::support_adequate_for`）や、サポートの人数を
+    /// 見る分岐は、`SupportDecay::effective_support`で減らした人数で判定します。
+    pub support_decay: SupportDecay,
+    /// `decide_varied`で別の判断を選ぶ確率（0.0-1.0）
+    ///
+    /// 練習用に、同じ状況でも判断に幅を持たせます。選ぶのは評価の差が
+    /// `VARIATION_BAND`以内の候補だけです。既定値は0.0（常に`decide`と同じ判断）です。
+    pub variation: f32,
+    /// `decide_sticky`が最後に返した判断
+    last_decision: Option<TacticalDecision>,
+}
+
+/// `DecisionEngine::decide_varied`が選ぶ候補の評価の幅（`weighted_scores`の単位）
+///
+/// 最善の評価からこの値以内の候補だけを選びます。期待得点で0.5点は、
+/// トライ1本の1割に満たない差です。
+pub const VARIATION_BAND: f32 = 0.5;
+
+/// 意思決定エンジンの設定エラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EngineError {
+    /// 状況によっては許可された候補が1つも残らない禁止設定
+    AllDecisionsForbidden,
+}
+
+impl std::fmt::Display for EngineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EngineError::AllDecisionsForbidden => {
+                write!(f, "設定エラー: すべての選択肢が禁止されています")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EngineError {}
+
+/// `DecisionEngine::sanity_check`が検出する設定の問題
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigWarning {
+    /// フェーズ数のソフト上限がハード上限を超えている
+    PhaseLimitsInverted { soft_limit: u32, hard_limit: u32 },
+    /// すべての判断が禁止されている
+    AllDecisionsForbidden,
+    /// 状況によっては許可された候補が1つも残らない（`forbid`が拒否する組み合わせ）
+    NoCandidateLeft,
+    /// ボールを保持する判断とドロップゴールがすべて禁止され、パニックモードでもキックを蹴る
+    PanicModeCannotKeepBall,
+    /// 負であってはならない閾値が負になっている
+    NegativeThreshold { name: &'static str, value: f32 },
+    /// 0.0-1.0の範囲で指定する閾値が範囲外
+    ThresholdOutOfRange { name: &'static str, value: f32 },
+    /// パニックモードの発動条件を満たす状況がない
+    PanicModeNeverActive,
+    /// 重みが有限の値でない
+    NonFiniteWeight { name: &'static str },
+}
+
+impl std::fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigWarning::PhaseLimitsInverted {
+                soft_limit,
+                hard_limit,
+            } => write!(
+                f,
+                "フェーズ数のソフト上限({})がハード上限({})を超えています",
+                soft_limit, hard_limit
+            ),
+            ConfigWarning::AllDecisionsForbidden => {
+                write!(f, "すべての判断が禁止されています")
+            }
+            ConfigWarning::NoCandidateLeft => {
+                write!(f, "禁止設定により判断の候補が残らない状況があります")
+            }
+            ConfigWarning::PanicModeCannotKeepBall => write!(
+                f,
+                "キック以外の判断がすべて禁止され、パニックモードでもキックを蹴ります"
+            ),
+            ConfigWarning::NegativeThreshold { name, value } => {
+                write!(f, "{}が負の値です: {}", name, value)
+            }
+            ConfigWarning::ThresholdOutOfRange { name, value } => {
+                write!(f, "{}が0.0-1.0の範囲外です: {}", name, value)
+            }
+            ConfigWarning::PanicModeNeverActive => {
+                write!(f, "パニックモードが発動する状況がありません")
+            }
+            ConfigWarning::NonFiniteWeight { name } => {
+                write!(f, "重み{}が有限の値ではありません", name)
+            }
+        }
+    }
+}
+
+impl Default for DecisionEngine {
+    fn default() -> Self {
+        Self {
+            phase_budget: PhaseBudget::default(),
+            headwind_threshold: 8.0,
+            fatigue_imbalance_threshold: 0.3,
+            forbidden: std::collections::HashSet::new(),
+            weights: FactorWeights::default(),
+            panic_mode: None,
+            counterattack_threshold: 0.7,
+            hysteresis: 0.0,
+            drop_goal_reliability: None,
+            support_decay: SupportDecay::default(),
+            variation: 0.0,
+            last_decision: None,
+        }
+    }
+}
+
+impl DecisionEngine {
+    /// デフォルト設定のエンジンを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 状況から戦術を決定する（出力や待機を伴わない純粋な判断）
+    ///
+    /// 判断の経路（フォールバックの候補評価を含む）ではヒープ確保を行わないため、
+    /// 先読み探索などで繰り返し呼び出しても割り当てのコストはかかりません。
+    ///
+    /// 初回の呼び出しでも遅延初期化は行いません。判断に乱数は使わず、設定は
+    /// フィールドの値をそのまま参照します。初回の呼び出しの待ち時間を
+    /// 以降と揃えたい場合は、先に`warm`を呼び出します。
+    pub fn decide(&self, state: &GameState) -> TacticalDecision {
+        self.decide_with_reason(state).0
+    }
+
+    /// 最初の判断の待ち時間を以降の判断と揃えるための事前実行
+    ///
+    /// すべてのプリセット（`all_fixtures`）で判断の経路（分岐と候補の評価）を
+    /// 1回ずつ通し、命令とよく使うデータをCPUキャッシュに載せます。
+    /// エンジンは遅延初期化も乱数も使わないため、事前に計算して保持する値はなく、
+    /// `warm`の後も判断の結果は変わりません。
+    pub fn warm(&self) {
+        for (_, state) in all_fixtures() {
+            let _ = std::hint::black_box(self.decide(&state));
+        }
+    }
+
+    /// 選択を禁止する判断を設定する
+    ///
+    /// 分岐が禁止された判断を選んだ場合は、`score_decisions`の候補のうち
+    /// 禁止されていないものから期待得点が最も高い判断に置き換えます。
+    /// スコアや残り時間による分岐の優先順位より、禁止設定が常に優先されます。
+    ///
+    /// 候補には状況によらずクラッシュ、クイックタップ、モールと状況に応じた種類の
+    /// キックが含まれます（ドロップゴールは圏内のみ）。このうち前者がすべて禁止され、
+    /// キックも1種類以上禁止されている場合は、候補が残らない状況があるため
+    /// `EngineError::AllDecisionsForbidden`を返します。
+    pub fn forbid(
+        mut self,
+        kinds: impl IntoIterator<Item = DecisionKind>,
+    ) -> Result<Self, EngineError> {
+        let forbidden: std::collections::HashSet<DecisionKind> = kinds.into_iter().collect();
+        if Self::leaves_no_candidate(&forbidden) {
+            return Err(EngineError::AllDecisionsForbidden);
+        }
+        self.forbidden = forbidden;
+        Ok(self)
+    }
+
+    /// 状況によっては許可された候補が1つも残らない禁止設定か
+    fn leaves_no_candidate(forbidden: &std::collections::HashSet<DecisionKind>) -> bool {
+        let fixed_allowed = [
+            DecisionKind::Crash,
+            DecisionKind::QuickTap,
+            DecisionKind::Maul,
+        ]
+        .iter()
+        .any(|kind| !forbidden.contains(kind));
+        let every_kick_allowed = KickType::ALL
+            .iter()
+            .all(|kick_type| !forbidden.contains(&DecisionKind::Kick(*kick_type)));
+        !fixed_allowed && !every_kick_allowed
+    }
+
+    /// 矛盾した設定や意味のない設定を検出する
+    ///
+    /// 次の点を確認し、問題があればすべて`Err`で返します。
+    ///
+    /// - `phase_budget`: ソフト上限がハード上限以下か
+    /// - `forbidden`: すべての判断が禁止されていないか、`forbid`が拒否する
+    ///   組み合わせでないか（構造体リテラルで直接設定した場合に検出します）
+    /// - `forbidden`と`panic_mode`: パニックモードが有効なとき、パス展開、クラッシュ、
+    ///   クイックタップ、モール、ドロップゴールのいずれかが許可されているか
+    /// - `headwind_threshold`、`counterattack_threshold`、`drop_goal_reliability`: 負でないか
+    /// - `fatigue_imbalance_threshold`、`variation`: 0.0-1.0の範囲内か
+    /// - `panic_mode`: `max_deficit`が1以上で、`remaining_under_secs`が0でないか
+    /// - `weights`: すべて有限の値か
+    ///
+    /// `hysteresis`の負の値と`counterattack_threshold`の1.0以上の値は、
+    /// 機能を無効にする設定として扱い、警告しません。
+    pub fn sanity_check(&self) -> Result<(), Vec<ConfigWarning>> {
+        let mut warnings = Vec::new();
+
+        if self.phase_budget.soft_limit > self.phase_budget.hard_limit {
+            warnings.push(ConfigWarning::PhaseLimitsInverted {
+                soft_limit: self.phase_budget.soft_limit,
+                hard_limit: self.phase_budget.hard_limit,
+            });
+        }
+
+        let keeps_ball = [
+            DecisionKind::PassSpread,
+            DecisionKind::Crash,
+            DecisionKind::QuickTap,
+            DecisionKind::Maul,
+            DecisionKind::DropGoal,
+        ];
+        let all_kinds = keeps_ball
+            .into_iter()
+            .chain([DecisionKind::Scrum, DecisionKind::Lineout])
+            .chain(KickType::ALL.map(DecisionKind::Kick));
+        if all_kinds.clone().all(|kind| self.forbidden.contains(&kind)) {
+            warnings.push(ConfigWarning::AllDecisionsForbidden);
+        } else if Self::leaves_no_candidate(&self.forbidden) {
+            warnings.push(ConfigWarning::NoCandidateLeft);
+        } else if self.panic_mode.is_some()
+            && keeps_ball.iter().all(|kind| self.forbidden.contains(kind))
+        {
+            warnings.push(ConfigWarning::PanicModeCannotKeepBall);
+        }
+
+        for (name, value) in [
+            ("headwind_threshold", self.headwind_threshold),
+            ("counterattack_threshold", self.counterattack_threshold),
+        ]
+        .into_iter()
+        .chain(
+            self.drop_goal_reliability
+                .map(|value| ("drop_goal_reliability", value)),
+        ) {
+            if value < 0.0 {
+                warnings.push(ConfigWarning::NegativeThreshold { name, value });
+            }
+        }
+        for (name, value) in [
+            (
+                "fatigue_imbalance_threshold",
+                self.fatigue_imbalance_threshold,
+            ),
+            ("variation", self.variation),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                warnings.push(ConfigWarning::ThresholdOutOfRange { name, value });
+            }
+        }
+
+        if let Some(panic) = self.panic_mode
+            && (panic.max_deficit < 1 || panic.remaining_under_secs == 0)
+        {
+            warnings.push(ConfigWarning::PanicModeNeverActive);
+        }
+
+        let weights = self.weights;
+        for (name, value) in [
+            ("position_risk", weights.position_risk),
+            ("time_pressure", weights.time_pressure),
+            ("score_urgency", weights.score_urgency),
+            ("fatigue", weights.fatigue),
+            ("weather", weights.weather),
+        ] {
+            if !value.is_finite() {
+                warnings.push(ConfigWarning::NonFiniteWeight { name });
+            }
+        }
+
+        if warnings.is_empty() {
+            Ok(())
+        } else {
+            Err(warnings)
+        }
+    }
+
+    /// 禁止されている判断
+    pub fn forbidden(&self) -> &std::collections::HashSet<DecisionKind> {
+        &self.forbidden
+    }
+
+    /// 状況で選べる判断を順に返す（選択肢の表示用）
+    ///
+    /// 次の判断を候補とし、そのうち`Teammates::support_adequate_for`を
+    /// （`support_decay`で減らしたサポートの人数で）満たし、このエンジンで禁止されていない（パニックモード中のキックを含む）ものだけを返します。
+    ///
+    /// - `PassSpread`: ギャップのある側のみ（バックスの準備が整っていなければ急ぎの展開）
+    /// - `Crash`、`QuickTap`、`Maul`: 常に候補
+    /// - `Kick`: すべての`KickType`
+    /// - `DropGoal`: ドロップゴールの圏内（`FieldPosition::drop_goal_range_factor`が正）のみ
+    /// - `Lineout`: ラインアウトを獲得している場合のみ、すべての投入先
+    ///
+    /// `Scrum`は状態にスクラムの権利を表す項目がないため、常に含めません。
+    pub fn applicable_decisions(
+        &self,
+        state: &GameState,
+    ) -> impl Iterator<Item = TacticalDecision> {
+        let lineouts = state
+            .lineout_awarded
+            .then_some(
+                [
+                    LineoutTarget::Front,
+                    LineoutTarget::Middle,
+                    LineoutTarget::Back,
+                    LineoutTarget::Maul,
+                ]
+                .map(|throw_to| TacticalDecision::Lineout { throw_to }),
+            )
+            .into_iter()
+            .flatten();
+
+        open_play_options(state, KickType::ALL)
+            .chain(lineouts)
+            .filter(move |decision| {
+                self.support_adequate(state, decision) && !self.is_forbidden(state, decision)
+            })
+    }
+
+    /// 前回の判断を覚えておき、判断が小刻みに入れ替わるのを抑える
+    ///
+    /// `decide`の判断が前回と異なっていても、`weighted_scores`の評価で前回の判断を
+    /// `hysteresis`より大きく上回らなければ前回の判断を続けます。次の場合は
+    /// 前回の判断にこだわらず`decide`の判断に切り替えます。
+    ///
+    /// - 安全のための分岐（`safety_branch`）で判断が決まる
+    /// - 前回の判断が禁止されている、サポートが足りない、または候補にない
+    ///   （ギャップが閉じた側へのパス展開など）
+    /// - 今回の判断が候補にない（ラインアウトなど評価の対象外の判断）
+    ///
+    /// `hysteresis`が0.0以下なら常に`decide`と同じ判断になります。
+    pub fn decide_sticky(&mut self, state: &GameState) -> TacticalDecision {
+        let decision = self.decide(state);
+        let keep_previous = self.hysteresis > 0.0
+            && !self.decided_by_safety_rule(state)
+            && self.last_decision.as_ref().is_some_and(|previous| {
+                if *previous == decision
+                    || self.is_forbidden(state, previous)
+                    || !self.support_adequate(state, previous)
+                {
+                    return false;
+                }
+                let scores = self.weighted_scores(state);
+                let score_of = |target: &TacticalDecision| {
+                    scores
+                        .iter()
+                        .find(|(candidate, _)| candidate == target)
+                        .map(|(_, score)| *score)
+                };
+                match (score_of(previous), score_of(&decision)) {
+                    (Some(previous), Some(next)) => next - previous <= self.hysteresis,
+                    _ => false,
+                }
+            });
+        if !keep_previous {
+            self.last_decision = Some(decision);
+        }
+        self.last_decision.expect("判断は記録済み")
+    }
+
+    /// 評価が最善に近い候補から、`variation`の確率で別の判断を選ぶ
+    ///
+    /// 次の候補を「最善に近い候補」とします。
+    ///
+    /// - `decide`の判断に適用するガードを通る（`passes_guards`を参照）
+    /// - `weighted_scores`の評価が、`decide`の判断と候補のうち高い方の評価から
+    ///   `VARIATION_BAND`を引いた値以上
+    ///
+    /// 確率`variation`で、`decide`の判断以外の最善に近い候補から均等に1つ選び、
+    /// それ以外は`decide`の判断を返します。明らかに評価の低い判断は選びません。
+    /// 次の場合は常に`decide`と同じ判断になります。
+    ///
+    /// - `variation`が0.0以下
+    /// - 安全のための分岐（`safety_branch`）で判断が決まる
+    /// - `decide`の判断が候補にない（ラインアウトなど評価の対象外の判断）
+    /// - 最善に近い候補がほかにない
+    ///
+    /// 乱数は`rng`から引くため、同じシードからは同じ判断の列になります。
+    pub fn decide_varied(&self, state: &GameState, rng: &mut SimRng) -> TacticalDecision {
+        let decision = self.decide(state);
+        if self.variation <= 0.0 || self.decided_by_safety_rule(state) {
+            return decision;
+        }
+        if !candidates(state).any(|candidate| candidate == decision) {
+            return decision;
+        }
+
+        let eligible = |candidate: &TacticalDecision| self.passes_guards(state, candidate);
+        let top = candidates(state)
+            .filter(eligible)
+            .map(|candidate| self.weighted_score(state, &candidate))
+            .fold(self.weighted_score(state, &decision), f32::max);
+        let alternatives = || {
+            candidates(state).filter(move |candidate| {
+                *candidate != decision
+                    && eligible(candidate)
+                    && self.weighted_score(state, candidate) >= top - VARIATION_BAND
+            })
+        };
+        let count = alternatives().count() as u32;
+        if count == 0 || !rng.next_bool(self.variation) {
+            return decision;
+        }
+        alternatives()
+            .nth(rng.next_below(count) as usize)
+            .expect("候補の数の範囲内")
+    }
+
+    /// `decide_sticky`が覚えている前回の判断を忘れる
+    pub fn reset_sticky(&mut self) {
+        self.last_decision = None;
+    }
+
+    /// 判断分岐を評価順に列挙
+    ///
+    /// `decide`が上から順に条件を評価し、最初に当てはまった分岐の判断を返します
+    /// （その判断には`guards`のガードを順に適用します）。
+    /// 閾値はこのエンジンの設定値を反映します。
+    #[must_use]
+    pub fn rationale_tree(&self) -> Vec<BranchSpec> {
+        let kicks = vec![
+            DecisionKind::Kick(KickType::HighPunt),
+            DecisionKind::Kick(KickType::Touch),
+            DecisionKind::Kick(KickType::Crossfield),
+            DecisionKind::Kick(KickType::Grubber),
+            DecisionKind::Kick(KickType::Bomb),
+        ];
+        let panic = self.panic_mode.map(|panic| BranchSpec {
+            condition: BranchCondition::PanicMode {
+                max_deficit: panic.max_deficit,
+                remaining_under_secs: panic.remaining_under_secs,
+            },
+            yields: vec![
+                DecisionKind::DropGoal,
+                DecisionKind::PassSpread,
+                DecisionKind::QuickTap,
+                DecisionKind::Crash,
+            ],
+        });
+        panic
+            .into_iter()
+            .chain([
+                BranchSpec {
+                    condition: BranchCondition::PhasesOverHardLimit {
+                        limit: self.phase_budget.hard_limit,
+                    },
+                    yields: kicks.clone(),
+                },
+                BranchSpec {
+                    condition: BranchCondition::Own22UnderPressure {
+                        headwind_threshold: self.headwind_threshold,
+                    },
+                    yields: vec![
+                        DecisionKind::Kick(KickType::Touch),
+                        DecisionKind::Maul,
+                        DecisionKind::Crash,
+                        DecisionKind::Kick(KickType::Grubber),
+                    ],
+                },
+                BranchSpec {
+                    condition: BranchCondition::LineoutInOpposition22,
+                    yields: vec![DecisionKind::Lineout],
+                },
+                BranchSpec {
+                    condition: BranchCondition::PenaltyGoalSufficesLate {
+                        remaining_under_secs: ScoreState::LATE_UNDER_SECS,
+                        drop_goal_reliability: self.drop_goal_reliability,
+                    },
+                    yields: vec![DecisionKind::DropGoal],
+                },
+                BranchSpec {
+                    condition: BranchCondition::TrailingLate,
+                    yields: vec![DecisionKind::QuickTap],
+                },
+                BranchSpec {
+                    condition: BranchCondition::LeadingLate {
+                        time_pressure_over: 0.75,
+                    },
+                    yields: vec![DecisionKind::Kick(KickType::Touch)],
+                },
+                BranchSpec {
+                    condition: BranchCondition::NarrowLeadLate {
+                        lead_up_to: ScoreState::NARROW_LEAD_UP_TO,
+                        remaining_under_secs: ScoreState::CLOSING_UNDER_SECS,
+                    },
+                    yields: vec![DecisionKind::Crash],
+                },
+                BranchSpec {
+                    condition: BranchCondition::Exhausted,
+                    yields: vec![DecisionKind::Crash],
+                },
+                BranchSpec {
+                    condition: BranchCondition::ForwardsMoreTired {
+                        threshold: self.fatigue_imbalance_threshold,
+                    },
+                    yields: vec![DecisionKind::PassSpread],
+                },
+                BranchSpec {
+                    condition: BranchCondition::BacksMoreTired {
+                        threshold: self.fatigue_imbalance_threshold,
+                    },
+                    yields: vec![DecisionKind::Crash],
+                },
+                BranchSpec {
+                    condition: BranchCondition::PoorHandling {
+                        difficulty_at_least: POOR_HANDLING_DIFFICULTY,
+                    },
+                    yields: vec![DecisionKind::Crash],
+                },
+                BranchSpec {
+                    condition: BranchCondition::GapInOpposition22,
+                    yields: vec![DecisionKind::PassSpread],
+                },
+                BranchSpec {
+                    condition: BranchCondition::PhasesOverSoftLimit {
+                        limit: self.phase_budget.soft_limit,
+                        kick_preference_at_least: 0.5,
+                    },
+                    yields: kicks,
+                },
+                BranchSpec {
+                    condition: BranchCondition::GapWithBacksReady,
+                    yields: vec![DecisionKind::PassSpread],
+                },
+                BranchSpec {
+                    condition: BranchCondition::ForwardsReady,
+                    yields: vec![DecisionKind::Crash],
+                },
+                BranchSpec {
+                    condition: BranchCondition::GapWithSupport {
+                        min_support: 2,
+                        support_decay: self.support_decay,
+                    },
+                    yields: vec![DecisionKind::PassSpread],
+                },
+                BranchSpec {
+                    condition: BranchCondition::Otherwise,
+                    yields: vec![DecisionKind::Kick(KickType::Touch)],
+                },
+            ])
+            .collect()
+    }
+
+    /// 判断分岐をGraphvizのDOT形式で出力する
+    ///
+    /// `rationale_tree`の順に条件を箱（`b0`, `b1`, ...）で並べ、
+    /// 各条件から次の条件へ`no`の辺、その分岐が返しうる判断の楕円
+    /// （`b0_0`, `b0_1`, ...）へ`yes`の辺を引きます。条件のラベルは`BranchCondition`の
+    /// `Debug`表示で、このエンジンの閾値を含みます。判断のラベルは`DecisionKind`の
+    /// `Debug`表示です。最後の分岐（`Otherwise`）には`no`の辺はありません。
+    #[must_use]
+    pub fn trace_to_dot(&self) -> String {
+        use std::fmt::Write;
+
+        let escape = |label: String| label.replace('\\', "\\\\").replace('"', "\\\"");
+        let tree = self.rationale_tree();
+        let mut dot = String::from("digraph decision_tree {\n    node [shape=box];\n");
+        for (index, spec) in tree.iter().enumerate() {
+            let _ = writeln!(
+                dot,
+                "    b{} [label=\"{}\"];",
+                index,
+                escape(format!("{:?}", spec.condition))
+            );
+            for (leaf, kind) in spec.yields.iter().enumerate() {
+                let _ = writeln!(
+                    dot,
+                    "    b{}_{} [label=\"{}\", shape=ellipse];",
+                    index,
+                    leaf,
+                    escape(format!("{:?}", kind))
+                );
+                let _ = writeln!(
+                    dot,
+                    "    b{} -> b{}_{} [label=\"yes\"];",
+                    index, index, leaf
+                );
+            }
+            if index + 1 < tree.len() {
+                let _ = writeln!(dot, "    b{} -> b{} [label=\"no\"];", index, index + 1);
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// すべての判断分岐を評価した結果を`rationale_tree`の順に返す
+    ///
+    /// 最初に`matched: true`となった分岐が判断を選びます。
+    /// その判断を`decide`が置き換えたかは`audit_guards`で確認できます。後続の分岐も評価するため、
+    /// どの条件が外れたかをまとめて確認できます。
+    #[must_use]
+    pub fn audit(&self, state: &GameState) -> Vec<BranchEval> {
+        self.rationale_tree()
+            .into_iter()
+            .map(|spec| BranchEval {
+                matched: spec.condition.matches(state),
+                observed: spec.condition.observed(state),
+                condition: spec.condition,
+            })
+            .collect()
+    }
+
+    /// 分岐が選んだ判断に適用するガードを適用順に列挙
+    ///
+    /// `Counterattack`はボールを保持する判断かタッチキックに置き換えます
+    /// （`guard_counterattack`を参照）。`Forbidden`と`SupportShortfall`は、禁止されておらずサポートの足りる候補のうち
+    /// `weighted_scores`の評価が最も高いものに置き換えます（`decide_with_reason`を参照）。
+    /// 置き換えるのはどちらか一方で、両方に当てはまる場合は`Forbidden`として扱います。
+    #[must_use]
+    pub fn guards(&self) -> Vec<GuardSpec> {
+        let candidates = vec![
+            DecisionKind::PassSpread,
+            DecisionKind::Crash,
+            DecisionKind::Kick(KickType::HighPunt),
+            DecisionKind::Kick(KickType::Touch),
+            DecisionKind::Kick(KickType::Crossfield),
+            DecisionKind::Kick(KickType::Grubber),
+            DecisionKind::Kick(KickType::Bomb),
+            DecisionKind::QuickTap,
+            DecisionKind::Maul,
+            DecisionKind::DropGoal,
+        ];
+        vec![
+            GuardSpec {
+                condition: GuardCondition::Counterattack {
+                    threshold: self.counterattack_threshold,
+                },
+                yields: vec![
+                    DecisionKind::Maul,
+                    DecisionKind::Crash,
+                    DecisionKind::Kick(KickType::Touch),
+                ],
+            },
+            GuardSpec {
+                condition: GuardCondition::Forbidden,
+                yields: candidates.clone(),
+            },
+            GuardSpec {
+                condition: GuardCondition::SupportShortfall {
+                    support_decay: self.support_decay,
+                },
+                yields: candidates,
+            },
+        ]
+    }
+
+    /// 分岐が選んだ判断に各ガードを適用した結果を`guards`の順に返す
+    ///
+    /// `applied: true`のガードがなければ、`decide`は`audit`で最初に当てはまった
+    /// 分岐の判断をそのまま返します。
+    #[must_use]
+    pub fn audit_guards(&self, state: &GameState) -> Vec<GuardEval> {
+        let (branch, _) = self.branch_decision(state);
+        let (selected, _) = self.guard_counterattack(state, (branch, ""));
+        let (decided, _) = self.decide_with_reason(state);
+        let forbidden = self.is_forbidden(state, &selected);
+        self.guards()
+            .into_iter()
+            .map(|spec| {
+                let (applied, checked) = match spec.condition {
+                    GuardCondition::Counterattack { .. } => (selected != branch, branch),
+                    GuardCondition::Forbidden => (forbidden, selected),
+                    GuardCondition::SupportShortfall { .. } => {
+                        (!forbidden && decided != selected, selected)
+                    }
+                };
+                GuardEval {
+                    applied,
+                    observed: spec.condition.observed(state, &checked),
+                    condition: spec.condition,
+                }
+            })
+            .collect()
+    }
+
+    /// 複数の状況をまとめて判断する
+    ///
+    /// 結果の順序は入力と同じです。`rayon`フィーチャーが有効な場合は
+    /// rayonで並列に判断しますが、結果は逐次実行と同一です。
+    #[must_use]
+    pub fn batch(&self, states: &[GameState]) -> Vec<TacticalDecision> {
+        #[cfg(feature = "rayon")]
+        {
+            self.batch_parallel(states)
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        {
+            self.batch_sequential(states)
+        }
+    }
+
+    /// フィールドポジションだけを変えた状況ごとの判断（判断マップ用）
+    ///
+    /// `base`の`position`を`FieldPosition::ALL`の各位置に置き換えて判断します。
+    /// 他のフィールド（スコア、疲労、ディフェンスの状態、ラインアウトの獲得など）は
+    /// `base`のまま変えないため、位置によっては現実にない組み合わせになることがあります。
+    pub fn decide_all_positions(&self, base: &GameState) -> [(FieldPosition, TacticalDecision); 5] {
+        FieldPosition::ALL.map(|position| {
+            let state = GameState {
+                position,
+                ..base.clone()
+            };
+            (position, self.decide(&state))
+        })
+    }
+
+    /// `batch`の逐次実行版（`rayon`フィーチャーが有効な場合はテストでの比較用）
+    #[cfg(any(test, not(feature = "rayon")))]
+    fn batch_sequential(&self, states: &[GameState]) -> Vec<TacticalDecision> {
+        states.iter().map(|state| self.decide(state)).collect()
+    }
+
+    /// `batch`の並列実行版
+    ///
+    /// `par_iter`の`collect`は入力の順序を保つため、結果の順序は逐次実行と同じです。
+    #[cfg(feature = "rayon")]
+    fn batch_parallel(&self, states: &[GameState]) -> Vec<TacticalDecision> {
+        use rayon::prelude::*;
+
+        states.par_iter().map(|state| self.decide(state)).collect()
+    }
+
+    /// 敵陣ゴール前のラインアウトでの投入先を選択
+    ///
+    /// 判断に使う入力:
+    ///
+    /// - `lineout_awarded`と`position`: 敵陣22m内の自チームボールのラインアウトのみ対象
+    /// - フォワードの疲労度と準備状態: 軽度の疲労以下で準備完了ならモール
+    /// - 数的優位: 相手に退場者がいれば後方へ投げてバックスで攻撃
+    /// - ディフェンスのプレッシャー: 競られる場合は確実な前方
+    ///
+    /// いずれにも当てはまらなければ中央へ投入します。
+    fn lineout_target(state: &GameState) -> Option<LineoutTarget> {
+        if !state.lineout_awarded || state.position != FieldPosition::Opposition22 {
+            return None;
+        }
+
+        let forwards_fresh =
+            FatigueLevel::from_percentage(state.fatigue.forwards) <= FatigueLevel::Moderate;
+
+        Some(if forwards_fresh && state.teammates.forwards_ready {
+            LineoutTarget::Maul
+        } else if state.numbers_advantage() > 0 {
+            LineoutTarget::Back
+        } else if state.defense.pressure() {
+            LineoutTarget::Front
+        } else {
+            LineoutTarget::Middle
+        })
+    }
+
+    /// 状況から戦術を決定し、その理由を返す
+    ///
+    /// `decide`と`make_complex_decision`が共有する判断ロジック本体です。
+    /// 分岐が選んだ判断が禁止されている場合や、`Teammates::support_adequate_for`
+    /// を`support_decay`で減らしたサポートの人数で満たさない場合は、禁止されておらずサポートの足りる候補のうち
+    /// `weighted_scores`の評価が最も高いものに置き換えます。
+    /// 自陣でのフィールド内へのキックは、先に`guard_counterattack`で保持する判断に置き換わります。
+    ///
+    /// パニックモード中はキックも禁止された判断として扱いますが、キック以外が
+    /// すべて`forbidden`に含まれる場合に限り、キックを候補に戻します。
+    ///
+    /// 置き換える候補にも`guard_counterattack`を適用するため、ガードが外したキックが
+    /// 置き換えで戻ることはありません。
+    ///
+    /// サポートの足りる候補が残らない場合は、分岐の判断が禁止されていなければ
+    /// そのまま使い、禁止されていれば禁止されていない候補から選びます
+    /// （`forbid`の検証により、禁止されていない候補は必ず残ります。ガードを適用した
+    /// 候補がすべて禁止されている場合に限り、ガードを適用する前の候補から選びます）。
+    fn decide_with_reason(&self, state: &GameState) -> (TacticalDecision, &'static str) {
+        let (decision, reason) = self.guard_counterattack(state, self.branch_decision(state));
+        let forbidden = self.is_forbidden(state, &decision);
+        if !forbidden && self.support_adequate(state, &decision) {
+            return (decision, reason);
+        }
+
+        let fallback_reason = if forbidden {
+            "🚫 禁止された判断を回避 → 期待得点が最も高い選択肢"
+        } else {
+            "👥 サポート不足の判断を回避 → 期待得点が最も高い選択肢"
+        };
+        // キック以外がすべて禁止されていれば、パニックモードでもキックを許す
+        let any_allowed = candidates(state).any(|candidate| !self.is_forbidden(state, &candidate));
+        let permitted = |candidate: &TacticalDecision| {
+            if any_allowed {
+                !self.is_forbidden(state, candidate)
+            } else {
+                !self.forbidden.contains(&candidate.kind())
+            }
+        };
+        let best = |supported_only: bool| {
+            candidates(state)
+                .map(|candidate| self.guard_counterattack(state, (candidate, "")).0)
+                .filter(|candidate| {
+                    permitted(candidate)
+                        && (!supported_only || self.support_adequate(state, candidate))
+                })
+                .map(|candidate| (candidate, self.weighted_score(state, &candidate)))
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(candidate, _)| candidate)
+        };
+        match best(true) {
+            Some(candidate) => (candidate, fallback_reason),
+            None if !forbidden => (decision, reason),
+            None => (
+                best(false)
+                    .or_else(|| candidates(state).find(permitted))
+                    .expect("forbidの検証により許可された候補が必ず残る"),
+                fallback_reason,
+            ),
+        }
+    }
+
+    /// 分岐の後に`decide_with_reason`が適用するガードで置き換えられない判断か
+    ///
+    /// `guard_counterattack`で保持する判断に置き換わらず、禁止されておらず、
+    /// サポートも足りている判断なら`true`を返します。
+    fn passes_guards(&self, state: &GameState, decision: &TacticalDecision) -> bool {
+        self.guard_counterattack(state, (*decision, "")).0 == *decision
+            && !self.is_forbidden(state, decision)
+            && self.support_adequate(state, decision)
+    }
+
+    /// 連続フェーズによる減少を考慮したサポートの人数
+    fn effective_support(&self, state: &GameState) -> u32 {
+        self.support_decay
+            .effective_support(&state.teammates, state.consecutive_phases)
+    }
+
+    /// 連続フェーズによる減少を考慮して、判断を実行できるだけのサポートがあるか
+    fn support_adequate(&self, state: &GameState, decision: &TacticalDecision) -> bool {
+        self.support_decay
+            .effective_teammates(state)
+            .support_adequate_for(decision)
+    }
+
+    /// 判断が禁止されているか（パニックモード中はボールを手放すキックも禁止）
+    fn is_forbidden(&self, state: &GameState, decision: &TacticalDecision) -> bool {
+        self.forbidden.contains(&decision.kind())
+            || (matches!(decision, TacticalDecision::Kick { .. })
+                && self.panic_mode.is_some_and(|panic| panic.is_active(state)))
+    }
+
+    /// 自陣で相手のカウンターアタックの脅威が大きい場合、フィールド内へのキックを
+    /// ボールを保持する判断（モール、クラッシュ）に置き換える
+    ///
+    /// どちらもサポートが足りなければ、タッチキックで確実に切ります。
+    fn guard_counterattack(
+        &self,
+        state: &GameState,
+        (decision, reason): (TacticalDecision, &'static str),
+    ) -> (TacticalDecision, &'static str) {
+        let territorial_kick = matches!(
+            decision,
+            TacticalDecision::Kick { kick_type } if kick_type != KickType::Touch
+        );
+        let in_own_half = matches!(
+            state.position,
+            FieldPosition::Own22 | FieldPosition::OwnHalf
+        );
+        if !territorial_kick
+            || !in_own_half
+            || state.opposition_counterattack_threat <= self.counterattack_threshold
+        {
+            return (decision, reason);
+        }
+
+        if self.support_adequate(state, &TacticalDecision::Maul) {
+            (
+                TacticalDecision::Maul,
+                "🛡️  自陣で相手のカウンターが脅威 → モールで保持",
+            )
+        } else if state.teammates.forwards_ready {
+            (
+                TacticalDecision::Crash,
+                "🛡️  自陣で相手のカウンターが脅威 → クラッシュボールで保持",
+            )
+        } else {
+            (
+                TacticalDecision::Kick {
+                    kick_type: KickType::Touch,
+                },
+                "🛡️  自陣で相手のカウンターが脅威 → タッチキックで切る",
+            )
+        }
+    }
+
+    /// パニックモード中の判断（ボールを手放さずに得点を狙う）
+    ///
+    /// 自陣22mでプレッシャーを受けている場合は、インターセプトされやすい
+    /// パス展開を避けてクイックタップかクラッシュボールで保持します。
+    /// ドロップゴールを選ぶかは`drop_goal_reliability`に従います。
+    fn panic_decision(&self, state: &GameState) -> (TacticalDecision, &'static str) {
+        let under_pressure_in_own22 =
+            state.position == FieldPosition::Own22 && state.defense.pressure();
+        if drop_goal_beats_attack(state, self.drop_goal_reliability) {
+            (
+                TacticalDecision::DropGoal,
+                "🔥 パニックモード: 3点以内のビハインド → ドロップゴールで追いつく",
+            )
+        } else if let Some(direction) = state.defense.optimal_direction()
+            && state.teammates.backs_ready
+            && !under_pressure_in_own22
+        {
+            (
+                TacticalDecision::PassSpread {
+                    direction,
+                    rushed: false,
+                },
+                "🔥 パニックモード: ギャップへパス展開でボールを生かす",
+            )
+        } else if self.effective_support(state) >= 1 {
+            (
+                TacticalDecision::QuickTap,
+                "🔥 パニックモード: クイックタップで速攻",
+            )
+        } else {
+            (
+                TacticalDecision::Crash,
+                "🔥 パニックモード: クラッシュボールで保持",
+            )
+        }
+    }
+
+    /// 安全のための分岐の判断が、そのまま`decide`の判断になるか
+    ///
+    /// 分岐に当てはまっても、判断が禁止されているかサポートが足りなければ
+    /// `decide`は候補から選び直すため`false`になります。
+    fn decided_by_safety_rule(&self, state: &GameState) -> bool {
+        self.safety_branch(state).is_some_and(|branch| {
+            let (decision, _) = self.guard_counterattack(state, branch);
+            !self.is_forbidden(state, &decision) && self.support_adequate(state, &decision)
+        })
+    }
+
+    /// 他の要素に関わらず判断が決まる安全のための分岐
+    fn safety_branch(&self, state: &GameState) -> Option<(TacticalDecision, &'static str)> {
+        // パニックモード: 終盤の僅差のビハインドではボールを手放さない
+        if self.panic_mode.is_some_and(|panic| panic.is_active(state)) {
+            return Some(self.panic_decision(state));
+        }
+
+        // フェーズ数のハード上限: 他の要素に関わらずキック
+        if state.consecutive_phases > self.phase_budget.hard_limit {
+            return Some((
+                TacticalDecision::Kick {
+                    kick_type: choose_kick_type(state),
+                },
+                "⛔ フェーズ数が上限を超過 → キックで強制リセット",
+            ));
+        }
+
+        // ケース1: 危険地帯でのプレー
+        if matches!(state.position, FieldPosition::Own22) && state.defense.pressure() {
+            // 強い向かい風ではタッチキックが距離を稼げないため、ボールを保持する
+            if state.headwind() > self.headwind_threshold {
+                return Some(
+                    if state.teammates.forwards_ready
+                        && self.effective_support(state) >= MAUL_MIN_SUPPORT
+                    {
+                        (
+                            TacticalDecision::Maul,
+                            "🌬️  自陣22mで強い向かい風 → モールで前進",
+                        )
+                    } else if state.teammates.forwards_ready {
+                        (
+                            TacticalDecision::Crash,
+                            "🌬️  自陣22mで強い向かい風 → クラッシュボールで保持",
+                        )
+                    } else {
+                        (
+                            TacticalDecision::Kick {
+                                kick_type: KickType::Grubber,
+                            },
+                            "🌬️  自陣22mで強い向かい風 → グラバーでコーナーへ",
+                        )
+                    },
+                );
+            }
+
+            return Some((
+                TacticalDecision::Kick {
+                    kick_type: KickType::Touch,
+                },
+                "⚠️  危険！自陣22mでプレッシャー → タッチキック",
+            ));
+        }
+
+        None
+    }
+
+    /// 判断分岐を上から評価し、最初に当てはまった分岐の判断を返す
+    fn branch_decision(&self, state: &GameState) -> (TacticalDecision, &'static str) {
+        if let Some(branch) = self.safety_branch(state) {
+            return branch;
+        }
+
+        let time_pressure = state.time_pressure();
+
+        // セットピース: 敵陣22m内での自チームボールのラインアウト
+        if let Some(throw_to) = Self::lineout_target(state) {
+            return (
+                TacticalDecision::Lineout { throw_to },
+                "📏 敵陣ゴール前のラインアウト → セットピースから攻撃",
+            );
+        }
+
+        // 終盤に3点以内のビハインド（または同点）でドロップゴールの圏内: 信頼できるキッカーなら逆転
+        if state.time_remaining_secs() < ScoreState::LATE_UNDER_SECS
+            && drop_goal_beats_attack(state, self.drop_goal_reliability)
+        {
+            return (
+                TacticalDecision::DropGoal,
+                "🎯 終盤に3点以内のビハインド → ドロップゴールで逆転",
+            );
+        }
+
+        // ケース2: 点差が大きく時間が少ない
+        let score_state = state.score.state(state.time_remaining_secs());
+        if score_state == ScoreState::DesperateBehind {
+            return (
+                TacticalDecision::QuickTap,
+                "🚨 ビハインド＆残り時間わずか → クイックタップで速攻",
+            );
+        }
+
+        // ケース3: 大量リードで守りたい
+        if score_state == ScoreState::CommandingLead && time_pressure > 0.75 {
+            return (
+                TacticalDecision::Kick {
+                    kick_type: KickType::Touch,
+                },
+                "🛡️  大量リード＆終盤 → 安全なキック",
+            );
+        }
+
+        // 僅差のリード（1-6点差）で残り3分未満: キックでボールを返さず、
+        // フォワードでフェーズを重ねて時計を進める
+        if state
+            .score
+            .is_narrow_lead_closing(state.time_remaining_secs())
+            && state.teammates.forwards_ready
+        {
+            return (
+                TacticalDecision::Crash,
+                "⏳ 僅差のリード＆残りわずか → ボールを保持して時間を使う",
+            );
+        }
+
+        // ケース4: 疲労が激しい
+        if matches!(state.fatigue_level(), FatigueLevel::Exhausted)
+            && state.teammates.forwards_ready
+        {
+            return (
+                TacticalDecision::Crash,
+                "😓 極度の疲労 → シンプルなクラッシュボール",
+            );
+        }
+
+        // 疲労の偏り: 元気なユニットを中心に攻める
+        let imbalance = state.fatigue.imbalance();
+        if imbalance > self.fatigue_imbalance_threshold
+            && state.teammates.backs_ready
+            && let Some(direction) = state.defense.optimal_direction()
+        {
+            return (
+                TacticalDecision::PassSpread {
+                    direction,
+                    rushed: false,
+                },
+                "🔀 フォワードが消耗 → バックスでワイドに展開",
+            );
+        }
+        if imbalance < -self.fatigue_imbalance_threshold && state.teammates.forwards_ready {
+            return (
+                TacticalDecision::Crash,
+                "🔀 バックスが消耗 → フォワードで前進",
+            );
+        }
+
+        // ケース5: 悪天候や強い横風でハンドリングが難しい
+        if state.handling_difficulty() >= POOR_HANDLING_DIFFICULTY && state.teammates.forwards_ready
+        {
+            return (
+                TacticalDecision::Crash,
+                "🌧️  悪天候 → フォワード中心のプレー",
+            );
+        }
+
+        // ケース6: 得点圏内
+        if state.scoring_opportunity() == ScoringOpportunity::Immediate
+            && let Some(direction) = state.defense.optimal_direction()
+        {
+            return (
+                TacticalDecision::PassSpread {
+                    direction,
+                    rushed: false,
+                },
+                "🎯 得点圏内でギャップ発見 → パス展開",
+            );
+        }
+
+        // ケース7: 連続フェーズがソフト上限を超え、キックが有利
+        if state.consecutive_phases > self.phase_budget.soft_limit
+            && self.phase_budget.kick_preference(state) >= 0.5
+        {
+            return (
+                TacticalDecision::Kick {
+                    kick_type: choose_kick_type(state),
+                },
+                "🔄 長い連続フェーズ → キックでリセット",
+            );
+        }
+
+        // デフォルト: バランスの取れた判断
+        if let Some(direction) = state.defense.optimal_direction()
+            && state.teammates.backs_ready
+        {
+            (
+                TacticalDecision::PassSpread {
+                    direction,
+                    rushed: false,
+                },
+                "✅ 標準的状況 → パス展開",
+            )
+        } else if state.teammates.forwards_ready {
+            (TacticalDecision::Crash, "💪 フォワードでゲイン")
+        } else if let Some(direction) = state.defense.optimal_direction()
+            && self.effective_support(state) >= 2
+        {
+            (
+                TacticalDecision::PassSpread {
+                    direction,
+                    rushed: true,
+                },
+                "⏱️  バックスの準備が間に合わない → 急ぎのパス展開",
+            )
+        } else {
+            (
+                TacticalDecision::Kick {
+                    kick_type: KickType::Touch,
+                },
+                "⚡ キックでフィールドポジション確保",
+            )
+        }
+    }
+}
+
+/// 複雑な意思決定を行う
+#[cfg(feature = "async")]
+pub async fn make_complex_decision(state: &GameState, timing: &Timing) -> TacticalDecision {
+    println!("\n🧠 複雑な状況判断を開始...\n");
+
+    // 各要素の分析
+    let position_risk = state.position.risk_level();
+    let time_pressure = state.time_pressure();
+    let score_urgency = state.score.urgency(state.time_remaining_secs());
+    let fatigue_impact = 1.0 - state.fatigue_overall();
+    let weather_difficulty = state.handling_difficulty();
+
+    println!("📐 リスク評価:");
+    println!("  - ポジションリスク: {:.0}%", position_risk * 100.0);
+    println!("  - 時間プレッシャー: {:.0}%", time_pressure * 100.0);
+    println!("  - スコア緊急性: {:.0}%", score_urgency * 100.0);
+    println!("  - 疲労影響: {:.0}%", (1.0 - fatigue_impact) * 100.0);
+    println!("  - ハンドリング難易度: {:.0}%", weather_difficulty * 100.0);
+
+    pause(timing.decision).await;
+
+    let (decision, reason) = DecisionEngine::new().decide_with_reason(state);
+    println!("\n{}", reason);
+    decision
+}
+
+// =============================================================================
+// 判断の差し替え
+// =============================================================================
+
+/// 状況から戦術を決定するもの
+///
+/// 実行時に判断ロジックを選べるよう、`Box<dyn Decider>`として扱えます。
+pub trait Decider {
+    /// 比較結果の表示などに使う名前
+    fn name(&self) -> &str;
+
+    /// 状況から戦術を決定
+    fn decide(&self, state: &GameState) -> TacticalDecision;
+}
+
+impl Decider for DecisionEngine {
+    fn name(&self) -> &str {
+        "DecisionEngine"
+    }
+
+    fn decide(&self, state: &GameState) -> TacticalDecision {
+        DecisionEngine::decide(self, state)
+    }
+}
+
+/// `candidate_decisions`から無作為に選ぶ比較用のベースライン
+///
+/// シードと状態の`snapshot_hash`から選ぶため、同じ状態には同じ判断を返します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RandomDecider {
+    /// 乱数シード
+    pub seed: u64,
+}
+
+impl Decider for RandomDecider {
+    fn name(&self) -> &str {
+        "RandomDecider"
+    }
+
+    fn decide(&self, state: &GameState) -> TacticalDecision {
+        let mut rng = SimRng::new(self.seed ^ state.snapshot_hash());
+        let candidates = candidate_decisions(state);
+        candidates[rng.next_below(candidates.len() as u32) as usize]
+    }
+}
+
+/// 常に`choose_kick_type`のキックを選ぶ比較用のベースライン
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KickEverythingDecider;
+
+impl Decider for KickEverythingDecider {
+    fn name(&self) -> &str {
+        "KickEverythingDecider"
+    }
+
+    fn decide(&self, state: &GameState) -> TacticalDecision {
+        TacticalDecision::Kick {
+            kick_type: choose_kick_type(state),
+        }
+    }
+}
+
+// =============================================================================
+// ウォームアップ
+// =============================================================================
+
+/// 出場メンバーの構成
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Squad {
+    /// フォワードの人数
+    pub forwards: u32,
+    /// バックスの人数
+    pub backs: u32,
+}
+
+impl Squad {
+    /// ルールに応じた標準的な構成
+    pub fn for_rules(rules: GameRules) -> Self {
+        match rules {
+            GameRules::Fifteens => Squad {
+                forwards: 8,
+                backs: 7,
+            },
+            GameRules::Sevens => Squad {
+                forwards: 3,
+                backs: 4,
+            },
+            GameRules::Tens => Squad {
+                forwards: 5,
+                backs: 5,
+            },
+            GameRules::Custom(rule_set) => Squad {
+                forwards: rule_set.players / 2,
+                backs: rule_set.players - rule_set.players / 2,
+            },
+        }
+    }
+}
+
+/// 試合前のウォームアップ
+///
+/// フォワードとバックスがそれぞれのドリルを並行して行い、
+/// 全員の準備が整った状態と、わずかに蓄積した疲労度を返します。
+#[cfg(feature = "async")]
+pub async fn warm_up(squad: &Squad, timing: &Timing) -> (TeamFatigue, Teammates) {
+    println!("🏃 ウォームアップ開始...");
+
+    let (forwards_fatigue, backs_fatigue) = tokio::join!(
+        async {
+            pause(timing.forwards_warm_up).await;
+            println!("✓ フォワード: スクラム・ラインアウト確認完了");
+            0.03
+        },
+        async {
+            pause(timing.backs_warm_up).await;
+            println!("✓ バックス: ムーブ確認完了");
+            0.02
+        },
+    );
+
+    let fatigue = TeamFatigue {
+        forwards: forwards_fatigue,
+        backs: backs_fatigue,
+    };
+    let teammates = Teammates {
+        backs_ready: true,
+        forwards_ready: true,
+        support_count: (squad.forwards + squad.backs) / 2,
+    };
+
+    println!("✓ ウォームアップ完了");
+    (fatigue, teammates)
+}
+
+// =============================================================================
+// 判断候補の評価
+// =============================================================================
+
+/// 状況に応じた判断の候補
+///
+/// ギャップのある側へのパス展開、クラッシュ、状況に合ったキック、
+/// クイックタップ、モール、ドロップゴール（敵陣と敵陣22mのみ）を候補とします。
+#[must_use]
+pub fn candidate_decisions(state: &GameState) -> Vec<TacticalDecision> {
+    candidates(state).collect()
+}
+
+/// ボールを保持して攻撃を続けた場合に得点できる確率
+///
+/// キックとドロップゴール以外の候補のうち、`成功確率 × (1.0 - リスク)`
+/// （`TacticalDecision::expected_points`でトライにつながる確率）が最も高いものです。
+fn attack_scoring_probability(state: &GameState) -> f32 {
+    let risk = state.position.risk_level();
+    candidates(state)
+        .filter(|candidate| {
+            !matches!(
+                candidate,
+                TacticalDecision::Kick { .. } | TacticalDecision::DropGoal
+            )
+        })
+        .map(|candidate| candidate.success_probability(state) * (1.0 - risk))
+        .fold(0.0, f32::max)
+}
+
+/// キッカーの信頼度を反映したドロップゴールの成功確率
+///
+/// `reliability × TacticalDecision::DropGoal.success_probability`（1.0が上限）です。
+fn drop_goal_probability(state: &GameState, reliability: f32) -> f32 {
+    (reliability * TacticalDecision::DropGoal.success_probability(state)).min(1.0)
+}
+
+/// 攻撃を続けるよりドロップゴールを選ぶべきか
+///
+/// ペナルティゴール1本で足りる点差では、ドロップゴールでもトライでも
+/// 同点または逆転という結果は変わらないため、得点できる確率で比べます。
+///
+/// - `reliability`が`None`: 敵陣22m内なら`true`
+/// - `Some(reliability)`: `drop_goal_probability`が`attack_scoring_probability`を上回れば`true`
+///
+/// ドロップゴールの圏外や、ペナルティゴール1本では足りない点差では`false`です。
+fn drop_goal_beats_attack(state: &GameState, reliability: Option<f32>) -> bool {
+    if state.position.drop_goal_range_factor() <= 0.0
+        || state.scores_needed() != ScorePlan::PenaltyGoal
+    {
+        return false;
+    }
+    match reliability {
+        None => state.position == FieldPosition::Opposition22,
+        Some(reliability) => {
+            drop_goal_probability(state, reliability) > attack_scoring_probability(state)
+        }
+    }
+}
+
+/// `candidate_decisions`と同じ候補を、ヒープ確保なしで順に返す
+fn candidates(state: &GameState) -> impl Iterator<Item = TacticalDecision> {
+    open_play_options(state, [choose_kick_type(state)])
+}
+
+/// オープンプレーの判断の候補を順に返す
+///
+/// `candidates`と`DecisionEngine::applicable_decisions`が共有する並びです。
+/// パス展開（ギャップのある側のみ、左→右。バックスの準備が整っていなければ急ぎの展開）、
+/// クラッシュ、`kicks`のキック、クイックタップ、モール、ドロップゴール
+/// （`FieldPosition::drop_goal_range_factor`が正の圏内のみ）の順に返します。
+fn open_play_options(
+    state: &GameState,
+    kicks: impl IntoIterator<Item = KickType>,
+) -> impl Iterator<Item = TacticalDecision> {
+    let rushed = !state.teammates.backs_ready;
+    let passes = [
+        (Direction::Left, state.defense.gap_on_left),
+        (Direction::Right, state.defense.gap_on_right),
+    ]
+    .into_iter()
+    .filter(|&(_, gap)| gap)
+    .map(move |(direction, _)| TacticalDecision::PassSpread { direction, rushed });
+    let drop_goal =
+        (state.position.drop_goal_range_factor() > 0.0).then_some(TacticalDecision::DropGoal);
+    passes
+        .chain([TacticalDecision::Crash])
+        .chain(
+            kicks
+                .into_iter()
+                .map(|kick_type| TacticalDecision::Kick { kick_type }),
+        )
+        .chain([TacticalDecision::QuickTap, TacticalDecision::Maul])
+        .chain(drop_goal)
+}
+
+/// 各候補の期待得点を評価（順序は`candidate_decisions`と同じ）
+#[must_use]
+pub fn score_decisions(state: &GameState) -> Vec<(TacticalDecision, f32)> {
+    candidate_decisions(state)
+        .into_iter()
+        .map(|decision| {
+            let points = decision.expected_points(state);
+            (decision, points)
+        })
+        .collect()
+}
+
+/// 進捗を報告しながら各候補の期待得点を評価
+///
+/// 候補を1つ評価するごとに、0.0から1.0までの進捗を`progress`へ送ります。
+/// 結果は`score_decisions`と同じです。受信側が先にドロップされても
+/// 評価は最後まで続けます。
+#[cfg(feature = "async")]
+#[must_use]
+pub async fn score_decisions_async(
+    state: &GameState,
+    progress: tokio::sync::mpsc::Sender<f32>,
+    timing: &Timing,
+) -> Vec<(TacticalDecision, f32)> {
+    let candidates = candidate_decisions(state);
+    let total = candidates.len() as f32;
+    let mut scored = Vec::with_capacity(candidates.len());
+
+    let _ = progress.send(0.0).await;
+    for (index, decision) in candidates.into_iter().enumerate() {
+        pause(timing.candidate_evaluation).await;
+        let points = decision.expected_points(state);
+        scored.push((decision, points));
+        let _ = progress.send((index + 1) as f32 / total).await;
+    }
+
+    scored
+}
+
+/// `DecisionQueue`が満杯のときに状態を追加した場合の動作
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// 空きができるまで`push`が待つ
+    ///
+    /// 状態は1つも失われませんが、判断が追いつくまで送り手が止まります。
+    Block,
+    /// 最も古い状態を捨てて新しい状態を入れる
+    ///
+    /// `push`は待たず、判断は常に新しい状態を優先します。
+    /// 捨てた状態は`push`の戻り値で返します。
+    DropOldest,
+}
+
+/// 容量に上限のある判断キュー
+///
+/// 状態は容量`capacity`の`tokio::sync::mpsc`チャネルに積まれ、バックグラウンドの
+/// タスクが1つずつ取り出して、`processing`だけ待ってから（分析の時間の演出）
+/// 判断します。判断は`new`が返す受信側へ、状態と組にして追加した順に届きます。
+/// チャネルが満杯のときの`push`の動作は`OverflowPolicy`で選びます。
+///
+/// キューをドロップすると、積まれている状態の判断を終えたところでタスクも終了します。
+#[cfg(feature = "async")]
+pub struct DecisionQueue {
+    sender: tokio::sync::mpsc::Sender<GameState>,
+    pending: std::sync::Arc<tokio::sync::Mutex<tokio::sync::mpsc::Receiver<GameState>>>,
+    policy: OverflowPolicy,
+    worker: tokio::task::JoinHandle<()>,
+}
+
+#[cfg(feature = "async")]
+impl DecisionQueue {
+    /// キューと判断の受信側を作成し、判断するタスクを起動する
+    ///
+    /// `capacity`が0の場合は1として扱います。
+    pub fn new(
+        engine: DecisionEngine,
+        capacity: usize,
+        policy: OverflowPolicy,
+        processing: Duration,
+    ) -> (
+        Self,
+        tokio::sync::mpsc::Receiver<(GameState, TacticalDecision)>,
+    ) {
+        let capacity = capacity.max(1);
+        let (sender, receiver) = tokio::sync::mpsc::channel(capacity);
+        let (output, decisions) = tokio::sync::mpsc::channel(capacity);
+        let pending = std::sync::Arc::new(tokio::sync::Mutex::new(receiver));
+
+        let worker_pending = std::sync::Arc::clone(&pending);
+        let worker = tokio::spawn(async move {
+            loop {
+                let next = worker_pending.lock().await.recv().await;
+                let Some(state) = next else { break };
+                pause(processing).await;
+                let decision = engine.decide(&state);
+                if output.send((state, decision)).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        (
+            DecisionQueue {
+                sender,
+                pending,
+                policy,
+                worker,
+            },
+            decisions,
+        )
+    }
+
+    /// 状態を追加する
+    ///
+    /// `DropOldest`で満杯だった場合は、捨てた最も古い状態を`Some`で返します。
+    /// 判断の受信側がドロップされてタスクが終了している場合は、
+    /// 追加できなかった状態を`Err`で返します。
+    pub async fn push(
+        &self,
+        state: GameState,
+    ) -> Result<Option<GameState>, tokio::sync::mpsc::error::SendError<GameState>> {
+        use tokio::sync::mpsc::error::{SendError, TrySendError};
+
+        match self.policy {
+            OverflowPolicy::Block => self.sender.send(state).await.map(|()| None),
+            OverflowPolicy::DropOldest => {
+                let mut state = state;
+                let mut dropped = None;
+                loop {
+                    match self.sender.try_send(state) {
+                        Ok(()) => return Ok(dropped),
+                        Err(TrySendError::Closed(state)) => return Err(SendError(state)),
+                        Err(TrySendError::Full(rejected)) => {
+                            state = rejected;
+                            // ロックを待つ間にタスクが取り出していれば、捨てずに再送する
+                            if let Ok(oldest) = self.pending.lock().await.try_recv() {
+                                dropped = Some(oldest);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// 新しい状態の受け付けを終え、積まれている状態の判断が終わるまで待つ
+    ///
+    /// 判断の受信側が満杯のまま受け取らなければ、受け取るまで待ち続けます。
+    pub async fn close(self) {
+        drop(self.sender);
+        let _ = self.worker.await;
+    }
+}
+
+// =============================================================================
+// 結果からの重みの調整
+// =============================================================================
+
+/// 候補の評価に加える要素ごとの重み
+///
+/// 各要素の値（0.0-1.0）に、判断の種類ごとの相性（`factor_affinity`）と
+/// この重みを掛けたものを期待得点に加えます。既定では全て0.0で、
+/// 評価は`score_decisions`と同じです。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FactorWeights {
+    /// フィールドポジションのリスク
+    pub position_risk: f32,
+    /// 時間のプレッシャー
+    pub time_pressure: f32,
+    /// スコアの緊急性
+    pub score_urgency: f32,
+    /// チームの疲労度
+    pub fatigue: f32,
+    /// 天候と横風によるハンドリングの難しさ
+    pub weather: f32,
+}
+
+impl FactorWeights {
+    fn to_array(self) -> [f32; 5] {
+        [
+            self.position_risk,
+            self.time_pressure,
+            self.score_urgency,
+            self.fatigue,
+            self.weather,
+        ]
+    }
+
+    fn from_array(
+        [
+            position_risk,
+            time_pressure,
+            score_urgency,
+            fatigue,
+            weather,
+        ]: [f32; 5],
+    ) -> Self {
+        FactorWeights {
+            position_risk,
+            time_pressure,
+            score_urgency,
+            fatigue,
+            weather,
+        }
+    }
+}
+
+/// 判断の結果（重みの調整に使う）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// 得点した
+    Scored { points: u32 },
+    /// 得点も失点もなかった
+    Neutral,
+    /// 失点した
+    Conceded { points: u32 },
+}
+
+impl Outcome {
+    /// 報酬（トライ+ゴール1本分の7点を1.0とする）
+    fn reward(&self) -> f32 {
+        match *self {
+            Outcome::Scored { points } => points as f32 / 7.0,
+            Outcome::Neutral => 0.0,
+            Outcome::Conceded { points } => -(points as f32) / 7.0,
+        }
+    }
+}
+
+/// 状況の各要素の値（`FactorWeights`と同じ順序）
+fn factor_values(state: &GameState) -> [f32; 5] {
+    [
+        state.position.risk_level(),
+        state.time_pressure(),
+        state.score.urgency(state.time_remaining_secs()),
+        state.fatigue_overall(),
+        state.handling_difficulty(),
+    ]
+}
+
+/// 判断の種類と各要素の相性（正=その要素が高いほど向いている）
+fn factor_affinity(kind: DecisionKind) -> [f32; 5] {
+    match kind {
+        DecisionKind::PassSpread => [-1.0, 0.0, 1.0, -0.5, -1.0],
+        DecisionKind::Crash => [0.0, 0.0, 0.0, -0.5, 1.0],
+        DecisionKind::Kick(_) => [1.0, -0.5, -1.0, 1.0, 0.0],
+        DecisionKind::QuickTap => [-1.0, 1.0, 1.0, -1.0, 0.0],
+        DecisionKind::Maul | DecisionKind::Scrum | DecisionKind::Lineout => {
+            [0.5, 0.0, 0.0, -1.0, 1.0]
+        }
+        DecisionKind::DropGoal => [0.0, 0.5, 0.5, 0.0, -0.5],
+    }
+}
+
+impl DecisionEngine {
+    /// 各候補の期待得点に、重み付けした要素の評価を加えたもの
+    ///
+    /// 順序は`candidate_decisions`と同じです。
+    #[must_use]
+    pub fn weighted_scores(&self, state: &GameState) -> Vec<(TacticalDecision, f32)> {
+        candidates(state)
+            .map(|decision| (decision, self.weighted_score(state, &decision)))
+            .collect()
+    }
+
+    /// 1つの候補の期待得点に、重み付けした要素の評価を加えたもの
+    fn weighted_score(&self, state: &GameState, decision: &TacticalDecision) -> f32 {
+        let values = factor_values(state);
+        let weights = self.weights.to_array();
+        let affinity = factor_affinity(decision.kind());
+        let bonus: f32 = (0..5).map(|i| weights[i] * affinity[i] * values[i]).sum();
+        decision.expected_points(state) + bonus
+    }
+
+    /// 判断の結果に応じて重みを調整する
+    ///
+    /// 得点につながった判断と相性の良い要素の重みを`learning_rate`に比例して上げ、
+    /// 失点につながった場合は下げます。学習済みのモデルではなく、
+    /// 結果を少しずつ反映するだけの経験則です。
+    pub fn update_weights(
+        &mut self,
+        decision: &TacticalDecision,
+        outcome: Outcome,
+        learning_rate: f32,
+    ) {
+        let reward = outcome.reward();
+        let affinity = factor_affinity(decision.kind());
+        let mut weights = self.weights.to_array();
+        for (weight, affinity) in weights.iter_mut().zip(affinity) {
+            *weight += learning_rate * reward * affinity;
+        }
+        self.weights = FactorWeights::from_array(weights);
+    }
+}
+
+// =============================================================================
+// 試合シミュレーション
+// =============================================================================
+
+/// シミュレーション用の再現可能な疑似乱数生成器（xorshift64*）
+#[derive(Debug, Clone)]
+pub struct SimRng {
+    state: u64,
+}
+
+impl SimRng {
+    /// シードから生成（同じシードなら同じ乱数列）
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed.max(1) }
+    }
+
+    /// 次の乱数（u64）
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// 次の乱数（0.0以上1.0未満）
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// 0以上`bound`未満の整数
+    pub fn next_below(&mut self, bound: u32) -> u32 {
+        (self.next_u64() % bound.max(1) as u64) as u32
+    }
+
+    /// 確率`probability`で`true`
+    pub fn next_bool(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+
+    /// スライスから1つ選択
+    pub fn pick<T: Clone>(&mut self, items: &[T]) -> T {
+        items[self.next_below(items.len() as u32) as usize].clone()
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl GameState {
+    /// 乱数から妥当な範囲のゲーム状態を生成
+    ///
+    /// 経過時間は試合時間以内、疲労度や整列度は0.0-1.0、風速は0-20m/s、
+    /// 方向は0-360°の範囲に収まります。同じシードからは同じ状態が生成されるため、
+    /// 再現性が必要なシナリオ生成に使います（`test-util`フィーチャー）。
+    pub fn random(rng: &mut SimRng) -> GameState {
+        let rules = rng.pick(&[GameRules::Fifteens, GameRules::Sevens, GameRules::Tens]);
+        GameState {
+            elapsed_time_secs: rng.next_below(rules.match_duration_secs() + 1),
+            rules,
+            score: Score {
+                own: rng.next_below(50),
+                opposition: rng.next_below(50),
+            },
+            position: rng.pick(&[
+                FieldPosition::Own22,
+                FieldPosition::OwnHalf,
+                FieldPosition::Midfield,
+                FieldPosition::OppositionHalf,
+                FieldPosition::Opposition22,
+            ]),
+            weather: rng.pick(&[
+                Weather::Sunny,
+                Weather::Cloudy,
+                Weather::Rainy,
+                Weather::Windy,
+                Weather::StormyRain,
+            ]),
+            wind: Wind {
+                speed: rng.next_f32() * 20.0,
+                direction: rng.next_f32() * 360.0,
+                gust: 0.0,
+            },
+            attacking_bearing: 0.0,
+            fatigue: TeamFatigue {
+                forwards: rng.next_f32(),
+                backs: rng.next_f32(),
+            },
+            opposition_fatigue: TeamFatigue {
+                forwards: rng.next_f32(),
+                backs: rng.next_f32(),
+            },
+            consecutive_phases: rng.next_below(20),
+            penalties_conceded: rng.next_below(15),
+            yellow_cards: rng.next_below(2),
+            opposition_yellow_cards: rng.next_below(2),
+            temporary_off: Vec::new(),
+            card_law: CardLaw::default(),
+            red_cards_at_secs: Vec::new(),
+            lineout_awarded: rng.next_bool(0.1),
+            possession: Possession::Own,
+            opposition_counterattack_threat: 0.0,
+            defense: DefenseLine {
+                pressure_side: rng.next_bool(0.5).then_some(Direction::Center),
+                gap_on_left: rng.next_bool(0.3),
+                gap_on_right: rng.next_bool(0.3),
+                alignment: rng.next_f32(),
+                space_behind_defense: false,
+            },
+            teammates: Teammates {
+                backs_ready: rng.next_bool(0.8),
+                forwards_ready: rng.next_bool(0.8),
+                support_count: rng.next_below(8),
+            },
+        }
+    }
+}
+
+/// proptest用の`GameState`とその構成要素の生成器（`test-util`フィーチャー）
+///
+/// 値の範囲は`GameState::random`と同じで、常に妥当な状態を生成します。
+/// wasm32向けのテストではproptestを使わないため、このモジュールもありません。
+#[cfg(any(all(test, not(target_arch = "wasm32")), feature = "test-util"))]
+pub mod strategies {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// 試合ルール
+    pub fn game_rules() -> impl Strategy<Value = GameRules> {
+        prop_oneof![
+            Just(GameRules::Fifteens),
+            Just(GameRules::Sevens),
+            Just(GameRules::Tens),
+        ]
+    }
+
+    /// フィールドポジション
+    pub fn field_position() -> impl Strategy<Value = FieldPosition> {
+        prop_oneof![
+            Just(FieldPosition::Own22),
+            Just(FieldPosition::OwnHalf),
+            Just(FieldPosition::Midfield),
+            Just(FieldPosition::OppositionHalf),
+            Just(FieldPosition::Opposition22),
+        ]
+    }
+
+    /// 天候
+    pub fn weather() -> impl Strategy<Value = Weather> {
+        prop_oneof![
+            Just(Weather::Sunny),
+            Just(Weather::Cloudy),
+            Just(Weather::Rainy),
+            Just(Weather::Windy),
+            Just(Weather::StormyRain),
+        ]
+    }
+
+    /// 風（風速0-20m/s、風向き0-360°、最大風速は風速+0-10m/s）
+    pub fn wind() -> impl Strategy<Value = Wind> {
+        (0.0f32..=20.0, 0.0f32..360.0, 0.0f32..=10.0).prop_map(|(speed, direction, extra)| Wind {
+            speed,
+            direction,
+            gust: speed + extra,
+        })
+    }
+
+    /// スコア（各チーム0-49点）
+    pub fn score() -> impl Strategy<Value = Score> {
+        (0u32..50, 0u32..50).prop_map(|(own, opposition)| Score { own, opposition })
+    }
+
+    /// チーム疲労度（0.0-1.0）
+    pub fn team_fatigue() -> impl Strategy<Value = TeamFatigue> {
+        (0.0f32..=1.0, 0.0f32..=1.0).prop_map(|(forwards, backs)| TeamFatigue { forwards, backs })
+    }
+
+    /// 攻撃方向
+    pub fn direction() -> impl Strategy<Value = Direction> {
+        prop_oneof![
+            Just(Direction::Left),
+            Just(Direction::Right),
+            Just(Direction::Center),
+        ]
+    }
+
+    /// ディフェンスライン
+    pub fn defense_line() -> impl Strategy<Value = DefenseLine> {
+        (
+            proptest::option::of(direction()),
+            any::<bool>(),
+            any::<bool>(),
+            0.0f32..=1.0,
+            any::<bool>(),
+        )
+            .prop_map(
+                |(pressure_side, gap_on_left, gap_on_right, alignment, space_behind_defense)| {
+                    DefenseLine {
+                        pressure_side,
+                        gap_on_left,
+                        gap_on_right,
+                        alignment,
+                        space_behind_defense,
+                    }
+                },
+            )
+    }
+
+    /// チームメイトの状態（サポート0-7人）
+    pub fn teammates() -> impl Strategy<Value = Teammates> {
+        (any::<bool>(), any::<bool>(), 0u32..8).prop_map(
+            |(backs_ready, forwards_ready, support_count)| Teammates {
+                backs_ready,
+                forwards_ready,
+                support_count,
+            },
+        )
+    }
+
+    /// レッドカードの扱い
+    pub fn card_law() -> impl Strategy<Value = CardLaw> {
+        prop_oneof![Just(CardLaw::PermanentRed), Just(CardLaw::ReplaceableRed20)]
+    }
+
+    /// ボールを持っているチーム
+    pub fn possession() -> impl Strategy<Value = Possession> {
+        prop_oneof![Just(Possession::Own), Just(Possession::Opposition)]
+    }
+
+    /// ゲーム状態（経過時間とレッドカードの時刻はルールの試合時間以内）
+    pub fn game_state() -> impl Strategy<Value = GameState> {
+        game_rules()
+            .prop_flat_map(|rules| {
+                (
+                    Just(rules.clone()),
+                    0..=rules.match_duration_secs(),
+                    score(),
+                    field_position(),
+                    weather(),
+                    (wind(), 0.0f32..360.0),
+                    (team_fatigue(), team_fatigue()),
+                    (
+                        0u32..20,
+                        0u32..15,
+                        0u32..2,
+                        0u32..2,
+                        any::<bool>(),
+                        possession(),
+                    ),
+                    defense_line(),
+                    teammates(),
+                    (
+                        card_law(),
+                        proptest::collection::vec(0..=rules.match_duration_secs(), 0..2),
+                    ),
+                    0.0f32..=1.0,
+                )
+            })
+            .prop_map(
+                |(
+                    rules,
+                    elapsed_time_secs,
+                    score,
+                    position,
+                    weather,
+                    (wind, attacking_bearing),
+                    (fatigue, opposition_fatigue),
+                    (
+                        consecutive_phases,
+                        penalties_conceded,
+                        yellow_cards,
+                        opposition_yellow_cards,
+                        lineout_awarded,
+                        possession,
+                    ),
+                    defense,
+                    teammates,
+                    (card_law, red_cards_at_secs),
+                    opposition_counterattack_threat,
+                )| GameState {
+                    rules,
+                    elapsed_time_secs,
+                    score,
+                    position,
+                    weather,
+                    wind,
+                    attacking_bearing,
+                    fatigue,
+                    opposition_fatigue,
+                    consecutive_phases,
+                    penalties_conceded,
+                    yellow_cards,
+                    opposition_yellow_cards,
+                    lineout_awarded,
+                    possession,
+                    opposition_counterattack_threat,
+                    temporary_off: Vec::new(),
+                    card_law,
+                    red_cards_at_secs,
+                    defense,
+                    teammates,
+                },
+            )
+    }
+}
+
+impl FieldPosition {
+    /// 1段階前進した位置
+    pub fn advance(&self) -> Self {
+        match self {
+            FieldPosition::Own22 => FieldPosition::OwnHalf,
+            FieldPosition::OwnHalf => FieldPosition::Midfield,
+            FieldPosition::Midfield => FieldPosition::OppositionHalf,
+            FieldPosition::OppositionHalf | FieldPosition::Opposition22 => {
+                FieldPosition::Opposition22
+            }
+        }
+    }
+
+    /// 1段階後退した位置
+    pub fn retreat(&self) -> Self {
+        match self {
+            FieldPosition::Own22 | FieldPosition::OwnHalf => FieldPosition::Own22,
+            FieldPosition::Midfield => FieldPosition::OwnHalf,
+            FieldPosition::OppositionHalf => FieldPosition::Midfield,
+            FieldPosition::Opposition22 => FieldPosition::OppositionHalf,
+        }
+    }
+}
+
+/// バックスの準備が整わないまま急いだパス展開で下がる成功確率
+pub const RUSHED_PASS_PENALTY: f32 = 0.15;
+
+impl TacticalDecision {
+    /// この判断の成功確率（0.0-1.0）
+    ///
+    /// 基本成功率に疲労によるパフォーマンス低下を掛け（キックは
+    /// `estimate_kick_accuracy`によるキッカーの精度）、パス展開では
+    /// 天候と横風の影響（`GameState::handling_difficulty`）も差し引きます。
+    /// 急ぎのパス展開（`rushed: true`）はさらに`RUSHED_PASS_PENALTY`だけ下がります。
+    pub fn success_probability(&self, state: &GameState) -> f32 {
+        let base = match self {
+            TacticalDecision::PassSpread { rushed, .. } => {
+                let rushed_penalty = if *rushed { RUSHED_PASS_PENALTY } else { 0.0 };
+                0.65 - state.handling_difficulty() * 0.5 - rushed_penalty
+            }
+            TacticalDecision::Crash => 0.75,
+            TacticalDecision::Kick { .. } => 0.8,
+            TacticalDecision::QuickTap => 0.5,
+            TacticalDecision::Maul => 0.7,
+            TacticalDecision::Scrum => 0.6,
+            TacticalDecision::Lineout { .. } => 0.75,
+            TacticalDecision::DropGoal => match state.position {
+                FieldPosition::Opposition22 => 0.6,
+                FieldPosition::OppositionHalf => 0.3,
+                _ => 0.05,
+            },
+        };
+        let multiplier = match self {
+            // キックの成否はチーム全体ではなくキッカーの疲労で決まる
+            TacticalDecision::Kick { kick_type } => estimate_kick_accuracy(*kick_type, state),
+            _ => state.fatigue_level().performance_multiplier(),
+        };
+        (base * multiplier).clamp(0.0, 1.0)
+    }
+
+    /// この判断の期待得点
+    ///
+    /// モデルの前提:
+    ///
+    /// - トライの価値はルールの得点でトライ＋コンバージョン×成功率50%（既定で6点）
+    /// - ボールを保持するプレーは、成功すると`1.0 - リスク`の確率でトライにつながる
+    /// - 失敗するとターンオーバーとなり、`リスク × 0.5`の確率でトライを奪われる
+    /// - キックは成功しても得点はないが、失点の可能性を避けられる
+    /// - ドロップゴールは成功でルールのドロップゴールの得点、失敗しても失点にはつながらない
+    pub fn expected_points(&self, state: &GameState) -> f32 {
+        let scoring = state.rules.scoring();
+        let try_value = scoring.try_points as f32 + scoring.conversion_points as f32 * 0.5;
+
+        let p = self.success_probability(state);
+        let risk = state.position.risk_level();
+        let conceded = (1.0 - p) * risk * 0.5 * try_value;
+
+        match self {
+            TacticalDecision::DropGoal => scoring.drop_points as f32 * p,
+            TacticalDecision::Kick { .. } => -conceded,
+            _ => p * (1.0 - risk) * try_value - conceded,
+        }
+    }
+}
+
+/// 早期終了条件（`true`を返した時点でシミュレーションを打ち切る）
+pub type StopCondition = Box<dyn Fn(&GameState) -> bool>;
+
+/// 反則の繰り返しによるイエローカードの基準
+///
+/// 自陣22m内での反則が`window_secs`秒以内に`penalties`回に達すると、
+/// イエローカードで1人が`sin_bin_secs`秒間シンビンになります。
+/// カードが出た時点で反則の記録はリセットされます。シンビン中の選手は
+/// `yellow_cards`に数えられ、`GameState::numbers_advantage`に反映されます。
+/// 数的不利の間は`PhaseBudget::kick_preference`が上がり、判断がキック寄りになります。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardEscalation {
+    /// カードが出る反則の回数
+    pub penalties: u32,
+    /// 反則を数える期間（秒）
+    pub window_secs: u32,
+    /// シンビンの時間（秒）
+    pub sin_bin_secs: u32,
+}
+
+impl Default for CardEscalation {
+    fn default() -> Self {
+        Self {
+            penalties: 3,
+            window_secs: 600,
+            sin_bin_secs: 600,
+        }
+    }
+}
+
+/// 試合シミュレーションの設定
+///
+/// シミュレーションに関わる設定をまとめたものです。既定値から一部だけ
+/// 変える場合は`with_*`メソッドを連ねて組み立てます
+/// （例: `SimulationConfig::default().with_seed(7).with_defensive_phase_secs(60)`）。
+pub struct SimulationConfig {
+    /// 判断に使うエンジン
+    pub engine: DecisionEngine,
+    /// 演出の待ち時間（分析の表示などの非同期ヘルパー用。シミュレーション自体は待たない）
+    #[cfg(feature = "async")]
+    pub timing: Timing,
+    /// 守備のフェーズで進む時間（秒）
+    ///
+    /// 判断を伴わない守備のフェーズで進む時間です。自チームボールのフェーズは
+    /// 判断ごとの`TacticalDecision::typical_phase_secs`だけ進みます。
+    /// 既定値は確率の基準と同じ90秒です（`REFERENCE_PHASE_SECS`を参照）。
+    /// 0秒では試合時計が進まないため、シミュレーションでは1秒として扱います。
+    pub defensive_phase_secs: u32,
+    /// 乱数シード
+    pub seed: u64,
+    /// 天候を遷移させる間隔（秒、`None`なら天候は変化しない）
+    pub weather_interval_secs: Option<u32>,
+    /// 早期終了条件（`None`なら試合終了まで続ける）
+    pub stop_when: Option<StopCondition>,
+    /// 反則の繰り返しによるイエローカード（`None`ならカードは出ない）
+    pub card_escalation: Option<CardEscalation>,
+    /// 指定した時刻（経過秒）に強制的に起こす出来事
+    ///
+    /// 試合時計が時刻に達した後の最初のフェーズの開始時に、判断や守備の処理より先に
+    /// `GameState::replay`と同じ規則で適用します。フェーズの途中で時刻に達した場合は
+    /// そのフェーズの結果が反映された後、次のフェーズの開始時に適用されます。
+    /// 同じ時刻の出来事は指定した順に適用し、試合終了（または早期終了）までに
+    /// 次のフェーズが始まらなかった出来事は適用されません。
+    /// 得点の変化は`MatchSummary::key_moments`にも記録されます。
+    pub scripted_events: Vec<(u32, Event)>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            engine: DecisionEngine::default(),
+            #[cfg(feature = "async")]
+            timing: Timing::default(),
+            defensive_phase_secs: REFERENCE_PHASE_SECS,
+            seed: 2024,
+            weather_interval_secs: None,
+            stop_when: None,
+            card_escalation: Some(CardEscalation::default()),
+            scripted_events: Vec::new(),
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// 判断に使うエンジンを設定
+    pub fn with_engine(mut self, engine: DecisionEngine) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// 演出の待ち時間を設定
+    #[cfg(feature = "async")]
+    pub fn with_timing(mut self, timing: Timing) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// 守備のフェーズで進む時間（秒）を設定（0秒は1秒に切り上げる）
+    pub fn with_defensive_phase_secs(mut self, defensive_phase_secs: u32) -> Self {
+        self.defensive_phase_secs = defensive_phase_secs.max(1);
+        self
+    }
+
+    /// 乱数シードを設定
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// 天候を遷移させる間隔（秒）を設定
+    pub fn with_weather_interval_secs(mut self, interval_secs: Option<u32>) -> Self {
+        self.weather_interval_secs = interval_secs;
+        self
+    }
+
+    /// 早期終了条件を設定
+    pub fn with_stop_when(mut self, stop_when: impl Fn(&GameState) -> bool + 'static) -> Self {
+        self.stop_when = Some(Box::new(stop_when));
+        self
+    }
+
+    /// 反則の繰り返しによるイエローカードの基準を設定
+    pub fn with_card_escalation(mut self, card_escalation: Option<CardEscalation>) -> Self {
+        self.card_escalation = card_escalation;
+        self
+    }
+
+    /// 指定した時刻に強制的に起こす出来事を設定
+    pub fn with_scripted_events(mut self, events: impl IntoIterator<Item = (u32, Event)>) -> Self {
+        self.scripted_events = events.into_iter().collect();
+        self
+    }
+}
+
+/// 試合シミュレーションの結果
+#[derive(Debug, Clone)]
+pub struct MatchSummary {
+    /// 終了時点の状態
+    pub final_state: GameState,
+    /// 自チームボールの各フェーズの判断
+    pub decisions: Vec<TacticalDecision>,
+    /// 各フェーズの判断を下した時点の状況（`decisions`と同じ順序）
+    pub contexts: Vec<PhaseContext>,
+    /// 早期終了条件で打ち切ったか
+    pub stopped_early: bool,
+    /// 早期終了した時刻（経過秒）
+    pub stopped_at_secs: Option<u32>,
+    /// `CancellationToken`で中断されたか（`true`なら試合の途中までの記録）
+    pub cancelled: bool,
+    /// 試合の重要な場面（時系列順）
+    pub key_moments: Vec<KeyMoment>,
+    /// 最も長く続いた連続フェーズ（判断が1つもなければ`None`）
+    ///
+    /// 各判断の時点の`GameState::consecutive_phases`が最大になったフェーズです。
+    /// 同じ長さが複数あれば最初のものを記録します。
+    pub longest_phase_sequence: Option<PhaseSequence>,
+    /// 最も緊急性の高い状況で下した判断（判断が1つもなければ`None`）
+    ///
+    /// 各判断の時点のスコアと残り時間から求めた`Score::urgency`が最大の判断です。
+    /// 同じ値が複数あれば最初のものを記録します。
+    pub highest_urgency_decision: Option<UrgentDecision>,
+    /// 試合全体で前進した距離（m、負の値は後退）
+    ///
+    /// 各フェーズの前後のフィールドポジションの中点（`FieldPosition::midpoint`）の差を
+    /// 合計したものです。得点や失点で中盤から再開したフェーズは陣地の増減に数えません。
+    /// `meters_kicked + meters_carried`に、守備のフェーズで後退した距離を加えた値です。
+    pub net_meters_gained: f32,
+    /// 自チームのキックの判断で前進した距離（m）
+    pub meters_kicked: f32,
+    /// キック以外の判断（パス・クラッシュ・モールなど）で前進した距離（m、失敗による後退を含む）
+    pub meters_carried: f32,
+}
+
+/// 文章に含める重要な場面の最大数
+const NARRATIVE_MAX_MOMENTS: usize = 5;
+
+/// 試合時計の表記（`分:秒`）
+fn narrative_clock(secs: u32) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// 英語の文章で使う判断の名前（冠詞付き）
+fn english_decision_name(decision: &TacticalDecision) -> String {
+    let side = |direction: &Direction| match direction {
+        Direction::Left => "left",
+        Direction::Right => "right",
+        Direction::Center => "middle",
+    };
+    match decision {
+        TacticalDecision::PassSpread {
+            direction,
+            rushed: false,
+        } => format!("a pass spread to the {}", side(direction)),
+        TacticalDecision::PassSpread {
+            direction,
+            rushed: true,
+        } => format!("a rushed pass to the {}", side(direction)),
+        TacticalDecision::Crash => "a crash ball".to_string(),
+        TacticalDecision::Kick { kick_type } => match kick_type {
+            KickType::HighPunt => "a high punt",
+            KickType::Touch => "a kick to touch",
+            KickType::Grubber => "a grubber kick",
+            KickType::Crossfield => "a cross-field kick",
+            KickType::Bomb => "a bomb",
+        }
+        .to_string(),
+        TacticalDecision::QuickTap => "a quick tap".to_string(),
+        TacticalDecision::Maul => "a maul".to_string(),
+        TacticalDecision::Scrum => "a scrum".to_string(),
+        TacticalDecision::Lineout { throw_to } => match throw_to {
+            LineoutTarget::Front => "a lineout to the front",
+            LineoutTarget::Middle => "a lineout to the middle",
+            LineoutTarget::Back => "a lineout to the back",
+            LineoutTarget::Maul => "a lineout drive",
+        }
+        .to_string(),
+        TacticalDecision::DropGoal => "a drop goal".to_string(),
+    }
+}
+
+impl MatchSummary {
+    /// 試合の経過を文章にまとめる
+    ///
+    /// 次の順に1段落の文章を組み立てます。
+    ///
+    /// 1. 最終スコアと勝敗
+    /// 2. 陣地（`net_meters_gained`とキック/ランの内訳）
+    /// 3. 重要な場面（`key_moments`の先頭から最大5件、時刻と直後のスコア）
+    /// 4. 最も緊急性の高い状況での判断（`highest_urgency_decision`）
+    /// 5. 戦術の変化: 判断の前半と後半でキックの割合を比べ、終了時点の
+    ///    フォワードの疲労度とともに述べる
+    pub fn to_narrative(&self, locale: Locale) -> String {
+        match locale {
+            Locale::Japanese => self.narrative_japanese(),
+            Locale::English => self.narrative_english(),
+        }
+    }
+
+    /// 日本語で試合の経過を文章にまとめる
+    fn narrative_japanese(&self) -> String {
+        let score = self.final_state.score;
+        let mut sentences = Vec::new();
+
+        sentences.push(match score.leader() {
+            Some(Team::Own) => format!("{}対{}で勝利した。", score.own, score.opposition),
+            Some(Team::Opposition) => format!("{}対{}で敗れた。", score.own, score.opposition),
+            None => format!("{}対{}の引き分けに終わった。", score.own, score.opposition),
+        });
+
+        sentences.push(format!(
+            "陣地は合計{:+.0}m（キックで{:.0}m、ボールを持って{:.0}m）。",
+            self.net_meters_gained, self.meters_kicked, self.meters_carried
+        ));
+
+        if !self.key_moments.is_empty() {
+            let moments: Vec<String> = self
+                .key_moments
+                .iter()
+                .take(NARRATIVE_MAX_MOMENTS)
+                .map(|moment| {
+                    let what = match moment.kind {
+                        KeyMomentKind::TryScored => "トライ",
+                        KeyMomentKind::TryConceded => "被トライ",
+                        KeyMomentKind::DropGoalScored => "ドロップゴール",
+                        KeyMomentKind::LeadChange => "リードの入れ替わり",
+                        KeyMomentKind::YellowCard => "イエローカード",
+                    };
+                    format!(
+                        "{}の{}（{}対{}）",
+                        narrative_clock(moment.at_secs),
+                        what,
+                        moment.score.own,
+                        moment.score.opposition
+                    )
+                })
+                .collect();
+            sentences.push(
+                match self.key_moments.len().saturating_sub(NARRATIVE_MAX_MOMENTS) {
+                    0 => format!("主な場面は{}。", moments.join("、")),
+                    more => format!("主な場面は{}ほか{}件。", moments.join("、"), more),
+                },
+            );
+        }
+
+        if let Some(urgent) = &self.highest_urgency_decision {
+            let situation = match urgent.score.difference() {
+                d if d < 0 => format!("{}点ビハインド", -d),
+                0 => "同点".to_string(),
+                d => format!("{}点リード", d),
+            };
+            sentences.push(format!(
+                "最も苦しい{}・残り{}分の場面では{}を選んだ。",
+                situation,
+                self.minutes_left_at(urgent.at_secs),
+                urgent.decision
+            ));
+        }
+
+        if let Some((early, late)) = self.kick_shares() {
+            let fatigue = self.final_state.fatigue.forwards * 100.0;
+            sentences.push(match late - early {
+                shift if shift > 0.1 => format!(
+                    "フォワードの疲労が{:.0}%に達すると、展開からキックで陣地を取る戦い方に移った（キックの割合{:.0}% → {:.0}%）。",
+                    fatigue,
+                    early * 100.0,
+                    late * 100.0
+                ),
+                shift if shift < -0.1 => format!(
+                    "フォワードの疲労が{:.0}%に達しても、後半はキックを減らしてボールを保持した（キックの割合{:.0}% → {:.0}%）。",
+                    fatigue,
+                    early * 100.0,
+                    late * 100.0
+                ),
+                _ => format!(
+                    "フォワードの疲労が{:.0}%に達するまで、キックとランの配分は変わらなかった（キックの割合{:.0}%）。",
+                    fatigue,
+                    late * 100.0
+                ),
+            });
+        }
+
+        sentences.concat()
+    }
+
+    /// 英語で試合の経過を文章にまとめる
+    fn narrative_english(&self) -> String {
+        let score = self.final_state.score;
+        let mut sentences = Vec::new();
+
+        sentences.push(match score.leader() {
+            Some(Team::Own) => format!("The team won {}–{}.", score.own, score.opposition),
+            Some(Team::Opposition) => format!("The team lost {}–{}.", score.own, score.opposition),
+            None => format!(
+                "The match ended level at {}–{}.",
+                score.own, score.opposition
+            ),
+        });
+
+        sentences.push(format!(
+            "Territory: {:+.0} m overall ({:.0} m from kicks, {:.0} m in hand).",
+            self.net_meters_gained, self.meters_kicked, self.meters_carried
+        ));
+
+        if !self.key_moments.is_empty() {
+            let moments: Vec<String> = self
+                .key_moments
+                .iter()
+                .take(NARRATIVE_MAX_MOMENTS)
+                .map(|moment| {
+                    let what = match moment.kind {
+                        KeyMomentKind::TryScored => "a try",
+                        KeyMomentKind::TryConceded => "a try conceded",
+                        KeyMomentKind::DropGoalScored => "a drop goal",
+                        KeyMomentKind::LeadChange => "a lead change",
+                        KeyMomentKind::YellowCard => "a yellow card",
+                    };
+                    format!(
+                        "{} at {} ({}–{})",
+                        what,
+                        narrative_clock(moment.at_secs),
+                        moment.score.own,
+                        moment.score.opposition
+                    )
+                })
+                .collect();
+            sentences.push(
+                match self.key_moments.len().saturating_sub(NARRATIVE_MAX_MOMENTS) {
+                    0 => format!("Key moments: {}.", moments.join(", ")),
+                    more => format!("Key moments: {}, and {} more.", moments.join(", "), more),
+                },
+            );
+        }
+
+        if let Some(urgent) = &self.highest_urgency_decision {
+            let situation = match urgent.score.difference() {
+                d if d < 0 => format!("Trailing by {}", -d),
+                0 => "Level".to_string(),
+                d => format!("Leading by {}", d),
+            };
+            sentences.push(format!(
+                "{} with {} minutes left, the team chose {}.",
+                situation,
+                self.minutes_left_at(urgent.at_secs),
+                english_decision_name(&urgent.decision)
+            ));
+        }
+
+        if let Some((early, late)) = self.kick_shares() {
+            let fatigue = self.final_state.fatigue.forwards * 100.0;
+            sentences.push(match late - early {
+                shift if shift > 0.1 => format!(
+                    "As forward fatigue reached {:.0}%, the team shifted from expansive play to territorial kicking ({:.0}% → {:.0}% kicks).",
+                    fatigue,
+                    early * 100.0,
+                    late * 100.0
+                ),
+                shift if shift < -0.1 => format!(
+                    "Even with forward fatigue at {:.0}%, the team kicked less as the match went on ({:.0}% → {:.0}% kicks).",
+                    fatigue,
+                    early * 100.0,
+                    late * 100.0
+                ),
+                _ => format!(
+                    "The balance between kicking and running held steady ({:.0}% kicks) as forward fatigue reached {:.0}%.",
+                    late * 100.0,
+                    fatigue
+                ),
+            });
+        }
+
+        sentences.join(" ")
+    }
+
+    /// 指定した時刻（経過秒）での残り時間（分、切り捨て）
+    fn minutes_left_at(&self, at_secs: u32) -> u32 {
+        self.final_state
+            .rules
+            .match_duration_secs()
+            .saturating_sub(at_secs)
+            / 60
+    }
+
+    /// 判断の前半と後半それぞれに占めるキックの割合（判断が2つ未満なら`None`）
+    fn kick_shares(&self) -> Option<(f32, f32)> {
+        if self.decisions.len() < 2 {
+            return None;
+        }
+        let (early, late) = self.decisions.split_at(self.decisions.len() / 2);
+        let kick_share = |decisions: &[TacticalDecision]| {
+            let kicks = decisions
+                .iter()
+                .filter(|decision| matches!(decision, TacticalDecision::Kick { .. }))
+                .count();
+            kicks as f32 / decisions.len() as f32
+        };
+        Some((kick_share(early), kick_share(late)))
+    }
+}
+
+/// 連続フェーズの記録
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseSequence {
+    /// 判断した時刻（経過秒）
+    pub at_secs: u32,
+    /// 判断時点の連続フェーズ数
+    pub phases: u32,
+}
+
+/// 緊急性とともに記録した判断
+#[derive(Debug, Clone)]
+pub struct UrgentDecision {
+    /// 判断した時刻（経過秒）
+    pub at_secs: u32,
+    /// 判断時点の緊急性（`Score::urgency`、0.0-1.0）
+    pub urgency: f32,
+    /// 選んだ判断
+    pub decision: TacticalDecision,
+    /// 判断時点のスコア
+    pub score: Score,
+}
+
+/// 判断を下した時点の状況
+#[derive(Debug, Clone, Copy)]
+pub struct PhaseContext {
+    /// 判断した時刻（経過秒）
+    pub at_secs: u32,
+    /// 残り時間（秒）
+    pub time_remaining_secs: u32,
+    /// フィールドポジション
+    pub position: FieldPosition,
+    /// スコア
+    pub score: Score,
+}
+
+/// 重要な場面の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyMomentKind {
+    /// 自チームのトライ
+    TryScored,
+    /// 相手チームのトライ
+    TryConceded,
+    /// 自チームのドロップゴール
+    DropGoalScored,
+    /// リードが入れ替わった（同点からのリードを含む）
+    LeadChange,
+    /// 反則の繰り返しで自チームにイエローカード
+    YellowCard,
+}
+
+/// 試合の重要な場面
+#[derive(Debug, Clone)]
+pub struct KeyMoment {
+    /// 発生した時刻（経過秒）
+    pub at_secs: u32,
+    /// 場面の種類
+    pub kind: KeyMomentKind,
+    /// 場面の直後のスコア
+    pub score: Score,
+}
+
+/// 得点の前後のスコアから重要な場面を検出する
+///
+/// 自チームの得点は点数ではなく、その得点を生んだ判断で分類します
+/// （ドロップゴールならドロップゴール、それ以外はトライ）。
+/// 相手ボールの守備のフェーズでは`decision`は`None`です。
+fn detect_key_moments(
+    decision: Option<&TacticalDecision>,
+    before: Score,
+    after: Score,
+    at_secs: u32,
+) -> Vec<KeyMoment> {
+    let mut moments = Vec::new();
+    let mut push = |kind| {
+        moments.push(KeyMoment {
+            at_secs,
+            kind,
+            score: after,
+        })
+    };
+
+    if after.own > before.own {
+        push(match decision {
+            Some(TacticalDecision::DropGoal) => KeyMomentKind::DropGoalScored,
+            _ => KeyMomentKind::TryScored,
+        });
+    }
+    if after.opposition > before.opposition {
+        push(KeyMomentKind::TryConceded);
+    }
+    if after.leader().is_some() && after.leader() != before.leader() {
+        push(KeyMomentKind::LeadChange);
+    }
+
+    moments
+}
+
+/// 判断の結果をゲーム状態に反映する
+///
+/// 成功すれば前進（敵陣22mではトライ）、失敗すれば後退（自陣22mでは失トライ）。
+/// キックは成功すると`kick`の陣地獲得だけ前進して連続フェーズをリセットし、
+/// 再獲得できなければ相手ボールになります。
+fn apply_outcome(
+    state: &mut GameState,
+    decision: &TacticalDecision,
+    success: bool,
+    kick: Option<KickOutcome>,
+) {
+    state.lineout_awarded = false;
+
+    match (success, kick, state.position) {
+        (true, _, _) if *decision == TacticalDecision::DropGoal => {
+            state.score.own += state.rules.scoring().drop_points;
+            state.position = FieldPosition::Midfield;
+            state.consecutive_phases = 0;
+        }
+        (true, Some(kick), position) => {
+            state.position = FieldPosition::from_meters(position.midpoint() + kick.gain_m);
+            state.consecutive_phases = 0;
+            if !kick.retained {
+                state.possession = Possession::Opposition;
+            }
+        }
+        (true, None, FieldPosition::Opposition22) => {
+            state.score.own += state.rules.scoring().converted_try();
+            state.position = FieldPosition::Midfield;
+            state.consecutive_phases = 0;
+        }
+        (true, None, position) => {
+            state.position = position.advance();
+            state.consecutive_phases += 1;
+        }
+        (false, _, FieldPosition::Own22) => {
+            state.score.opposition += state.rules.scoring().converted_try();
+            state.position = FieldPosition::Midfield;
+            state.consecutive_phases = 0;
+        }
+        (false, _, position) => {
+            state.position = position.retreat();
+            state.consecutive_phases = 0;
+        }
+    }
+}
+
+/// 成功確率や守備の確率の基準になるフェーズの長さ（秒）
+///
+/// 判断の成功確率や守備で反則・奪い返す確率は、1フェーズ90秒として調整されています。
+const REFERENCE_PHASE_SECS: u32 = 90;
+
+/// このフェーズで結果が決まるか
+///
+/// `REFERENCE_PHASE_SECS`より短いフェーズは、長さに比例した確率でしか得点や
+/// ターンオーバー（前進・後退を含む）が起こりません。決まらなければボールも位置も
+/// そのままです。基準以上の長さなら乱数を使わず常に`true`を返します。
+fn phase_resolves(phase_secs: u32, rng: &mut SimRng) -> bool {
+    phase_secs >= REFERENCE_PHASE_SECS
+        || rng.next_bool(phase_secs as f32 / REFERENCE_PHASE_SECS as f32)
+}
+
+/// 守備のフェーズで反則する確率
+const DEFENSIVE_PENALTY_PROBABILITY: f32 = 0.15;
+
+/// 相手ボールのフェーズを進め、反則したかを返す
+///
+/// 15%の確率で反則し、相手ボールのまま1段階後退します（自陣22mではその場に留まる）。
+/// 反則しなければ疲労度の差（ルールに応じた重みで比較）に応じた確率
+/// （10%-90%、同程度なら40%）で
+/// ボールを奪い返します。奪い返せなければ1段階後退し、自陣22mでは
+/// 失トライとなって中盤から自チームボールで再開します。
+fn defend_phase(state: &mut GameState, rng: &mut SimRng) -> bool {
+    let fatigue_edge = state.opposition_fatigue_overall() - state.fatigue_overall();
+    let regain_probability = (0.4 + fatigue_edge * 0.5).clamp(0.1, 0.9);
+    state.consecutive_phases = 0;
+
+    if rng.next_bool(DEFENSIVE_PENALTY_PROBABILITY) {
+        state.penalties_conceded += 1;
+        state.position = state.position.retreat();
+        return true;
+    }
+
+    if rng.next_bool(regain_probability) {
+        state.possession = Possession::Own;
+    } else if state.position == FieldPosition::Own22 {
+        state.score.opposition += state.rules.scoring().converted_try();
+        state.position = FieldPosition::Midfield;
+        state.possession = Possession::Own;
+    } else {
+        state.position = state.position.retreat();
+    }
+    false
+}
+
+/// 進行中の試合シミュレーション
+///
+/// フェーズの途中で中断されることはなく、`play_phase`の呼び出しの間では
+/// 状態と記録が常に整合しています。
+#[derive(Clone)]
+struct MatchRun<'a> {
+    config: &'a SimulationConfig,
+    /// 守備のフェーズで進む時間（秒、1秒以上）
+    defensive_phase_secs: u32,
+    state: GameState,
+    rng: SimRng,
+    decisions: Vec<TacticalDecision>,
+    contexts: Vec<PhaseContext>,
+    key_moments: Vec<KeyMoment>,
+    longest_phase_sequence: Option<PhaseSequence>,
+    highest_urgency_decision: Option<UrgentDecision>,
+    /// 自陣22m内で反則した時刻
+    red_zone_penalties: Vec<u32>,
+    /// シンビン中の選手が戻る時刻
+    sin_bin_returns: Vec<u32>,
+    /// まだ適用していない台本の出来事（時刻順）
+    scripted_events: std::collections::VecDeque<(u32, Event)>,
+    net_meters_gained: f32,
+    meters_kicked: f32,
+    meters_carried: f32,
+}
+
+impl<'a> MatchRun<'a> {
+    fn new(initial: GameState, config: &'a SimulationConfig) -> Self {
+        MatchRun {
+            config,
+            defensive_phase_secs: config.defensive_phase_secs.max(1),
+            state: initial,
+            rng: SimRng::new(config.seed),
+            decisions: Vec::new(),
+            contexts: Vec::new(),
+            key_moments: Vec::new(),
+            longest_phase_sequence: None,
+            highest_urgency_decision: None,
+            red_zone_penalties: Vec::new(),
+            sin_bin_returns: Vec::new(),
+            scripted_events: {
+                let mut events = config.scripted_events.clone();
+                events.sort_by_key(|(at_secs, _)| *at_secs);
+                events.into()
+            },
+            net_meters_gained: 0.0,
+            meters_kicked: 0.0,
+            meters_carried: 0.0,
+        }
+    }
+
+    /// 次のフェーズに進むか（試合終了または早期終了条件を満たしたら`false`）
+    fn should_continue(&self) -> bool {
+        self.state.time_remaining_secs() > 0 && !self.should_stop()
+    }
+
+    /// 早期終了条件を満たしているか
+    fn should_stop(&self) -> bool {
+        self.config
+            .stop_when
+            .as_ref()
+            .is_some_and(|stop_when| stop_when(&self.state))
+    }
+
+    /// 時刻に達した台本の出来事を適用する
+    fn apply_scripted_events(&mut self) {
+        let now = self.state.elapsed_time_secs;
+        let score_before = self.state.score;
+        let mut due = Vec::new();
+        while let Some((at_secs, _)) = self.scripted_events.front()
+            && *at_secs <= now
+        {
+            let (_, event) = self
+                .scripted_events
+                .pop_front()
+                .expect("先頭の出来事がある");
+            due.push(event);
+        }
+        if due.is_empty() {
+            return;
+        }
+        self.state = GameState::replay(self.state.clone(), &due);
+        self.key_moments.extend(detect_key_moments(
+            None,
+            score_before,
+            self.state.score,
+            now,
+        ));
+    }
+
+    /// 1フェーズ進める
+    fn play_phase(&mut self, on_phase: &mut impl FnMut(&GameState, &TacticalDecision)) {
+        self.apply_scripted_events();
+        let config = self.config;
+        let state = &mut self.state;
+        let rng = &mut self.rng;
+
+        let score_before = state.score;
+        let mut decision = None;
+        let position_before = state.position;
+        let phase_secs;
+        if state.possession == Possession::Opposition {
+            phase_secs = self.defensive_phase_secs;
+            let in_red_zone = state.position == FieldPosition::Own22;
+            let penalized = if phase_resolves(phase_secs, rng) {
+                defend_phase(state, rng)
+            } else {
+                state.consecutive_phases = 0;
+                false
+            };
+            if penalized
+                && in_red_zone
+                && let Some(escalation) = config.card_escalation
+            {
+                let now = state.elapsed_time_secs;
+                self.red_zone_penalties
+                    .retain(|&at| at + escalation.window_secs > now);
+                self.red_zone_penalties.push(now);
+                if self.red_zone_penalties.len() as u32 >= escalation.penalties {
+                    self.red_zone_penalties.clear();
+                    state.yellow_cards += 1;
+                    self.sin_bin_returns.push(now + escalation.sin_bin_secs);
+                    self.key_moments.push(KeyMoment {
+                        at_secs: now,
+                        kind: KeyMomentKind::YellowCard,
+                        score: state.score,
+                    });
+                }
+            }
+        } else {
+            let chosen = config.engine.decide(state);
+            phase_secs = chosen.typical_phase_secs();
+            on_phase(state, &chosen);
+            let context = PhaseContext {
+                at_secs: state.elapsed_time_secs,
+                time_remaining_secs: state.time_remaining_secs(),
+                position: state.position,
+                score: state.score,
+            };
+            if self
+                .longest_phase_sequence
+                .is_none_or(|longest| state.consecutive_phases > longest.phases)
+            {
+                self.longest_phase_sequence = Some(PhaseSequence {
+                    at_secs: context.at_secs,
+                    phases: state.consecutive_phases,
+                });
+            }
+            let urgency = state.score.urgency(context.time_remaining_secs);
+            if self
+                .highest_urgency_decision
+                .as_ref()
+                .is_none_or(|highest| urgency > highest.urgency)
+            {
+                self.highest_urgency_decision = Some(UrgentDecision {
+                    at_secs: context.at_secs,
+                    urgency,
+                    decision: chosen,
+                    score: context.score,
+                });
+            }
+            let mut probability = chosen.success_probability(state);
+            let kick = match chosen {
+                TacticalDecision::Kick { kick_type } => {
+                    let outcome = sample_kick_outcome(kick_type, state, rng);
+                    // 蹴った瞬間の突風で距離が出なければ、その分だけ失敗しやすい
+                    probability *= outcome.gain_m / kick_type.base_gain();
+                    Some(outcome)
+                }
+                _ => None,
+            };
+            // キックとドロップゴールは蹴った時点で結果が決まる
+            let resolves = matches!(
+                chosen,
+                TacticalDecision::Kick { .. } | TacticalDecision::DropGoal
+            ) || phase_resolves(phase_secs, rng);
+            if resolves {
+                let success = rng.next_f32() < probability;
+                apply_outcome(state, &chosen, success, kick);
+            } else {
+                state.lineout_awarded = false;
+                state.consecutive_phases += 1;
+            }
+            self.contexts.push(context);
+            if state.score == score_before {
+                let gained = state.position.midpoint() - position_before.midpoint();
+                if kick.is_some() {
+                    self.meters_kicked += gained;
+                } else {
+                    self.meters_carried += gained;
+                }
+            }
+            self.decisions.push(chosen);
+            decision = self.decisions.last();
+        }
+        if state.score == score_before {
+            self.net_meters_gained += state.position.midpoint() - position_before.midpoint();
+        }
+        self.key_moments.extend(detect_key_moments(
+            decision,
+            score_before,
+            state.score,
+            state.elapsed_time_secs,
+        ));
+
+        let fatigue_gain = state.rules.fatigue_rate() * phase_secs as f32 / 60.0;
+        state.fatigue.forwards = (state.fatigue.forwards + fatigue_gain).min(1.0);
+        state.fatigue.backs = (state.fatigue.backs + fatigue_gain).min(1.0);
+        state.opposition_fatigue.forwards =
+            (state.opposition_fatigue.forwards + fatigue_gain).min(1.0);
+        state.opposition_fatigue.backs = (state.opposition_fatigue.backs + fatigue_gain).min(1.0);
+        let previous_secs = state.elapsed_time_secs;
+        state.elapsed_time_secs =
+            (state.elapsed_time_secs + phase_secs).min(state.rules.match_duration_secs());
+        let now = state.elapsed_time_secs;
+        // ハーフタイムで陣地を入れ替える
+        let half_time = state.rules.match_duration_secs() / 2;
+        if previous_secs < half_time && now >= half_time {
+            state.attacking_bearing = (state.attacking_bearing + 180.0).rem_euclid(360.0);
+        }
+        state.temporary_off.retain(|off| off.returns_at_secs > now);
+        let returning = self.sin_bin_returns.iter().filter(|&&at| at <= now).count() as u32;
+        self.sin_bin_returns.retain(|&at| at > now);
+        state.yellow_cards = state.yellow_cards.saturating_sub(returning);
+
+        if let Some(interval) = config.weather_interval_secs.filter(|&secs| secs > 0)
+            && previous_secs / interval != state.elapsed_time_secs / interval
+        {
+            state.weather = state.weather.next(rng);
+        }
+    }
+
+    /// 結果をまとめる
+    fn finish(self, cancelled: bool) -> MatchSummary {
+        let stopped_at_secs = (!cancelled && self.state.time_remaining_secs() > 0)
+            .then_some(self.state.elapsed_time_secs);
+        MatchSummary {
+            final_state: self.state,
+            decisions: self.decisions,
+            contexts: self.contexts,
+            stopped_early: stopped_at_secs.is_some(),
+            stopped_at_secs,
+            cancelled,
+            key_moments: self.key_moments,
+            longest_phase_sequence: self.longest_phase_sequence,
+            highest_urgency_decision: self.highest_urgency_decision,
+            net_meters_gained: self.net_meters_gained,
+            meters_kicked: self.meters_kicked,
+            meters_carried: self.meters_carried,
+        }
+    }
+}
+
+/// 試合をフェーズ単位でシミュレーション
+///
+/// 自チームボールの各フェーズで`DecisionEngine::decide`により判断し、
+/// 成功確率に基づいて結果を反映します。試合時計は判断ごとの
+/// `TacticalDecision::typical_phase_secs`（守備のフェーズは`defensive_phase_secs`）だけ進みます。
+/// フェーズが短いほど結果の決まる確率も下がるため、時間あたりの得点や
+/// ターンオーバーの起こりやすさはフェーズの長さによらません（`REFERENCE_PHASE_SECS`を参照）。
+/// キックで相手ボールになった後は、ボールを奪い返すまで判断を伴わない守備のフェーズが続きます。
+/// `stop_when`が設定されていれば、条件を満たした時点で打ち切ります。
+/// `card_escalation`が設定されていれば、自陣22m内で反則を繰り返すと
+/// イエローカードで人数が減り、シンビン明けに戻ります。
+/// `scripted_events`の出来事は、時刻に達した後の最初のフェーズの開始時に適用します。
+///
+/// `on_phase`は各フェーズの判断直後に、判断時点の状態と選んだ判断を受け取ります。
+/// 状態は参照で渡されるため、コールバックから状態を書き換えることはできません。
+pub fn simulate_match(
+    initial: GameState,
+    config: &SimulationConfig,
+    mut on_phase: impl FnMut(&GameState, &TacticalDecision),
+) -> MatchSummary {
+    let mut run = MatchRun::new(initial, config);
+    while run.should_continue() {
+        run.play_phase(&mut on_phase);
+    }
+    run.finish(false)
+}
+
+/// フェーズを前後に移動できるシミュレーション（デバッガ用）
+///
+/// 各フェーズ終了時点の状態と記録（乱数生成器の状態を含む）を履歴として保持します。
+/// `step_back`や`goto`で戻る場合は再計算せずに保存した状態をそのまま復元し、
+/// 既に計算したフェーズへ進む場合も同じ履歴を再利用します。
+/// 履歴の先へ進むときだけ新たにフェーズを計算するため、結果は常に
+/// `simulate_match`と同じ流れになります。
+pub struct SimulationSession<'a> {
+    history: Vec<MatchRun<'a>>,
+    cursor: usize,
+}
+
+impl<'a> SimulationSession<'a> {
+    /// 試合開始前（フェーズ0）のセッションを作成
+    pub fn new(initial: GameState, config: &'a SimulationConfig) -> Self {
+        SimulationSession {
+            history: vec![MatchRun::new(initial, config)],
+            cursor: 0,
+        }
+    }
+
+    /// 現在のフェーズ番号（0=開始前）
+    pub fn phase(&self) -> usize {
+        self.cursor
+    }
+
+    /// 現在のフェーズ終了時点の状態
+    pub fn state(&self) -> &GameState {
+        &self.history[self.cursor].state
+    }
+
+    /// 1フェーズ進める（試合終了または早期終了条件により進めなければ`false`）
+    pub fn step(&mut self) -> bool {
+        if self.cursor + 1 < self.history.len() {
+            self.cursor += 1;
+            return true;
+        }
+
+        let current = &self.history[self.cursor];
+        if !current.should_continue() {
+            return false;
+        }
+        let mut next = current.clone();
+        next.play_phase(&mut |_, _| {});
+        self.history.push(next);
+        self.cursor += 1;
+        true
+    }
+
+    /// 1フェーズ戻る（開始前なら`false`）
+    pub fn step_back(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.cursor -= 1;
+        true
+    }
+
+    /// 指定したフェーズへ移動する（試合終了により届かなければ`false`で、最終フェーズに留まる）
+    pub fn goto(&mut self, phase: usize) -> bool {
+        if phase < self.history.len() {
+            self.cursor = phase;
+            return true;
+        }
+        self.cursor = self.history.len() - 1;
+        while self.cursor < phase {
+            if !self.step() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// 現在のフェーズまでの結果
+    pub fn summary(&self) -> MatchSummary {
+        self.history[self.cursor].clone().finish(false)
+    }
+}
+
+/// シミュレーションの中断を伝えるトークン
+///
+/// クローンしたトークンは同じ中断状態を共有します。
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+#[cfg(feature = "async")]
+impl CancellationToken {
+    /// 中断されていないトークンを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 中断を要求する
+    pub fn cancel(&self) {
+        self.cancelled
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// 中断が要求されているか
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+/// 中断できる非同期版の`simulate_match`
+///
+/// フェーズごとに他のタスクへ実行を譲り（唯一の`.await`）、再開時に
+/// `cancel`が中断されていればその時点で終了します。フェーズの処理は
+/// `.await`をまたがないため、中断されても状態が途中まで更新されることはなく、
+/// 返される`MatchSummary`は最後に完了したフェーズまでの整合した記録です
+/// （`cancelled`が`true`になります）。
+///
+/// 返されたFuture自体をドロップした場合も、同じ理由で状態が壊れることはありません
+/// （結果は失われます）。中断されなければ`simulate_match`と同じ結果になります。
+#[cfg(feature = "async")]
+pub async fn simulate_match_cancellable(
+    initial: GameState,
+    config: &SimulationConfig,
+    cancel: CancellationToken,
+    mut on_phase: impl FnMut(&GameState, &TacticalDecision),
+) -> MatchSummary {
+    let mut run = MatchRun::new(initial, config);
+    while run.should_continue() {
+        if cancel.is_cancelled() {
+            return run.finish(true);
+        }
+        run.play_phase(&mut on_phase);
+        tokio::task::yield_now().await;
+    }
+    run.finish(false)
+}
+
+/// 同じ試合状況を複数のルールでシミュレーションして比較する
+///
+/// 基準の状態を各ルールに合わせて次のように調整してから、
+/// 既定の`SimulationConfig`（同じ乱数シード）でシミュレーションします。
+///
+/// - 経過時間: 試合時間に対する割合を保って換算（例: 15人制の40分 → 7人制の7分）
+/// - 一時退場の復帰時刻、レッドカードの時刻: 経過時間と同じ割合で換算
+/// - 疲労の蓄積速度と人数: ルールの値を使用
+/// - それ以外（スコア、位置、天候、疲労度など）: そのまま
+pub fn compare_formats(base: GameState, formats: &[GameRules]) -> Vec<(GameRules, MatchSummary)> {
+    let base_duration = base.rules.match_duration_secs().max(1) as u64;
+    let rebase = |secs: u32, rules: &GameRules| {
+        (secs as u64 * rules.match_duration_secs() as u64 / base_duration) as u32
+    };
+
+    formats
+        .iter()
+        .map(|rules| {
+            let mut state = base.clone();
+            state.rules = rules.clone();
+            state.elapsed_time_secs = rebase(base.elapsed_time_secs, rules);
+            for off in &mut state.temporary_off {
+                off.returns_at_secs = rebase(off.returns_at_secs, rules);
+            }
+            for at in &mut state.red_cards_at_secs {
+                *at = rebase(*at, rules);
+            }
+            let summary = simulate_match(state, &SimulationConfig::default(), |_, _| {});
+            (rules.clone(), summary)
+        })
+        .collect()
+}
+
+// =============================================================================
+// リプレイの比較
+// =============================================================================
+
+/// 記録したシミュレーションの1フェーズ
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayPhase {
+    /// 自チームボールのフェーズの判断（守備のフェーズは`None`）
+    pub decision: Option<TacticalDecision>,
+    /// フェーズ終了時点の状態
+    pub state: GameState,
+}
+
+/// フェーズごとの判断と状態を記録したシミュレーション
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchReplay {
+    /// シミュレーションに使った乱数シード
+    pub seed: u64,
+    /// 開始時点の状態
+    pub initial: GameState,
+    /// 各フェーズの記録（時系列順）
+    pub phases: Vec<ReplayPhase>,
+}
+
+impl MatchReplay {
+    /// `simulate_match`と同じ流れでシミュレーションし、フェーズごとに記録する
+    pub fn record(initial: GameState, config: &SimulationConfig) -> MatchReplay {
+        let mut run = MatchRun::new(initial.clone(), config);
+        let mut phases = Vec::new();
+        while run.should_continue() {
+            let mut decision = None;
+            run.play_phase(&mut |_, chosen| decision = Some(*chosen));
+            phases.push(ReplayPhase {
+                decision,
+                state: run.state.clone(),
+            });
+        }
+        MatchReplay {
+            seed: config.seed,
+            initial,
+            phases,
+        }
+    }
+}
+
+/// 2つのリプレイの食い違い
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayDivergence {
+    /// 乱数シードが異なる（設定の違いを切り分けられないため比較しない）
+    SeedMismatch { a: u64, b: u64 },
+    /// 開始時点の状態が異なる
+    InitialState,
+    /// フェーズ（0始まり）の判断が異なる
+    Decision {
+        phase: usize,
+        a: Option<TacticalDecision>,
+        b: Option<TacticalDecision>,
+    },
+    /// 判断は同じだが、フェーズ終了時点の状態が異なる
+    State { phase: usize },
+    /// フェーズ数が異なる（一方が先に終了した）
+    Length { a: usize, b: usize },
+}
+
+/// 2つのリプレイを比較し、食い違いを時系列順に返す
+///
+/// 2つのリプレイは同じ乱数シードで記録されていることを前提とします。
+/// シードが同じなら、エンジンの設定が同じ限り乱数の列も判断も一致するため、
+/// 最初の食い違いが設定変更の影響が現れたフェーズです。以降のフェーズの食い違いは、
+/// そこから連鎖した影響として続けて報告します。
+/// シードが異なる場合は`SeedMismatch`だけを返します。
+pub fn diff_replays(a: &MatchReplay, b: &MatchReplay) -> Vec<ReplayDivergence> {
+    if a.seed != b.seed {
+        return vec![ReplayDivergence::SeedMismatch {
+            a: a.seed,
+            b: b.seed,
+        }];
+    }
+
+    let mut divergences = Vec::new();
+    if a.initial != b.initial {
+        divergences.push(ReplayDivergence::InitialState);
+    }
+    for (phase, (pa, pb)) in a.phases.iter().zip(&b.phases).enumerate() {
+        if pa.decision != pb.decision {
+            divergences.push(ReplayDivergence::Decision {
+                phase,
+                a: pa.decision,
+                b: pb.decision,
+            });
+        } else if pa.state != pb.state {
+            divergences.push(ReplayDivergence::State { phase });
+        }
+    }
+    if a.phases.len() != b.phases.len() {
+        divergences.push(ReplayDivergence::Length {
+            a: a.phases.len(),
+            b: b.phases.len(),
+        });
+    }
+    divergences
+}
+
+// =============================================================================
+// シーズン集計
+// =============================================================================
+
+/// 集計のセル（フィールドポジション × スコア状況）
+pub type HistogramCell = (FieldPosition, ScoreState);
+
+/// 複数試合の判断をセルごとに集計したもの
+///
+/// 各判断は、判断を下した時点のフィールドポジションと
+/// `Score::state`によるスコア状況のセルに数えられます。
+#[derive(Debug, Clone, Default)]
+pub struct SeasonHistogram {
+    cells: std::collections::HashMap<HistogramCell, Vec<(DecisionKind, u32)>>,
+}
+
+impl SeasonHistogram {
+    /// セル内の判断の種類ごとの回数（初出順）
+    pub fn counts(&self, cell: HistogramCell) -> &[(DecisionKind, u32)] {
+        self.cells.get(&cell).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// セル内の判断の総数
+    pub fn total(&self, cell: HistogramCell) -> u32 {
+        self.counts(cell).iter().map(|&(_, count)| count).sum()
+    }
+
+    /// セル内で最も多かった判断（同数なら先に現れたもの）
+    pub fn modal_decision(&self, cell: HistogramCell) -> Option<DecisionKind> {
+        self.counts(cell)
+            .iter()
+            .fold(
+                None,
+                |best: Option<(DecisionKind, u32)>, &(kind, count)| match best {
+                    Some((_, best_count)) if best_count >= count => best,
+                    _ => Some((kind, count)),
+                },
+            )
+            .map(|(kind, _)| kind)
+    }
+
+    /// 判断が1つ以上あるセル
+    pub fn cells(&self) -> impl Iterator<Item = HistogramCell> + '_ {
+        self.cells.keys().copied()
+    }
+}
+
+/// 複数試合の判断をフィールドポジションとスコア状況で集計する
+pub fn season_histogram(matches: &[MatchSummary]) -> SeasonHistogram {
+    let mut histogram = SeasonHistogram::default();
+    for summary in matches {
+        for (decision, context) in summary.decisions.iter().zip(&summary.contexts) {
+            let cell = (
+                context.position,
+                context.score.state(context.time_remaining_secs),
+            );
+            let counts = histogram.cells.entry(cell).or_default();
+            let kind = decision.kind();
+            match counts.iter_mut().find(|(seen, _)| *seen == kind) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((kind, 1)),
+            }
+        }
+    }
+    histogram
+}
+
+/// 1試合の判断の時点のフィールドポジション × スコア状況の回数
+///
+/// 行が`FieldPosition::ALL`の順（自陣22mから敵陣22m）、列が`ScoreState::ALL`の順
+/// （大量リードから残り時間の少ないビハインド）の5×5の表です。
+/// 自チームボールの各フェーズ（`MatchSummary::contexts`）を1回として数えます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Heatmap {
+    counts: [[u32; ScoreState::ALL.len()]; FieldPosition::ALL.len()],
+}
+
+impl Heatmap {
+    /// セルの回数
+    pub fn count(&self, position: FieldPosition, state: ScoreState) -> u32 {
+        self.counts[position as usize][state as usize]
+    }
+
+    /// すべてのセルの回数の合計
+    pub fn total(&self) -> u32 {
+        self.counts.iter().flatten().sum()
+    }
+
+    /// CSVで書き出す
+    ///
+    /// 1行目はヘッダー（`position`に続けて`ScoreState::ALL`の各分類）で、
+    /// 以降はフィールドポジションごとに1行です。
+    pub fn to_csv(&self, mut w: impl std::io::Write) -> std::io::Result<()> {
+        write!(w, "position")?;
+        for state in ScoreState::ALL {
+            write!(w, ",{:?}", state)?;
+        }
+        writeln!(w)?;
+
+        for (position, row) in FieldPosition::ALL.iter().zip(&self.counts) {
+            write!(w, "{:?}", position)?;
+            for count in row {
+                write!(w, ",{}", count)?;
+            }
+            writeln!(w)?;
+        }
+        Ok(())
+    }
+}
+
+/// 1試合の判断をフィールドポジションとスコア状況で数える
+pub fn heatmap(summary: &MatchSummary) -> Heatmap {
+    let mut heatmap = Heatmap::default();
+    for context in &summary.contexts {
+        let state = context.score.state(context.time_remaining_secs);
+        heatmap.counts[context.position as usize][state as usize] += 1;
+    }
+    heatmap
+}
+
+// =============================================================================
+// CSVレポート
+// =============================================================================
+
+/// CSVのフィールドとして書き出せるよう、必要なら引用符で囲む
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 複数の状況に対する判断と理由をCSVで書き出す
+///
+/// 1行目はヘッダーで、以降は状況ごとに1行です。列は次の通りです。
+///
+/// - `own_score`, `opposition_score`: スコア
+/// - `position`, `weather`: フィールドポジションと天候
+/// - `decision`, `reason`: デフォルト設定の`DecisionEngine`の判断と理由
+/// - `top1`, `top1_score`, `top2`, `top2_score`: `weighted_scores`の評価が高い上位2つの候補
+pub fn batch_report_csv(states: &[GameState], mut w: impl std::io::Write) -> std::io::Result<()> {
+    let engine = DecisionEngine::new();
+    writeln!(
+        w,
+        "own_score,opposition_score,position,weather,decision,reason,top1,top1_score,top2,top2_score"
+    )?;
+
+    for state in states {
+        let (decision, reason) = engine.decide_with_reason(state);
+        let mut scored = engine.weighted_scores(state);
+        scored.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        let top = |rank: usize| match scored.get(rank) {
+            Some((candidate, score)) => {
+                (csv_field(&candidate.to_string()), format!("{:.2}", score))
+            }
+            None => (String::new(), String::new()),
+        };
+        let (top1, top1_score) = top(0);
+        let (top2, top2_score) = top(1);
+
+        writeln!(
+            w,
+            "{},{},{:?},{:?},{},{},{},{},{},{}",
+            state.score.own,
+            state.score.opposition,
+            state.position,
+            state.weather,
+            csv_field(&decision.to_string()),
+            csv_field(reason),
+            top1,
+            top1_score,
+            top2,
+            top2_score
+        )?;
+    }
+    Ok(())
+}
+
+// =============================================================================
+// エンジンの比較
+// =============================================================================
+
+/// `DecisionEngine::profile`で使えるプロファイル名
+pub const ENGINE_PROFILES: &[&str] = &["default", "conservative", "expansive"];
+
+impl DecisionEngine {
+    /// 名前付きの設定（プロファイル）のエンジンを作成
+    ///
+    /// - `default`: `DecisionEngine::default()`
+    /// - `conservative`: 早めにキックで陣地を取り、自陣でのリスクを重く見る
+    /// - `expansive`: フェーズを重ねてボールを保持し、得点の緊急性を重く見る
+    pub fn profile(name: &str) -> Option<DecisionEngine> {
+        let engine = match name {
+            "default" => DecisionEngine::default(),
+            "conservative" => DecisionEngine {
+                phase_budget: PhaseBudget {
+                    soft_limit: 6,
+                    hard_limit: 10,
+                },
+                headwind_threshold: 12.0,
+                weights: FactorWeights {
+                    position_risk: 1.0,
+                    ..FactorWeights::default()
+                },
+                ..DecisionEngine::default()
+            },
+            "expansive" => DecisionEngine {
+                phase_budget: PhaseBudget {
+                    soft_limit: 15,
+                    hard_limit: 25,
+                },
+                headwind_threshold: 5.0,
+                weights: FactorWeights {
+                    score_urgency: 1.0,
+                    ..FactorWeights::default()
+                },
+                ..DecisionEngine::default()
+            },
+            _ => return None,
+        };
+        Some(engine)
+    }
+}
+
+/// 表示幅が`width`になるよう右を空白で埋める（ASCII以外の文字は幅2とみなす）
+fn pad_display(text: &str, width: usize) -> String {
+    let shown: usize = text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum();
+    format!("{}{}", text, " ".repeat(width.saturating_sub(shown)))
+}
+
+/// 同じ状況に対する2つのエンジンの判断を表形式で書き出す
+///
+/// 判断と理由に続けて、各候補の`weighted_scores`の評価を左右に並べます。
+pub fn write_engine_comparison(
+    state: &GameState,
+    (name_a, engine_a): (&str, &DecisionEngine),
+    (name_b, engine_b): (&str, &DecisionEngine),
+    mut w: impl std::io::Write,
+) -> std::io::Result<()> {
+    let decide = |engine: &DecisionEngine| {
+        let (decision, reason) = engine.decide_with_reason(state);
+        (decision.to_string(), reason)
+    };
+    let (decision_a, reason_a) = decide(engine_a);
+    let (decision_b, reason_b) = decide(engine_b);
+
+    let row = |w: &mut dyn std::io::Write, label: &str, a: &str, b: &str| {
+        writeln!(
+            w,
+            "{} | {} | {}",
+            pad_display(label, 20),
+            pad_display(a, 48),
+            b
+        )
+    };
+    row(&mut w, "", name_a, name_b)?;
+    writeln!(w, "{}", "-".repeat(90))?;
+    row(&mut w, "判断", &decision_a, &decision_b)?;
+    row(&mut w, "理由", reason_a, reason_b)?;
+    writeln!(w, "{}", "-".repeat(90))?;
+    for ((candidate, score_a), (_, score_b)) in engine_a
+        .weighted_scores(state)
+        .into_iter()
+        .zip(engine_b.weighted_scores(state))
+    {
+        row(
+            &mut w,
+            &candidate.to_string(),
+            &format!("{:.2}", score_a),
+            &format!("{:.2}", score_b),
+        )?;
+    }
+    Ok(())
+}
+
+/// `compare <プリセット> --a <プロファイル> --b <プロファイル>`を処理する
+///
+/// 比較を出力した場合は`true`、`compare`以外の引数なら`false`を返します。
+/// プリセットやプロファイルが不明な場合はメッセージを表示して終了コード2で終了します。
+fn run_compare_command(args: &[String]) -> bool {
+    match compare_command(args, std::io::stdout()) {
+        Some(Ok(())) => true,
+        Some(Err(message)) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+        None => false,
+    }
+}
+
+/// `compare`サブコマンドの引数を解釈し、比較を`w`に書き出す
+///
+/// 先頭の引数が`compare`でなければ`None`を返します。プリセットやプロファイルが
+/// 不明な場合は、表示するメッセージを`Err`で返します。
+fn compare_command(args: &[String], mut w: impl std::io::Write) -> Option<Result<(), String>> {
+    if args.first().map(String::as_str) != Some("compare") {
+        return None;
+    }
+
+    let option = |flag: &str| {
+        args.iter()
+            .position(|arg| arg == flag)
+            .and_then(|i| args.get(i + 1))
+            .map(String::as_str)
+            .unwrap_or("default")
+    };
+    let preset = args.get(1).map(String::as_str).unwrap_or("close_game_late");
+    let state = match GameState::preset(preset) {
+        Ok(state) => state,
+        Err(error) => return Some(Err(error.to_string())),
+    };
+    let profile = |flag: &str| {
+        let profile_name = option(flag);
+        DecisionEngine::profile(profile_name)
+            .map(|engine| (profile_name, engine))
+            .ok_or_else(|| {
+                format!(
+                    "不明なプロファイル: {}（利用可能: {}）",
+                    profile_name,
+                    ENGINE_PROFILES.join(", ")
+                )
+            })
+    };
+    let result = profile("--a").and_then(|a| Ok((a, profile("--b")?)));
+    let ((name_a, engine_a), (name_b, engine_b)) = match result {
+        Ok(engines) => engines,
+        Err(message) => return Some(Err(message)),
+    };
+
+    writeln!(w, "【{}】", preset).expect("比較の書き出しに失敗");
+    write_engine_comparison(&state, (name_a, &engine_a), (name_b, &engine_b), w)
+        .expect("比較の書き出しに失敗");
+    Some(Ok(()))
+}
+
+// =============================================================================
+// シナリオプリセット
+// =============================================================================
+
+/// 存在しないプリセット名を指定したときのエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownPreset {
+    /// 指定されたプリセット名
+    pub name: String,
+}
+
+impl std::fmt::Display for UnknownPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "不明なプリセット: {} (利用可能: {})",
+            self.name,
+            PRESET_NAMES.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnknownPreset {}
+
+/// 利用可能なプリセット名
+pub const PRESET_NAMES: &[&str] = &["close_game_late", "rainy_red_zone"];
+
+/// 接戦の終盤、自陣でボール確保
+fn close_game_late() -> GameState {
+    GameState {
+        rules: GameRules::Fifteens,
+        elapsed_time_secs: 75 * 60, // 75分経過
+        score: Score {
+            own: 21,
+            opposition: 24,
+        }, // 3点ビハインド
+        position: FieldPosition::OwnHalf,
+        weather: Weather::Cloudy,
+        wind: Wind {
+            speed: 3.0,
+            direction: 90.0,
+            gust: 5.0,
+        },
+        attacking_bearing: 0.0,
+        fatigue: TeamFatigue {
+            forwards: 0.65,
+            backs: 0.50,
+        },
+        opposition_fatigue: TeamFatigue {
+            forwards: 0.70,
+            backs: 0.55,
+        },
+        consecutive_phases: 3,
+        penalties_conceded: 8,
+        yellow_cards: 0,
+        opposition_yellow_cards: 0,
+        temporary_off: Vec::new(),
+        card_law: CardLaw::default(),
+        red_cards_at_secs: Vec::new(),
+        lineout_awarded: false,
+        possession: Possession::Own,
+        opposition_counterattack_threat: 0.0,
+        defense: DefenseLine {
+            pressure_side: Some(Direction::Center),
+            gap_on_left: false,
+            gap_on_right: false,
+            alignment: 0.8,
+            space_behind_defense: false,
+        },
+        teammates: Teammates {
+            backs_ready: true,
+            forwards_ready: true,
+            support_count: 5,
+        },
+    }
+}
+
+/// 雨天、敵陣22m内でチャンス
+fn rainy_red_zone() -> GameState {
+    GameState {
+        rules: GameRules::Fifteens,
+        elapsed_time_secs: 35 * 60,
+        score: Score {
+            own: 14,
+            opposition: 10,
+        },
+        position: FieldPosition::Opposition22,
+        weather: Weather::Rainy,
+        wind: Wind {
+            speed: 8.0,
+            direction: 180.0,
+            gust: 12.0,
+        },
+        attacking_bearing: 0.0,
+        fatigue: TeamFatigue {
+            forwards: 0.40,
+            backs: 0.35,
+        },
+        opposition_fatigue: TeamFatigue {
+            forwards: 0.45,
+            backs: 0.40,
+        },
+        consecutive_phases: 12,
+        penalties_conceded: 3,
+        yellow_cards: 0,
+        opposition_yellow_cards: 0,
+        temporary_off: Vec::new(),
+        card_law: CardLaw::default(),
+        red_cards_at_secs: Vec::new(),
+        lineout_awarded: false,
+        possession: Possession::Own,
+        opposition_counterattack_threat: 0.0,
+        defense: DefenseLine {
+            pressure_side: None,
+            gap_on_left: true,
+            gap_on_right: false,
+            alignment: 0.6,
+            space_behind_defense: false,
+        },
+        teammates: Teammates {
+            backs_ready: true,
+            forwards_ready: true,
+            support_count: 7,
+        },
+    }
+}
+
+impl GameState {
+    /// 名前からプリセットのシナリオを取得
+    pub fn preset(name: &str) -> Result<GameState, UnknownPreset> {
+        match name {
+            "close_game_late" => Ok(close_game_late()),
+            "rainy_red_zone" => Ok(rainy_red_zone()),
+            _ => Err(UnknownPreset {
+                name: name.to_string(),
+            }),
+        }
+    }
+}
+
+/// すべてのプリセットを`(名前, 状態)`の組で順に返す
+///
+/// ベンチマークや一括判断（`DecisionEngine::batch`）でプリセットの一覧を
+/// 重複して書かずに済むよう、`PRESET_NAMES`の順序で列挙します。
+pub fn all_fixtures() -> impl Iterator<Item = (&'static str, GameState)> {
+    PRESET_NAMES.iter().map(|&name| {
+        let state = GameState::preset(name).expect("PRESET_NAMESは全て解決できる");
+        (name, state)
+    })
+}
+
+impl TryFrom<&str> for GameState {
+    type Error = UnknownPreset;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        GameState::preset(name)
+    }
+}
+
+/// コマンドライン引数の`--preset <名前>`で指定されたプリセットを解決する
+///
+/// `--preset`がなければ`None`を返します。名前が省略された場合は空の名前として
+/// 解決するため、`UnknownPreset`になります。
+fn preset_option(args: &[String]) -> Option<Result<GameState, UnknownPreset>> {
+    let index = args.iter().position(|arg| arg == "--preset")?;
+    let name = args.get(index + 1).map(String::as_str).unwrap_or("");
+    Some(GameState::try_from(name))
+}
+
+// =============================================================================
+// 状態の検証
+// =============================================================================
+
+/// `GameState::validate`が検出する、ルールと矛盾する状態
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateViolation {
+    /// 出場メンバーの人数がルールの出場人数と異なる
+    SquadSize { expected: u32, actual: u32 },
+    /// 経過時間が試合時間を超えている
+    ElapsedBeyondFullTime {
+        elapsed_secs: u32,
+        duration_secs: u32,
+    },
+    /// 退いている人数（イエロー、レッド、一時退場）が出場人数以上で、誰も残っていない
+    TooManyPlayersOff { off: u32, players: u32 },
+    /// 相手チームのイエローカード人数が出場人数以上で、誰も残っていない
+    TooManyOppositionOff { off: u32, players: u32 },
+    /// 一時退場の人数が交代できる人数を超えている
+    TooManyTemporaryOff { off: u32, substitutions: u32 },
+    /// サポートの人数がボール保持者以外の出場人数を超えている
+    SupportExceedsPlayers { support: u32, max: u32 },
+    /// 経過時間の間に重ねられない連続フェーズ数
+    ImplausiblePhaseCount { phases: u32, max: u32 },
+}
+
+impl std::fmt::Display for StateViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StateViolation::SquadSize { expected, actual } => {
+                write!(
+                    f,
+                    "出場メンバーが{}人です（ルールでは{}人）",
+                    actual, expected
+                )
+            }
+            StateViolation::ElapsedBeyondFullTime {
+                elapsed_secs,
+                duration_secs,
+            } => write!(
+                f,
+                "経過時間{}秒が試合時間{}秒を超えています",
+                elapsed_secs, duration_secs
+            ),
+            StateViolation::TooManyPlayersOff { off, players } => {
+                write!(f, "退いている{}人が出場人数{}人以上です", off, players)
+            }
+            StateViolation::TooManyOppositionOff { off, players } => write!(
+                f,
+                "相手の退いている{}人が出場人数{}人以上です",
+                off, players
+            ),
+            StateViolation::TooManyTemporaryOff { off, substitutions } => write!(
+                f,
+                "一時退場の{}人が交代できる人数{}人を超えています",
+                off, substitutions
+            ),
+            StateViolation::SupportExceedsPlayers { support, max } => {
+                write!(f, "サポート{}人が上限{}人を超えています", support, max)
+            }
+            StateViolation::ImplausiblePhaseCount { phases, max } => write!(
+                f,
+                "連続フェーズ数{}が経過時間で可能な{}を超えています",
+                phases, max
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StateViolation {}
+
+impl GameState {
+    /// ルールに依存する制約を確認し、矛盾をすべて返す
+    ///
+    /// 人数に関わる制約はルール（`GameRules::players`、`GameRules::substitutions`）から
+    /// 求めるため、15人制・10人制・7人制でそれぞれ次の値になります。
+    ///
+    /// - 出場メンバー（`squad`）: フォワードとバックスの合計が15/10/7人
+    /// - 経過時間: 試合時間（80/60/14分）以内
+    /// - 退いている人数: イエロー・レッド・一時退場の合計が出場人数未満
+    ///   （相手チームのイエローカード人数も同様）
+    /// - 一時退場: 交代できる人数（8/5/5人）以内
+    /// - サポート: ボール保持者を除いた出場人数（14/9/6人）以内
+    /// - 連続フェーズ数: 最短のフェーズ（クラッシュボールの8秒）で重ねた場合に
+    ///   経過時間内に収まる数以内
+    pub fn validate(&self, squad: &Squad) -> Result<(), Vec<StateViolation>> {
+        let players = self.rules.players();
+        let mut violations = Vec::new();
+
+        let squad_size = squad.forwards + squad.backs;
+        if squad_size != players {
+            violations.push(StateViolation::SquadSize {
+                expected: players,
+                actual: squad_size,
+            });
+        }
+
+        let duration_secs = self.rules.match_duration_secs();
+        if self.elapsed_time_secs > duration_secs {
+            violations.push(StateViolation::ElapsedBeyondFullTime {
+                elapsed_secs: self.elapsed_time_secs,
+                duration_secs,
+            });
+        }
+
+        let off = self.yellow_cards + self.players_red_carded() + self.players_temporarily_off();
+        if off >= players {
+            violations.push(StateViolation::TooManyPlayersOff { off, players });
+        }
+        if self.opposition_yellow_cards >= players {
+            violations.push(StateViolation::TooManyOppositionOff {
+                off: self.opposition_yellow_cards,
+                players,
+            });
+        }
+
+        let temporary_off = self.players_temporarily_off();
+        let substitutions = self.rules.substitutions();
+        if temporary_off > substitutions {
+            violations.push(StateViolation::TooManyTemporaryOff {
+                off: temporary_off,
+                substitutions,
+            });
+        }
+
+        let max_support = players.saturating_sub(1);
+        if self.teammates.support_count > max_support {
+            violations.push(StateViolation::SupportExceedsPlayers {
+                support: self.teammates.support_count,
+                max: max_support,
+            });
+        }
+
+        let shortest_phase_secs = TacticalDecision::Crash.typical_phase_secs();
+        let max_phases = self.elapsed_time_secs / shortest_phase_secs + 1;
+        if self.consecutive_phases > max_phases {
+            violations.push(StateViolation::ImplausiblePhaseCount {
+                phases: self.consecutive_phases,
+                max: max_phases,
+            });
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+// =============================================================================
+// 状態の差分更新
+// =============================================================================
+
+/// `GameState`の一部のフィールドだけを更新する差分
+///
+/// `Some`のフィールドだけを上書きし、`None`のフィールドは変更しません。
+/// 各フィールドは`GameState`の同名のフィールドに対応します。
+/// 一時退場・レッドカードの履歴など、リストで持つフィールドは対象外です。
+/// シリアライズ形式では省略したフィールドが`None`になります。
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GameStatePatch {
+    pub rules: Option<GameRules>,
+    pub elapsed_time_secs: Option<u32>,
+    pub score: Option<Score>,
+    pub position: Option<FieldPosition>,
+    pub weather: Option<Weather>,
+    pub wind: Option<Wind>,
+    pub attacking_bearing: Option<f32>,
+    pub fatigue: Option<TeamFatigue>,
+    pub opposition_fatigue: Option<TeamFatigue>,
+    pub consecutive_phases: Option<u32>,
+    pub penalties_conceded: Option<u32>,
+    pub yellow_cards: Option<u32>,
+    pub opposition_yellow_cards: Option<u32>,
+    pub card_law: Option<CardLaw>,
+    pub lineout_awarded: Option<bool>,
+    pub possession: Option<Possession>,
+    pub opposition_counterattack_threat: Option<f32>,
+    pub defense: Option<DefenseLine>,
+    pub teammates: Option<Teammates>,
+}
+
+/// 差分で更新できる`GameState`のフィールド（`GameStatePatch`のフィールドに対応）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameStateField {
+    Rules,
+    ElapsedTime,
+    Score,
+    Position,
+    Weather,
+    Wind,
+    AttackingBearing,
+    Fatigue,
+    OppositionFatigue,
+    ConsecutivePhases,
+    PenaltiesConceded,
+    YellowCards,
+    OppositionYellowCards,
+    CardLaw,
+    LineoutAwarded,
+    Possession,
+    OppositionCounterattackThreat,
+    Defense,
+    Teammates,
+}
+
+/// 値が指定されていて現在値と異なる場合だけ上書きし、変更を記録する
+fn patch_field<T: PartialEq>(
+    target: &mut T,
+    value: Option<T>,
+    field: GameStateField,
+    changed: &mut Vec<GameStateField>,
+) {
+    if let Some(value) = value
+        && *target != value
+    {
+        *target = value;
+        changed.push(field);
+    }
+}
+
+impl GameState {
+    /// 差分を適用し、実際に値が変わったフィールドを返す
+    ///
+    /// 差分に含まれていても現在と同じ値のフィールドは、変更として数えません。
+    pub fn apply(&mut self, patch: GameStatePatch) -> Vec<GameStateField> {
+        use GameStateField as F;
+
+        let mut changed = Vec::new();
+        patch_field(&mut self.rules, patch.rules, F::Rules, &mut changed);
+        patch_field(
+            &mut self.elapsed_time_secs,
+            patch.elapsed_time_secs,
+            F::ElapsedTime,
+            &mut changed,
+        );
+        patch_field(&mut self.score, patch.score, F::Score, &mut changed);
+        patch_field(
+            &mut self.position,
+            patch.position,
+            F::Position,
+            &mut changed,
+        );
+        patch_field(&mut self.weather, patch.weather, F::Weather, &mut changed);
+        patch_field(&mut self.wind, patch.wind, F::Wind, &mut changed);
+        patch_field(
+            &mut self.attacking_bearing,
+            patch.attacking_bearing,
+            F::AttackingBearing,
+            &mut changed,
+        );
+        patch_field(&mut self.fatigue, patch.fatigue, F::Fatigue, &mut changed);
+        patch_field(
+            &mut self.opposition_fatigue,
+            patch.opposition_fatigue,
+            F::OppositionFatigue,
+            &mut changed,
+        );
+        patch_field(
+            &mut self.consecutive_phases,
+            patch.consecutive_phases,
+            F::ConsecutivePhases,
+            &mut changed,
+        );
+        patch_field(
+            &mut self.penalties_conceded,
+            patch.penalties_conceded,
+            F::PenaltiesConceded,
+            &mut changed,
+        );
+        patch_field(
+            &mut self.yellow_cards,
+            patch.yellow_cards,
+            F::YellowCards,
+            &mut changed,
+        );
+        patch_field(
+            &mut self.opposition_yellow_cards,
+            patch.opposition_yellow_cards,
+            F::OppositionYellowCards,
+            &mut changed,
+        );
+        patch_field(&mut self.card_law, patch.card_law, F::CardLaw, &mut changed);
+        patch_field(
+            &mut self.lineout_awarded,
+            patch.lineout_awarded,
+            F::LineoutAwarded,
+            &mut changed,
+        );
+        patch_field(
+            &mut self.possession,
+            patch.possession,
+            F::Possession,
+            &mut changed,
+        );
+        patch_field(
+            &mut self.opposition_counterattack_threat,
+            patch.opposition_counterattack_threat,
+            F::OppositionCounterattackThreat,
+            &mut changed,
+        );
+        patch_field(&mut self.defense, patch.defense, F::Defense, &mut changed);
+        patch_field(
+            &mut self.teammates,
+            patch.teammates,
+            F::Teammates,
+            &mut changed,
+        );
+        changed
+    }
+}
+
+// =============================================================================
+// イベントからの再構築
+// =============================================================================
+
+/// カードの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Card {
+    /// イエローカード（一時退場）
+    Yellow,
+    /// レッドカード（退場）
+    Red,
+}
+
+/// 試合中の出来事
+///
+/// 出来事の列を`GameState::replay`で順に適用すると、任意の時点の状態を再構築できます。
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// 得点: `team`の得点に`kind`の点数（試合のルールの`ScoringValues`）を加え、
+    /// 攻撃が終わるため連続フェーズ数を0に戻す
+    ScoreEvent { team: Team, kind: ScoreKind },
+    /// フェーズの開始: 経過時間を`at_secs`にし、連続フェーズ数を1増やす
+    PhaseStarted { at_secs: u32 },
+    /// フィールドポジションの変化: `position`に移動する
+    PositionChanged { position: FieldPosition },
+    /// カード: 自チームのイエローは`yellow_cards`、レッドは`red_cards_at_secs`
+    /// （現在の経過時間）に記録する。相手チームは人数だけを管理しているため、
+    /// イエロー・レッドとも`opposition_yellow_cards`に数える
+    CardIssued { team: Team, card: Card },
+}
+
+impl GameState {
+    /// 初期状態に出来事を順に適用した状態
+    ///
+    /// `apply`による差分更新に対する、イベントソーシング版の再構築です。
+    pub fn replay(initial: GameState, events: &[Event]) -> GameState {
+        events.iter().fold(initial, |mut state, event| {
+            match *event {
+                Event::ScoreEvent { team, kind } => {
+                    let points = state.rules.scoring().points(kind);
+                    state.score += match team {
+                        Team::Own => Score {
+                            own: points,
+                            opposition: 0,
+                        },
+                        Team::Opposition => Score {
+                            own: 0,
+                            opposition: points,
+                        },
+                    };
+                    state.consecutive_phases = 0;
+                }
+                Event::PhaseStarted { at_secs } => {
+                    state.elapsed_time_secs = at_secs;
+                    state.consecutive_phases += 1;
+                }
+                Event::PositionChanged { position } => state.position = position,
+                Event::CardIssued {
+                    team: Team::Own,
+                    card: Card::Yellow,
+                } => state.yellow_cards += 1,
+                Event::CardIssued {
+                    team: Team::Own,
+                    card: Card::Red,
+                } => state.red_cards_at_secs.push(state.elapsed_time_secs),
+                Event::CardIssued {
+                    team: Team::Opposition,
+                    ..
+                } => state.opposition_yellow_cards += 1,
+            }
+            state
+        })
+    }
+}
+
+// =============================================================================
+// WebAssembly向けのエクスポート
+// =============================================================================
+
+/// JSONの`GameState`から判断し、判断をJSONで返す
+///
+/// 入力はシナリオファイルと同じ形式（`GameState`のシリアライズ形式）です。
+/// 出力は`TacticalDecision`のシリアライズ形式で、例えば`"Crash"`や
+/// `{"PassSpread":{"direction":"Left"}}`になります。
+/// 入力を読み込めない場合は、その理由を`Err`で返します。
+#[cfg(feature = "wasm")]
+pub fn decide_json(state_json: &str) -> Result<String, String> {
+    let state: GameState = serde_json::from_str(state_json).map_err(|error| error.to_string())?;
+    serde_json::to_string(&DecisionEngine::new().decide(&state)).map_err(|error| error.to_string())
+}
+
+/// JavaScriptから呼び出す`decide`（`decide_json`と同じ）
+///
+/// 入力を読み込めない場合はJavaScriptの`Error`を投げます。
+#[cfg(feature = "wasm")]
+#[wasm_bindgen::prelude::wasm_bindgen(js_name = decide)]
+pub fn wasm_decide(state_json: &str) -> Result<String, wasm_bindgen::JsError> {
+    decide_json(state_json).map_err(|message| wasm_bindgen::JsError::new(&message))
+}
+
+// =============================================================================
+// 判断の回帰チェック（ゴールデンファイル）
+// =============================================================================
+
+/// ゴールデンファイルのパス
+#[cfg(any(test, feature = "test-util"))]
+const GOLDEN_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/examples/golden/complex_game_decisions.txt"
+);
+
+/// ゴールデンファイルに記録する状態の数
+#[cfg(any(test, feature = "test-util"))]
+const GOLDEN_CORPUS_SIZE: u64 = 50;
+
+/// 固定シードで生成した状態に対する判断の一覧（1行1状態）
+#[cfg(any(test, feature = "test-util"))]
+pub fn golden_corpus() -> String {
+    let engine = DecisionEngine::default();
+    (1..=GOLDEN_CORPUS_SIZE)
+        .map(|seed| {
+            let state = GameState::random(&mut SimRng::new(seed));
+            format!("{}\t{:?}\n", seed, engine.decide(&state))
+        })
+        .collect()
+}
+
+/// 現在の判断をゴールデンファイルと比較し、差分のあった行を返す
+#[cfg(any(test, feature = "test-util"))]
+pub fn check_golden() -> std::io::Result<Vec<String>> {
+    let expected = std::fs::read_to_string(GOLDEN_PATH)?;
+    let actual = golden_corpus();
+    Ok(expected
+        .lines()
+        .zip(actual.lines())
+        .filter(|(expected, actual)| expected != actual)
+        .map(|(expected, actual)| format!("- {}\n+ {}", expected, actual))
+        .chain(
+            (expected.lines().count() != actual.lines().count())
+                .then(|| "行数が一致しません".to_string()),
+        )
+        .collect())
+}
+
+/// コマンドライン引数に応じてゴールデンファイルを再生成・検証する
+///
+/// - `--regenerate-golden`: 現在の判断でゴールデンファイルを上書き
+/// - `--check-golden`: ゴールデンファイルと比較し、差分があれば終了コード1で終了
+///
+/// コーパスは`GameState::random`で生成するため`test-util`フィーチャーが必要です。
+/// フィーチャーなしで指定した場合は終了コード2で終了します。
+/// いずれかを処理した場合は`true`を返します。
+fn run_golden_command(args: &[String]) -> bool {
+    let regenerate = args.iter().any(|arg| arg == "--regenerate-golden");
+    if regenerate || args.iter().any(|arg| arg == "--check-golden") {
+        run_golden(regenerate);
+        true
+    } else {
+        false
+    }
+}
+
+/// ゴールデンファイルを再生成（`regenerate`）または検証する
+#[cfg(any(test, feature = "test-util"))]
+fn run_golden(regenerate: bool) {
+    if regenerate {
+        std::fs::write(GOLDEN_PATH, golden_corpus()).expect("ゴールデンファイルの書き込みに失敗");
+        println!("✅ ゴールデンファイルを再生成しました: {}", GOLDEN_PATH);
+        return;
+    }
+    let diffs = check_golden().expect("ゴールデンファイルの読み込みに失敗");
+    if diffs.is_empty() {
+        println!("✅ 判断はゴールデンファイルと一致しています");
+    } else {
+        for diff in &diffs {
+            println!("{}", diff);
+        }
+        println!("❌ {}件の判断が変化しました", diffs.len());
+        std::process::exit(1);
+    }
+}
+
+/// `test-util`なしのビルド: コーパスを生成できないため終了コード2で終了する
+#[cfg(not(any(test, feature = "test-util")))]
+fn run_golden(_regenerate: bool) {
+    eprintln!("ゴールデンファイルの検証・再生成には`--features test-util`が必要です");
+    std::process::exit(2);
+}
+
+// =============================================================================
+// メイン実行
+// =============================================================================
+
+#[cfg(feature = "async")]
+#[tokio::main]
 async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if run_golden_command(&args) || run_compare_command(&args) {
+        return;
+    }
+    // `--csv`でプリセットの判断をCSVで出力
+    if args.iter().any(|arg| arg == "--csv") {
+        let states: Vec<GameState> = all_fixtures().map(|(_, state)| state).collect();
+        batch_report_csv(&states, std::io::stdout()).expect("CSVの書き出しに失敗");
+        return;
+    }
+
+    // `--instant`で演出の待ち時間を省略
+    let config =
+        SimulationConfig::default().with_timing(if args.iter().any(|arg| arg == "--instant") {
+            Timing::instant()
+        } else {
+            Timing::realistic()
+        });
+    let timing = config.timing;
+
+    // `--preset <名前>`で指定したプリセットだけを分析
+    if let Some(preset) = preset_option(&args) {
+        let state = preset.unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            std::process::exit(2);
+        });
+        analyze_game_state(&state, &timing).await;
+        let decision = make_complex_decision(&state, &timing).await;
+        println!("\n✨ 最終判断: {}", decision);
+        return;
+    }
+
     println!("🏉 複雑なゲームシミュレーション - Rust 2024 Edition\n");
     println!("{}", "=".repeat(60));
 
-    // シナリオ1: 接戦の終盤
-    println!("\n【シナリオ1】接戦の終盤、自陣でボール確保");
-    let state1 = GameState {
-        rules: GameRules::Fifteens,
-        elapsed_time_secs: 75 * 60, // 75分経過
-        score: Score {
-            own: 21,
-            opposition: 24,
-        }, // 3点ビハインド
-        position: FieldPosition::OwnHalf,
-        weather: Weather::Cloudy,
-        wind: Wind {
-            speed: 3.0,
-            direction: 90.0,
-        },
-        fatigue: TeamFatigue {
-            forwards: 0.65,
-            backs: 0.50,
-        },
-        consecutive_phases: 3,
-        penalties_conceded: 8,
-        yellow_cards: 0,
-        defense: DefenseLine {
-            pressure: true,
-            gap_on_left: false,
-            gap_on_right: false,
-            alignment: 0.8,
-        },
-        teammates: Teammates {
+    // シナリオ1: 接戦の終盤
+    println!("\n【シナリオ1】接戦の終盤、自陣でボール確保");
+    let state1 = GameState::preset("close_game_late").expect("組み込みプリセット");
+
+    analyze_game_state(&state1, &timing).await;
+    let decision1 = make_complex_decision(&state1, &timing).await;
+    println!("\n✨ 最終判断: {}", decision1);
+
+    println!("\n{}", "=".repeat(60));
+
+    // シナリオ2: 悪天候、得点圏内
+    println!("\n【シナリオ2】雨天、敵陣22m内でチャンス");
+    let state2 = GameState::preset("rainy_red_zone").expect("組み込みプリセット");
+
+    analyze_game_state(&state2, &timing).await;
+    let decision2 = make_complex_decision(&state2, &timing).await;
+    println!("\n✨ 最終判断: {}", decision2);
+
+    println!("\n{}", "=".repeat(60));
+
+    // シナリオ3: 試合全体のシミュレーション（30点差で打ち切り）
+    println!("\n【シナリオ3】シナリオ2から試合終了までシミュレーション");
+    let summary = simulate_match(
+        state2,
+        &config.with_stop_when(|state: &GameState| state.score.difference().abs() >= 30),
+        |_, _| {},
+    );
+    println!(
+        "📊 最終スコア: {} - {} ({}フェーズ)",
+        summary.final_state.score.own,
+        summary.final_state.score.opposition,
+        summary.decisions.len()
+    );
+    println!(
+        "📏 陣地: 合計{:+.0}m（キック{:+.0}m / キャリー{:+.0}m）",
+        summary.net_meters_gained, summary.meters_kicked, summary.meters_carried
+    );
+    for moment in &summary.key_moments {
+        println!(
+            "  {:>2}分 {:?} ({} - {})",
+            moment.at_secs / 60,
+            moment.kind,
+            moment.score.own,
+            moment.score.opposition
+        );
+    }
+    if let Some(longest) = summary.longest_phase_sequence {
+        println!(
+            "🔁 最長の連続フェーズ: {}フェーズ（{}分）",
+            longest.phases,
+            longest.at_secs / 60
+        );
+    }
+    if let Some(urgent) = &summary.highest_urgency_decision {
+        println!(
+            "🔥 最も緊急性の高い判断: {}（{}分、緊急性{:.0}%）",
+            urgent.decision,
+            urgent.at_secs / 60,
+            urgent.urgency * 100.0
+        );
+    }
+    if let Some(secs) = summary.stopped_at_secs {
+        println!("⏹️  {}分{}秒で大勢決着のため打ち切り", secs / 60, secs % 60);
+    }
+
+    println!("\n{}", "=".repeat(60));
+    println!("\n✅ シミュレーション完了！");
+    println!("\n💡 このシミュレーションは、複数の変数を考慮した");
+    println!("   現実的な意思決定プロセスを示しています。");
+}
+
+/// `async`フィーチャーなしのビルド: 同期APIだけでプリセットの判断を表示する
+#[cfg(not(feature = "async"))]
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if run_golden_command(&args) || run_compare_command(&args) {
+        return;
+    }
+
+    let states: Vec<GameState> = match preset_option(&args) {
+        Some(preset) => vec![preset.unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            std::process::exit(2);
+        })],
+        None => all_fixtures().map(|(_, state)| state).collect(),
+    };
+    // `--csv`で判断をCSVで出力
+    if args.iter().any(|arg| arg == "--csv") {
+        batch_report_csv(&states, std::io::stdout()).expect("CSVの書き出しに失敗");
+        return;
+    }
+
+    let engine = DecisionEngine::new();
+    for state in &states {
+        report_game_state(state, &mut std::io::stdout(), Locale::default());
+        let (decision, reason) = engine.decide_with_reason(state);
+        println!("\n{}", reason);
+        println!("\n✨ 最終判断: {}", decision);
+    }
+}
+
+// =============================================================================
+// テスト
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 中盤、同点、晴天無風、ギャップなしでフォワード準備完了の標準的な状況
+    fn neutral_state() -> GameState {
+        GameState {
+            elapsed_time_secs: 30 * 60,
+            score: Score {
+                own: 10,
+                opposition: 10,
+            },
+            position: FieldPosition::Midfield,
+            weather: Weather::Sunny,
+            wind: Wind {
+                speed: 0.0,
+                direction: 0.0,
+                gust: 0.0,
+            },
+            fatigue: TeamFatigue {
+                forwards: 0.3,
+                backs: 0.3,
+            },
+            consecutive_phases: 2,
+            penalties_conceded: 2,
+            defense: DefenseLine {
+                pressure_side: None,
+                gap_on_left: false,
+                gap_on_right: false,
+                alignment: 0.8,
+                space_behind_defense: false,
+            },
+            ..close_game_late()
+        }
+    }
+
+    /// 横風12m/sのシナリオ
+    fn crosswind_state() -> GameState {
+        GameState {
+            wind: Wind {
+                speed: 12.0,
+                direction: 90.0,
+                gust: 12.0,
+            },
+            ..close_game_late()
+        }
+    }
+
+    #[test]
+    fn crosswind_penalizes_high_punt_more_than_grubber() {
+        let state = crosswind_state();
+        let punt_loss =
+            1.0 - estimate_kick_gain(KickType::HighPunt, &state) / KickType::HighPunt.base_gain();
+        let grubber_loss =
+            1.0 - estimate_kick_gain(KickType::Grubber, &state) / KickType::Grubber.base_gain();
+        assert!(punt_loss > grubber_loss * 5.0);
+        assert!(estimate_kick_gain(KickType::Grubber, &state) > 0.0);
+    }
+
+    #[test]
+    fn strong_crosswind_makes_choose_kick_type_pick_a_grubber() {
+        let state = crosswind_state();
+        assert_eq!(choose_kick_type(&state), KickType::Grubber);
+
+        let mut calm = crosswind_state();
+        calm.wind.speed = 0.0;
+        assert_ne!(choose_kick_type(&calm), KickType::Grubber);
+    }
+
+    #[test]
+    fn stop_when_ends_match_at_thirty_point_lead() {
+        let initial = GameState {
+            elapsed_time_secs: 20 * 60,
+            score: Score {
+                own: 35,
+                opposition: 7,
+            },
+            ..rainy_red_zone()
+        };
+        let config = SimulationConfig {
+            stop_when: Some(Box::new(|state: &GameState| state.score.difference() >= 30)),
+            ..SimulationConfig::default()
+        };
+        let summary = simulate_match(initial, &config, |_, _| {});
+
+        assert!(summary.stopped_early);
+        let stopped_at = summary.stopped_at_secs.expect("早期終了した時刻");
+        assert!(stopped_at < GameRules::Fifteens.match_duration_secs());
+        assert!(summary.final_state.score.difference() >= 30);
+    }
+
+    #[test]
+    fn default_config_plays_full_match() {
+        let summary = simulate_match(rainy_red_zone(), &SimulationConfig::default(), |_, _| {});
+        assert!(!summary.stopped_early);
+        assert_eq!(summary.stopped_at_secs, None);
+        assert_eq!(summary.final_state.time_remaining_secs(), 0);
+    }
+
+    #[test]
+    fn scripted_try_lands_on_the_first_phase_at_minute_50() {
+        let minute_50 = 50 * 60;
+        let initial = GameState {
+            elapsed_time_secs: 45 * 60,
+            ..close_game_late()
+        };
+        let plain = SimulationConfig::default();
+        let scripted = SimulationConfig::default().with_scripted_events([(
+            minute_50,
+            Event::ScoreEvent {
+                team: Team::Opposition,
+                kind: ScoreKind::Try,
+            },
+        )]);
+        let mut expected = SimulationSession::new(initial.clone(), &plain);
+        let mut session = SimulationSession::new(initial, &scripted);
+
+        // 50分に達するまでは台本なしと同じ流れになる
+        while session.state().elapsed_time_secs < minute_50 {
+            assert_eq!(session.state(), expected.state());
+            assert!(session.step() && expected.step());
+        }
+        let phase_start = session.state().clone();
+        assert!(session.step());
+
+        let try_points = phase_start.rules.scoring().try_points;
+        let moment = session
+            .summary()
+            .key_moments
+            .into_iter()
+            .find(|moment| {
+                moment.kind == KeyMomentKind::TryConceded
+                    && moment.at_secs == phase_start.elapsed_time_secs
+            })
+            .expect("台本のトライがフェーズの開始時刻に記録される");
+        assert_eq!(
+            moment.score.opposition,
+            phase_start.score.opposition + try_points
+        );
+    }
+
+    /// 風速だけを変えた状態
+    fn with_wind_speed(speed: f32) -> GameState {
+        let mut state = close_game_late();
+        state.wind.speed = speed;
+        state
+    }
+
+    #[test]
+    fn sub_threshold_wind_change_hashes_identically() {
+        let base = with_wind_speed(3.0);
+        let jittered = with_wind_speed(3.3);
+        assert_eq!(base.snapshot_hash(), jittered.snapshot_hash());
+    }
+
+    #[test]
+    fn decision_relevant_change_changes_hash() {
+        let base = close_game_late();
+        let windier = with_wind_speed(6.0);
+        let mut scored = close_game_late();
+        scored.score.own += 3;
+        assert_ne!(base.snapshot_hash(), windier.snapshot_hash());
+        assert_ne!(base.snapshot_hash(), scored.snapshot_hash());
+    }
+
+    #[test]
+    fn coarser_quantization_yields_more_cache_hits() {
+        let states: Vec<GameState> = (0..20)
+            .map(|i| {
+                let mut state = with_wind_speed(3.0 + i as f32 * 0.3);
+                state.fatigue.forwards += i as f32 * 0.02;
+                state.defense.alignment += i as f32 * 0.01;
+                state
+            })
+            .collect();
+        let cache_hits = |quantization: QuantizationConfig| {
+            let mut cache = std::collections::HashSet::new();
+            states
+                .iter()
+                .filter(|state| !cache.insert(state.snapshot_hash_with(&quantization)))
+                .count()
+        };
+
+        let fine = QuantizationConfig {
+            fatigue_step: 0.01,
+            alignment_step: 0.01,
+            wind_step: 0.1,
+        };
+        let coarse = QuantizationConfig {
+            fatigue_step: 0.25,
+            alignment_step: 0.25,
+            wind_step: 5.0,
+        };
+        assert!(cache_hits(coarse) > cache_hits(QuantizationConfig::default()));
+        assert!(cache_hits(QuantizationConfig::default()) > cache_hits(fine));
+    }
+
+    #[test]
+    fn non_positive_quantization_step_keeps_values_distinct() {
+        let base = with_wind_speed(3.0);
+        // 既定の単位では同じ区間に入る小さな変化が、その項目の単位を0以下にすると区別される
+        let check = |nudge: fn(&mut GameState), set_step: fn(&mut QuantizationConfig, f32)| {
+            let mut nudged = base.clone();
+            nudge(&mut nudged);
+            assert_eq!(base.snapshot_hash(), nudged.snapshot_hash());
+            for step in [0.0, -1.0, f32::NAN] {
+                let mut exact = QuantizationConfig::default();
+                set_step(&mut exact, step);
+                assert_ne!(
+                    base.snapshot_hash_with(&exact),
+                    nudged.snapshot_hash_with(&exact),
+                    "step={step}"
+                );
+                assert_eq!(
+                    base.snapshot_hash_with(&exact),
+                    base.clone().snapshot_hash_with(&exact)
+                );
+            }
+        };
+
+        check(
+            |state| state.wind.speed += 0.001,
+            |config, step| config.wind_step = step,
+        );
+        check(
+            |state| state.fatigue.forwards += 0.001,
+            |config, step| config.fatigue_step = step,
+        );
+        check(
+            |state| state.defense.alignment += 0.001,
+            |config, step| config.alignment_step = step,
+        );
+    }
+
+    #[test]
+    fn sevens_duration_is_fourteen_minutes() {
+        assert_eq!(
+            Duration::from(GameRules::Sevens),
+            Duration::from_secs(14 * 60)
+        );
+        assert_eq!(
+            Duration::from(GameRules::Fifteens),
+            Duration::from_secs(80 * 60)
+        );
+    }
+
+    #[test]
+    fn elapsed_and_remaining_sum_to_match_duration() {
+        let state = close_game_late();
+        assert_eq!(state.elapsed(), Duration::from_secs(75 * 60));
+        assert_eq!(state.remaining(), Duration::from_secs(5 * 60));
+        assert_eq!(state.elapsed() + state.remaining(), state.match_duration());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test(start_paused = true)]
+    async fn analysis_sink_captures_score_and_fatigue_lines() {
+        let mut buffer: Vec<u8> = Vec::new();
+        analyze_game_state_to(
+            &close_game_late(),
+            &mut buffer,
+            Locale::Japanese,
+            &Timing::instant(),
+        )
+        .await;
+
+        let output = String::from_utf8(buffer).expect("UTF-8で書き出される");
+        assert!(output.contains("📊 スコア: 21 - 24 (差: -3点)"));
+        assert!(output.contains("😓 疲労度: FW 65% / BK 50%"));
+    }
+
+    /// `--no-default-features`（tokioなし）でも実行される最小構成の利用例
+    #[test]
+    fn pure_api_reports_and_decides_without_async_runtime() {
+        let state = close_game_late();
+        let mut buffer: Vec<u8> = Vec::new();
+        report_game_state(&state, &mut buffer, Locale::Japanese);
+        let output = String::from_utf8(buffer).expect("UTF-8で書き出される");
+        assert!(output.contains("📊 スコア: 21 - 24 (差: -3点)"));
+
+        let decision = DecisionEngine::new().decide(&state);
+        let summary = simulate_match(state, &SimulationConfig::default(), |_, _| {});
+        assert!(!summary.decisions.is_empty());
+        assert_eq!(summary.decisions[0], decision);
+    }
+
+    fn with_phases(mut state: GameState, consecutive_phases: u32) -> GameState {
+        state.consecutive_phases = consecutive_phases;
+        state
+    }
+
+    #[test]
+    fn flat_rushing_line_with_space_behind_selects_a_grubber() {
+        let mut state = neutral_state();
+        state.position = FieldPosition::OppositionHalf;
+        state.defense.pressure_side = Some(Direction::Center);
+        assert_ne!(choose_kick_type(&state), KickType::Grubber);
+
+        state.defense.space_behind_defense = true;
+        assert_eq!(choose_kick_type(&state), KickType::Grubber);
+
+        // 背後にスペースがあっても、プレッシャーがなければ価値で選ぶ
+        let mut unpressured = state.clone();
+        unpressured.defense.pressure_side = None;
+        assert_ne!(choose_kick_type(&unpressured), KickType::Grubber);
+
+        // 自陣では背後を狙わない
+        let mut own_half = state.clone();
+        own_half.position = FieldPosition::OwnHalf;
+        assert_ne!(choose_kick_type(&own_half), KickType::Grubber);
+    }
+
+    #[test]
+    fn rain_raises_combined_kick_preference() {
+        let calm = Wind {
+            speed: 0.0,
+            direction: 0.0,
+            gust: 0.0,
+        };
+        let sunny = combined_kick_preference(FieldPosition::Midfield, Weather::Sunny, &calm);
+        let rainy = combined_kick_preference(FieldPosition::Midfield, Weather::Rainy, &calm);
+        assert_eq!(sunny, FieldPosition::Midfield.kick_preference());
+        assert!(rainy > sunny);
+
+        let gale = Wind {
+            speed: 25.0,
+            ..calm
+        };
+        assert!(combined_kick_preference(FieldPosition::Midfield, Weather::Sunny, &gale) < sunny);
+    }
+
+    #[test]
+    fn playing_short_handed_raises_kick_preference() {
+        let budget = PhaseBudget::default();
+        let state = neutral_state();
+        let carded = GameState {
+            yellow_cards: 1,
+            ..state.clone()
+        };
+        assert!(
+            (budget.kick_preference(&carded)
+                - budget.kick_preference(&state)
+                - SHORT_HANDED_KICK_BIAS)
+                .abs()
+                < 1e-6
+        );
+
+        // 相手も1人少なければ数的不利ではない
+        let both_carded = GameState {
+            opposition_yellow_cards: 1,
+            ..carded
+        };
+        assert_eq!(
+            budget.kick_preference(&both_carded),
+            budget.kick_preference(&state)
+        );
+    }
+
+    #[test]
+    fn soft_limit_escalates_kick_preference() {
+        let engine = DecisionEngine::default();
+        let budget = engine.phase_budget;
+        let at_soft = budget.kick_preference(&with_phases(neutral_state(), budget.soft_limit));
+        let past_soft =
+            budget.kick_preference(&with_phases(neutral_state(), budget.soft_limit + 2));
+        assert!(past_soft > at_soft);
+
+        assert_eq!(
+            engine.decide(&with_phases(neutral_state(), budget.soft_limit)),
+            TacticalDecision::Crash
+        );
+        assert!(matches!(
+            engine.decide(&with_phases(neutral_state(), budget.soft_limit + 2)),
+            TacticalDecision::Kick { .. }
+        ));
+    }
+
+    #[test]
+    fn hard_limit_forces_kick_even_with_a_red_zone_gap() {
+        let engine = DecisionEngine::default();
+        let mut state = with_phases(neutral_state(), engine.phase_budget.hard_limit + 1);
+        state.position = FieldPosition::Opposition22;
+        state.defense.gap_on_left = true;
+        state.teammates.support_count = 10;
+
+        assert!(matches!(
+            engine.decide(&state),
+            TacticalDecision::Kick { .. }
+        ));
+        state.consecutive_phases = 2;
+        assert!(matches!(
+            engine.decide(&state),
+            TacticalDecision::PassSpread { .. }
+        ));
+    }
+
+    /// シードから天候の列を生成
+    fn weather_sequence(seed: u64, len: usize) -> Vec<Weather> {
+        let mut rng = SimRng::new(seed);
+        let mut weather = Weather::Cloudy;
+        (0..len)
+            .map(|_| {
+                weather = weather.next(&mut rng);
+                weather
+            })
+            .collect()
+    }
+
+    #[test]
+    fn seeded_weather_sequence_is_reproducible() {
+        assert_eq!(weather_sequence(42, 200), weather_sequence(42, 200));
+    }
+
+    #[test]
+    fn weather_only_moves_between_adjacent_conditions() {
+        for seed in 1..50 {
+            let sequence = weather_sequence(seed, 100);
+            for pair in sequence.windows(2) {
+                let (from, to) = (pair[0], pair[1]);
+                assert!(
+                    from.transition_probabilities()
+                        .iter()
+                        .any(|&(next, _)| next == to),
+                    "{:?} -> {:?} は隣接していない",
+                    from,
+                    to
+                );
+            }
+        }
+        for weather in [
+            Weather::Sunny,
+            Weather::Cloudy,
+            Weather::Rainy,
+            Weather::Windy,
+            Weather::StormyRain,
+        ] {
+            let total: f32 = weather
+                .transition_probabilities()
+                .iter()
+                .map(|&(_, p)| p)
+                .sum();
+            assert!((total - 1.0).abs() < 1e-6);
+        }
+    }
+
+    /// シード1から`count`までの`GameState::random`で生成した状態
+    fn random_states(count: u64) -> Vec<GameState> {
+        (1..=count)
+            .map(|seed| GameState::random(&mut SimRng::new(seed)))
+            .collect()
+    }
+
+    #[test]
+    fn batch_matches_elementwise_decide() {
+        let engine = DecisionEngine::default();
+        let mut states = vec![close_game_late(), rainy_red_zone()];
+        states.extend(random_states(200));
+
+        let expected: Vec<_> = states.iter().map(|state| engine.decide(state)).collect();
+        assert_eq!(engine.batch(&states), expected);
+        assert!(engine.batch(&[]).is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn rayon_and_sequential_batches_agree() {
+        let engine = DecisionEngine::default();
+        let states = random_states(501);
+        assert_eq!(
+            engine.batch_parallel(&states),
+            engine.batch_sequential(&states)
+        );
+    }
+
+    /// 敵陣ゴール前の自チームボールのラインアウト
+    fn lineout_state() -> GameState {
+        let mut state = neutral_state();
+        state.position = FieldPosition::Opposition22;
+        state.lineout_awarded = true;
+        state.teammates.support_count = 6;
+        state
+    }
+
+    #[test]
+    fn lineout_near_line_with_fresh_forwards_sets_up_maul() {
+        assert_eq!(
+            DecisionEngine::default().decide(&lineout_state()),
+            TacticalDecision::Lineout {
+                throw_to: LineoutTarget::Maul
+            }
+        );
+    }
+
+    #[test]
+    fn lineout_with_tired_forwards_and_numbers_goes_to_the_back() {
+        let mut state = lineout_state();
+        state.fatigue.forwards = 0.85;
+        state.opposition_yellow_cards = 1;
+        assert_eq!(
+            DecisionEngine::default().decide(&state),
+            TacticalDecision::Lineout {
+                throw_to: LineoutTarget::Back
+            }
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn flat_scenario_deserializes_into_nested_state() {
+        let json = r#"{
+            "rules": "Sevens",
+            "elapsed_time_secs": 600,
+            "own_score": 12,
+            "opp_score": 7,
+            "position": "Opposition22",
+            "weather": "Rainy",
+            "wind": { "speed": 4.0, "direction": 90.0 },
+            "fw_fatigue": 0.45,
+            "bk_fatigue": 0.3,
+            "opposition_fatigue": { "forwards": 0.6, "backs": 0.7 },
+            "consecutive_phases": 5,
+            "penalties_conceded": 2,
+            "yellow_cards": 0,
+            "opposition_yellow_cards": 1,
+            "lineout_awarded": true,
+            "defense": {
+                "pressure_side": "Left",
+                "gap_on_left": false,
+                "gap_on_right": true,
+                "alignment": 0.7
+            },
+            "teammates": { "backs_ready": true, "forwards_ready": false, "support_count": 3 }
+        }"#;
+
+        let state: GameState = serde_json::from_str(json).expect("フラットなシナリオを読める");
+        assert_eq!(state.rules, GameRules::Sevens);
+        assert_eq!((state.score.own, state.score.opposition), (12, 7));
+        assert_eq!((state.fatigue.forwards, state.fatigue.backs), (0.45, 0.3));
+        assert_eq!(state.opposition_fatigue.backs, 0.7);
+        assert_eq!(state.wind.gust, 0.0, "省略した最大風速は突風なし");
+        assert_eq!(state.position, FieldPosition::Opposition22);
+        assert_eq!(state.opposition_yellow_cards, 1);
+        assert!(state.lineout_awarded);
+        assert!(state.defense.gap_on_right);
+        assert_eq!(state.defense.pressure_side, Some(Direction::Left));
+        assert_eq!(state.teammates.support_count, 3);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn legacy_pressure_flag_loads_as_center_pressure() {
+        let defense = |fields: &str| -> DefenseLine {
+            serde_json::from_str(&format!(
+                r#"{{ {fields}, "gap_on_left": false, "gap_on_right": true, "alignment": 0.7 }}"#
+            ))
+            .expect("ディフェンスラインを読める")
+        };
+        assert_eq!(
+            defense(r#""pressure": true"#).pressure_side,
+            Some(Direction::Center)
+        );
+        assert_eq!(defense(r#""pressure": false"#).pressure_side, None);
+        assert_eq!(
+            defense(r#""pressure": true, "pressure_side": "Left""#).pressure_side,
+            Some(Direction::Left)
+        );
+
+        // 旧形式のシナリオでも自陣22mの安全ルールが働く
+        let mut state = close_game_late();
+        state.position = FieldPosition::Own22;
+        let mut value = serde_json::to_value(&state).expect("シリアライズできる");
+        let line = value["defense"].as_object_mut().expect("オブジェクト");
+        line.remove("pressure_side");
+        line.insert("pressure".into(), true.into());
+        let legacy: GameState = serde_json::from_value(value).expect("旧形式を読める");
+        assert_eq!(legacy, state);
+        assert_eq!(
+            DecisionEngine::default().decide(&legacy),
+            DecisionEngine::default().decide(&state)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn scenario_round_trip_preserves_values() {
+        for state in [close_game_late(), rainy_red_zone(), neutral_state()] {
+            let value = serde_json::to_value(&state).expect("シリアライズできる");
+            assert_eq!(value["own_score"], state.score.own);
+            assert_eq!(value["fw_fatigue"], state.fatigue.forwards);
+            assert!(value.get("score").is_none());
+            assert!(value.get("fatigue").is_none());
+
+            let restored: GameState = serde_json::from_value(value.clone()).expect("読み戻せる");
+            assert_eq!(restored.snapshot_hash(), state.snapshot_hash());
+            assert_eq!(
+                serde_json::to_value(&restored).expect("再シリアライズできる"),
+                value
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn hand_written_wire_tags_deserialize_into_variants() {
+        let decisions: Vec<TacticalDecision> = serde_json::from_str(
+            r#"[
+                { "PassSpread": { "direction": "Right", "rushed": true } },
+                { "PassSpread": { "direction": "Left" } },
+                "Crash",
+                { "Kick": { "kick_type": "Grubber" } },
+                "QuickTap",
+                "Maul",
+                "Scrum",
+                { "Lineout": { "throw_to": "Maul" } },
+                "DropGoal"
+            ]"#,
+        )
+        .expect("文書化したタグで判断を読める");
+        assert_eq!(
+            decisions,
+            [
+                TacticalDecision::PassSpread {
+                    direction: Direction::Right,
+                    rushed: true,
+                },
+                TacticalDecision::PassSpread {
+                    direction: Direction::Left,
+                    rushed: false,
+                },
+                TacticalDecision::Crash,
+                TacticalDecision::Kick {
+                    kick_type: KickType::Grubber,
+                },
+                TacticalDecision::QuickTap,
+                TacticalDecision::Maul,
+                TacticalDecision::Scrum,
+                TacticalDecision::Lineout {
+                    throw_to: LineoutTarget::Maul,
+                },
+                TacticalDecision::DropGoal,
+            ]
+        );
+
+        let kicks: Vec<KickType> =
+            serde_json::from_str(r#"["HighPunt", "Touch", "Grubber", "Crossfield", "Bomb"]"#)
+                .expect("キックの種類を読める");
+        assert_eq!(kicks, KickType::ALL);
+        let positions: Vec<FieldPosition> = serde_json::from_str(
+            r#"["Own22", "OwnHalf", "Midfield", "OppositionHalf", "Opposition22"]"#,
+        )
+        .expect("フィールドポジションを読める");
+        assert_eq!(positions, FieldPosition::ALL);
+        let weather: Vec<Weather> =
+            serde_json::from_str(r#"["Sunny", "Cloudy", "Rainy", "Windy", "StormyRain"]"#)
+                .expect("天候を読める");
+        assert_eq!(
+            weather,
+            [
+                Weather::Sunny,
+                Weather::Cloudy,
+                Weather::Rainy,
+                Weather::Windy,
+                Weather::StormyRain,
+            ]
+        );
+        let rules: Vec<GameRules> =
+            serde_json::from_str(r#"["Fifteens", "Sevens", "Tens"]"#).expect("ルールを読める");
+        assert_eq!(
+            rules,
+            [GameRules::Fifteens, GameRules::Sevens, GameRules::Tens]
+        );
+        fn read<T: serde::de::DeserializeOwned>(json: &str) -> Vec<T> {
+            serde_json::from_str(json).expect("列挙型を読める")
+        }
+        let roles: Vec<PlayerRole> = read(r#"["Forward", "Back"]"#);
+        let card_laws: Vec<CardLaw> = read(r#"["PermanentRed", "ReplaceableRed20"]"#);
+        let teams: Vec<Team> = read(r#"["Own", "Opposition"]"#);
+        let targets: Vec<LineoutTarget> = read(r#"["Front", "Middle", "Back", "Maul"]"#);
+        let directions: Vec<Direction> = read(r#"["Left", "Right", "Center"]"#);
+        assert_eq!(roles, [PlayerRole::Forward, PlayerRole::Back]);
+        assert_eq!(
+            card_laws,
+            [CardLaw::PermanentRed, CardLaw::ReplaceableRed20]
+        );
+        assert_eq!(teams, [Team::Own, Team::Opposition]);
+        assert_eq!(
+            targets,
+            [
+                LineoutTarget::Front,
+                LineoutTarget::Middle,
+                LineoutTarget::Back,
+                LineoutTarget::Maul,
+            ]
+        );
+        assert_eq!(
+            directions,
+            [Direction::Left, Direction::Right, Direction::Center]
+        );
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn decide_json_matches_decide() {
+        for state in [close_game_late(), rainy_red_zone(), neutral_state()] {
+            let input = serde_json::to_string(&state).expect("シリアライズできる");
+            let output = decide_json(&input).expect("判断できる");
+            let decision: TacticalDecision =
+                serde_json::from_str(&output).expect("判断を読み戻せる");
+            assert_eq!(decision, DecisionEngine::new().decide(&state));
+        }
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn decide_json_reports_unreadable_input() {
+        let error = decide_json("{ \"own_score\": ").expect_err("壊れたJSONは読めない");
+        assert!(!error.is_empty());
+    }
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn wasm_decide_returns_the_engine_decision() {
+        let state = close_game_late();
+        let input = serde_json::to_string(&state).expect("シリアライズできる");
+        let output = wasm_decide(&input).unwrap_or_else(|_| panic!("判断できる"));
+        let decision: TacticalDecision = serde_json::from_str(&output).expect("判断を読み戻せる");
+        assert_eq!(decision, DecisionEngine::new().decide(&state));
+    }
+
+    #[test]
+    fn drop_goal_expected_points_is_three_times_success_probability() {
+        let mut state = neutral_state();
+        state.position = FieldPosition::Opposition22;
+        let p = TacticalDecision::DropGoal.success_probability(&state);
+        let ev = TacticalDecision::DropGoal.expected_points(&state);
+        assert!(p > 0.0 && p < 1.0);
+        assert!((ev - 3.0 * p).abs() < 1e-5);
+
+        state.position = FieldPosition::Own22;
+        assert!(TacticalDecision::DropGoal.expected_points(&state) < ev);
+    }
+
+    #[test]
+    fn decide_all_positions_varies_only_the_position() {
+        let mut base = neutral_state();
+        base.defense.pressure_side = Some(Direction::Center);
+        base.defense.gap_on_left = true;
+        let engine = DecisionEngine::default();
+        let map = engine.decide_all_positions(&base);
+
+        assert_eq!(map.map(|(position, _)| position), FieldPosition::ALL);
+        for (position, decision) in map {
+            let state = GameState {
+                position,
+                ..base.clone()
+            };
+            assert_eq!(decision, engine.decide(&state));
+        }
+        assert_ne!(map[0].1, map[4].1);
+    }
+
+    #[test]
+    fn close_pressured_own_half_rates_harder_than_relaxed_red_zone() {
+        let relaxed = GameState {
+            position: FieldPosition::Opposition22,
+            score: Score {
+                own: 24,
+                opposition: 3,
+            },
+            ..neutral_state()
+        };
+        let scenario_1 = close_game_late();
+        assert!(scenario_1.difficulty_rating() > relaxed.difficulty_rating());
+        for state in [&scenario_1, &relaxed] {
+            assert!((0.0..=1.0).contains(&state.difficulty_rating()));
+        }
+    }
+
+    #[test]
+    fn open_play_excludes_set_pieces_from_applicable_decisions() {
+        let mut state = neutral_state();
+        state.defense.gap_on_left = true;
+        let engine = DecisionEngine::default();
+        let applicable: Vec<TacticalDecision> = engine.applicable_decisions(&state).collect();
+
+        assert!(!applicable.contains(&TacticalDecision::Scrum));
+        assert!(
+            !applicable
+                .iter()
+                .any(|decision| matches!(decision, TacticalDecision::Lineout { .. }))
+        );
+        assert!(applicable.contains(&TacticalDecision::Crash));
+        assert!(applicable.contains(&TacticalDecision::PassSpread {
+            direction: Direction::Left,
+            rushed: false,
+        }));
+        assert!(
+            applicable
+                .iter()
+                .any(|decision| matches!(decision, TacticalDecision::Kick { .. }))
+        );
+
+        state.lineout_awarded = true;
+        state.teammates.forwards_ready = false;
+        let applicable: Vec<TacticalDecision> = engine.applicable_decisions(&state).collect();
+        assert!(applicable.contains(&TacticalDecision::Lineout {
+            throw_to: LineoutTarget::Front,
+        }));
+        assert!(!applicable.contains(&TacticalDecision::Lineout {
+            throw_to: LineoutTarget::Maul,
+        }));
+        assert!(!applicable.contains(&TacticalDecision::Maul));
+    }
+
+    #[test]
+    fn drop_goal_is_a_candidate_only_within_range() {
+        for position in FieldPosition::ALL {
+            let state = GameState {
+                position,
+                ..neutral_state()
+            };
+            assert_eq!(
+                candidate_decisions(&state).contains(&TacticalDecision::DropGoal),
+                position.drop_goal_range_factor() > 0.0,
+                "{position:?}"
+            );
+        }
+
+        // 圏外ではドロップゴールが候補にならないため、それだけを残す禁止設定は拒否する
+        let all_but_drop_goal = [
+            DecisionKind::Crash,
+            DecisionKind::QuickTap,
+            DecisionKind::Maul,
+            DecisionKind::Kick(KickType::Bomb),
+        ];
+        assert_eq!(
+            DecisionEngine::new().forbid(all_but_drop_goal).err(),
+            Some(EngineError::AllDecisionsForbidden)
+        );
+    }
+
+    #[test]
+    fn applicable_decisions_share_the_candidate_order() {
+        // キックの種類とラインアウト以外は、候補と同じ判断が同じ順に並ぶ
+        let engine = DecisionEngine::default();
+        for state in random_states(200) {
+            let expected: Vec<TacticalDecision> = candidate_decisions(&state)
+                .into_iter()
+                .filter(|decision| {
+                    !matches!(decision, TacticalDecision::Kick { .. })
+                        && engine.support_adequate(&state, decision)
+                        && !engine.is_forbidden(&state, decision)
+                })
+                .collect();
+            let shared: Vec<TacticalDecision> = engine
+                .applicable_decisions(&state)
+                .filter(|decision| {
+                    !matches!(
+                        decision,
+                        TacticalDecision::Kick { .. } | TacticalDecision::Lineout { .. }
+                    )
+                })
+                .collect();
+            assert_eq!(shared, expected);
+        }
+    }
+
+    #[test]
+    fn long_phase_sequences_strip_support_from_a_wide_pass() {
+        let mut state = neutral_state();
+        state.defense.gap_on_left = true;
+        state.teammates.support_count = 3;
+        let pass = TacticalDecision::PassSpread {
+            direction: Direction::Left,
+            rushed: false,
+        };
+        let decay = SupportDecay::default();
+        let engine = DecisionEngine::default();
+
+        let early = with_phases(state.clone(), 2);
+        assert_eq!(decay.effective_support(&early.teammates, 2), 3);
+        assert!(
+            decay
+                .effective_teammates(&early)
+                .support_adequate_for(&pass)
+        );
+        assert!(engine.applicable_decisions(&early).any(|d| d == pass));
+
+        let late = with_phases(state, 15);
+        assert!(decay.effective_support(&late.teammates, 15) < 2);
+        assert!(!decay.effective_teammates(&late).support_adequate_for(&pass));
+        assert!(!engine.applicable_decisions(&late).any(|d| d == pass));
+    }
+
+    #[test]
+    fn audit_guards_report_a_support_shortfall() {
+        let mut state = neutral_state();
+        state.defense.gap_on_left = true;
+        state.teammates.backs_ready = true;
+        state.teammates.support_count = 1;
+        let engine = DecisionEngine::default();
+
+        // 分岐はギャップへのパス展開を選ぶが、サポートが1人では実行できない
+        let first = engine
+            .audit(&state)
+            .into_iter()
+            .find(|eval| eval.matched)
+            .expect("Otherwiseは常に当てはまる");
+        assert_eq!(first.condition, BranchCondition::GapWithBacksReady);
+        let shortfall = GuardCondition::SupportShortfall {
+            support_decay: engine.support_decay,
+        };
+        let applied: Vec<GuardEval> = engine
+            .audit_guards(&state)
+            .into_iter()
+            .filter(|eval| eval.applied)
+            .collect();
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].condition, shortfall);
+        assert!(applied[0].observed.contains("サポート=1人"));
+
+        let decision = engine.decide(&state);
+        assert!(!matches!(decision, TacticalDecision::PassSpread { .. }));
+        let spec = engine
+            .guards()
+            .into_iter()
+            .find(|spec| spec.condition == shortfall)
+            .expect("サポート不足のガードがある");
+        assert!(spec.yields.contains(&decision.kind()));
+        assert!(state.teammates.support_adequate_for(&decision));
+
+        // サポートが足りればガードは何も置き換えない
+        state.teammates.support_count = 3;
+        assert!(engine.audit_guards(&state).iter().all(|eval| !eval.applied));
+    }
+
+    #[test]
+    fn counterattack_guard_holds_through_the_support_fallback() {
+        let engine = DecisionEngine::default();
+        let territorial = |decision: &TacticalDecision| {
+            matches!(
+                decision,
+                TacticalDecision::Kick { kick_type } if *kick_type != KickType::Touch
+            )
+        };
+        let mut fallbacks = 0;
+        for mut state in random_states(3000) {
+            state.position = FieldPosition::OwnHalf;
+            state.opposition_counterattack_threat = 0.9;
+            let (branch, _) = engine.branch_decision(&state);
+            let guards = engine.audit_guards(&state);
+            let applied = |condition: fn(&GuardCondition) -> bool| {
+                guards
+                    .iter()
+                    .any(|eval| condition(&eval.condition) && eval.applied)
+            };
+
+            // 分岐が選んだキックはガードが置き換え、置き換えの候補からもキックは戻らない
+            assert_eq!(
+                applied(|c| matches!(c, GuardCondition::Counterattack { .. })),
+                territorial(&branch)
+            );
+            if applied(|c| matches!(c, GuardCondition::SupportShortfall { .. })) {
+                fallbacks += 1;
+            }
+            let decision = engine.decide(&state);
+            assert!(!territorial(&decision), "{decision:?} {state:?}");
+        }
+        assert!(fallbacks > 0);
+    }
+
+    #[test]
+    fn trace_to_dot_starts_at_the_hard_phase_limit() {
+        let engine = DecisionEngine {
+            panic_mode: None,
+            ..DecisionEngine::default()
+        };
+        let dot = engine.trace_to_dot();
+
+        assert!(dot.starts_with("digraph decision_tree {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot.matches('"').count() % 2, 0);
+        assert!(dot.contains("b0 [label=\"PhasesOverHardLimit { limit: 15 }\"];"));
+        assert!(dot.contains("b0 -> b1 [label=\"no\"];"));
+        assert!(dot.contains("b0_0 [label=\"Kick(HighPunt)\", shape=ellipse];"));
+        assert!(dot.contains("b1 [label=\"Own22UnderPressure { headwind_threshold: 8.0 }\"];"));
+        assert!(dot.contains("b1_0 [label=\"Kick(Touch)\", shape=ellipse];"));
+
+        // 宣言したノードだけを辺でつなぐ
+        let declared: std::collections::HashSet<&str> = dot
+            .lines()
+            .filter(|line| line.contains("[label=") && !line.contains("->"))
+            .filter_map(|line| line.split_whitespace().next())
+            .collect();
+        for edge in dot.lines().filter(|line| line.contains("->")) {
+            let mut ends = edge.split_whitespace();
+            let from = ends.next().expect("辺の始点");
+            let to = ends.nth(1).expect("辺の終点");
+            assert!(declared.contains(from) && declared.contains(to), "{edge}");
+        }
+        assert_eq!(
+            declared.iter().filter(|node| !node.contains('_')).count(),
+            engine.rationale_tree().len()
+        );
+    }
+
+    #[test]
+    fn narrow_late_lead_keeps_the_ball_instead_of_kicking() {
+        let engine = DecisionEngine::default();
+        let mut state = with_phases(neutral_state(), engine.phase_budget.soft_limit + 2);
+        state.position = FieldPosition::OwnHalf;
+        state.elapsed_time_secs = state.rules.match_duration_secs() - 90;
+
+        // 同点なら長い連続フェーズをキックでリセットする
+        assert!(matches!(
+            engine.decide(&state),
+            TacticalDecision::Kick { .. }
+        ));
+
+        state.score = Score {
+            own: 13,
+            opposition: 10,
+        };
+        assert_eq!(engine.decide(&state), TacticalDecision::Crash);
+    }
+
+    #[test]
+    fn drop_goal_depends_on_kicker_reliability() {
+        let mut state = neutral_state();
+        state.position = FieldPosition::Opposition22;
+        state.score = Score {
+            own: 10,
+            opposition: 12,
+        };
+        state.elapsed_time_secs = state.rules.match_duration_secs() - 90;
+
+        // 既定（信頼度なし）は敵陣22m内なら常にドロップゴール
+        assert_eq!(
+            DecisionEngine::default().decide(&state),
+            TacticalDecision::DropGoal
+        );
+
+        let reliable = DecisionEngine {
+            drop_goal_reliability: Some(1.3),
+            ..DecisionEngine::default()
+        };
+        assert_eq!(reliable.decide(&state), TacticalDecision::DropGoal);
+
+        // 平均的なキッカーでは、クラッシュでトライを狙う方が得点できる確率が高い
+        let average = DecisionEngine {
+            drop_goal_reliability: Some(1.0),
+            ..DecisionEngine::default()
+        };
+        assert!(drop_goal_probability(&state, 1.0) < attack_scoring_probability(&state));
+        let decision = average.decide(&state);
+        assert_ne!(decision, TacticalDecision::DropGoal);
+        assert!(!matches!(decision, TacticalDecision::Kick { .. }));
+    }
+
+    #[test]
+    fn drop_goal_from_opposition_half_needs_a_reliable_kicker() {
+        let mut state = neutral_state();
+        state.position = FieldPosition::OppositionHalf;
+        state.score = Score {
+            own: 10,
+            opposition: 12,
+        };
+        state.elapsed_time_secs = state.rules.match_duration_secs() - 90;
+
+        // 既定では敵陣からドロップゴールは狙わない（パニックモードならクイックタップ）
+        let default = DecisionEngine::default();
+        assert_ne!(default.decide(&state), TacticalDecision::DropGoal);
+        let panicking = DecisionEngine {
+            panic_mode: Some(PanicMode::default()),
+            ..DecisionEngine::default()
+        };
+        assert_eq!(panicking.decide(&state), TacticalDecision::QuickTap);
+
+        // 成功確率は敵陣22m内の半分なので、平均的なキッカーでは攻撃を続ける
+        let average = DecisionEngine {
+            drop_goal_reliability: Some(1.0),
+            ..DecisionEngine::default()
+        };
+        assert_ne!(average.decide(&state), TacticalDecision::DropGoal);
+        let specialist = DecisionEngine {
+            drop_goal_reliability: Some(2.0),
+            ..DecisionEngine::default()
+        };
+        assert_eq!(specialist.decide(&state), TacticalDecision::DropGoal);
+    }
+
+    #[test]
+    fn hia_replacement_restores_numbers_on_return() {
+        let mut state = neutral_state();
+        state.elapsed_time_secs = 20 * 60;
+        state.temporary_off.push(TempOff {
+            position: PlayerRole::Forward,
+            returns_at_secs: 32 * 60,
+        });
+        assert_eq!(state.effective_players(), 14);
+        assert_eq!(state.numbers_advantage(), -1);
+
+        state.elapsed_time_secs = 31 * 60 + 59;
+        assert_eq!(state.effective_players(), 14);
+
+        state.elapsed_time_secs = 32 * 60;
+        assert_eq!(state.effective_players(), 15);
+        assert_eq!(state.numbers_advantage(), 0);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test(start_paused = true)]
+    async fn english_locale_reports_fatigue_with_unit_labels() {
+        let mut buffer: Vec<u8> = Vec::new();
+        analyze_game_state_to(
+            &close_game_late(),
+            &mut buffer,
+            Locale::English,
+            &Timing::instant(),
+        )
+        .await;
+
+        let output = String::from_utf8(buffer).expect("UTF-8で書き出される");
+        assert!(output.contains("Forwards fatigue: 65%"));
+        assert!(output.contains("Elapsed: 75m00s"));
+        assert!(output.contains("Score: 21–24 (-3)"));
+    }
+
+    #[test]
+    fn on_phase_callback_sees_every_recorded_decision() {
+        let mut seen = Vec::new();
+        let mut elapsed = Vec::new();
+        let config = SimulationConfig::default();
+        let phase_secs = config.defensive_phase_secs;
+        let summary = simulate_match(close_game_late(), &config, |state, decision| {
+            seen.push(*decision);
+            elapsed.push(state.elapsed_time_secs);
+        });
+
+        assert!(!seen.is_empty());
+        assert_eq!(seen, summary.decisions);
+        // 相手ボールの守備のフェーズでは呼ばれないため、間隔は判断のフェーズ時間に
+        // 守備のフェーズ時間の倍数を足したもの
+        assert!(elapsed[0] >= close_game_late().elapsed_time_secs);
+        for (i, pair) in elapsed.windows(2).enumerate() {
+            let gap = pair[1] - pair[0];
+            let own_phase = seen[i].typical_phase_secs();
+            assert!(gap >= own_phase && (gap - own_phase) % phase_secs == 0);
+        }
+        let recorded: Vec<u32> = summary
+            .contexts
+            .iter()
+            .map(|context| context.at_secs)
+            .collect();
+        assert_eq!(elapsed, recorded);
+    }
+
+    #[test]
+    fn random_is_reproducible_for_equal_seeds() {
+        for seed in 1..=100 {
+            let first = GameState::random(&mut SimRng::new(seed));
+            let second = GameState::random(&mut SimRng::new(seed));
+            assert_eq!(
+                format!("{:?}", first),
+                format!("{:?}", second),
+                "seed {}",
+                seed
+            );
+            assert!(first.elapsed_time_secs <= first.rules.match_duration_secs());
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    proptest::proptest! {
+        #[test]
+        fn generated_states_are_in_range(state in strategies::game_state()) {
+            proptest::prop_assert!(state.elapsed_time_secs <= state.rules.match_duration_secs());
+            for value in [
+                state.fatigue.forwards,
+                state.fatigue.backs,
+                state.opposition_fatigue.forwards,
+                state.opposition_fatigue.backs,
+                state.defense.alignment,
+            ] {
+                proptest::prop_assert!((0.0..=1.0).contains(&value));
+            }
+            proptest::prop_assert!((0.0..=20.0).contains(&state.wind.speed));
+            proptest::prop_assert!((0.0..360.0).contains(&state.wind.direction));
+            proptest::prop_assert!(state.wind.gust >= state.wind.speed);
+        }
+
+        #[test]
+        fn sampled_wind_speed_never_leaves_speed_to_gust(wind in strategies::wind(), seed in proptest::prelude::any::<u64>()) {
+            let speed = wind.sample_instantaneous(&mut SimRng::new(seed));
+            proptest::prop_assert!((wind.speed..=wind.gust).contains(&speed));
+        }
+
+        #[test]
+        fn decisions_are_deterministic(state in strategies::game_state()) {
+            let engine = DecisionEngine::default();
+            proptest::prop_assert_eq!(engine.decide(&state), engine.decide(&state.clone()));
+        }
+
+        #[test]
+        fn never_passes_toward_a_side_without_a_gap(state in strategies::game_state()) {
+            if let TacticalDecision::PassSpread { direction, .. } = DecisionEngine::default().decide(&state) {
+                let has_gap = match direction {
+                    Direction::Left => state.defense.gap_on_left,
+                    Direction::Right => state.defense.gap_on_right,
+                    Direction::Center => false,
+                };
+                proptest::prop_assert!(has_gap, "ギャップのない{:?}へ展開", direction);
+            }
+        }
+
+        #[test]
+        fn never_passes_from_own_22_under_pressure(
+            mut state in strategies::game_state()
+        ) {
+            state.position = FieldPosition::Own22;
+            state.defense.pressure_side = Some(Direction::Center);
+            let decision = DecisionEngine::default().decide(&state);
+            proptest::prop_assert!(
+                !matches!(decision, TacticalDecision::PassSpread { .. }),
+                "自陣22mでプレッシャー下のパス展開: {:?}",
+                decision
+            );
+        }
+
+        #[test]
+        fn decisions_on_a_mirrored_state_are_mirrored(state in strategies::game_state()) {
+            // 両側にギャップがある場合はどちらの状態でも左を優先するため対象外
+            proptest::prop_assume!(!(state.defense.gap_on_left && state.defense.gap_on_right));
+            let engine = DecisionEngine::default();
+            proptest::prop_assert_eq!(
+                engine.decide(&state.mirror()),
+                engine.decide(&state).mirror()
+            );
+        }
+
+        #[test]
+        fn first_matching_audit_branch_yields_the_decision(state in strategies::game_state()) {
+            let engine = DecisionEngine::default();
+            let first = engine
+                .audit(&state)
+                .into_iter()
+                .find(|eval| eval.matched)
+                .expect("Otherwiseは常に当てはまる");
+            let spec = engine
+                .rationale_tree()
+                .into_iter()
+                .find(|spec| spec.condition == first.condition)
+                .expect("監査はrationale_treeと同じ分岐を評価する");
+            // ガードが判断を置き換えた場合は、最後に置き換えたガードの仕様に従う
+            let decision = engine.decide(&state);
+            let guards = engine.guards();
+            match engine.audit_guards(&state).into_iter().rfind(|eval| eval.applied) {
+                Some(applied) => {
+                    let guard = guards
+                        .iter()
+                        .find(|spec| spec.condition == applied.condition)
+                        .expect("監査はguardsと同じガードを評価する");
+                    proptest::prop_assert!(
+                        guard.yields.contains(&decision.kind()),
+                        "{:?} -> {:?}",
+                        applied,
+                        decision
+                    );
+                    if let GuardCondition::SupportShortfall { support_decay } = guard.condition {
+                        proptest::prop_assert!(
+                            support_decay.effective_teammates(&state).support_adequate_for(&decision),
+                            "{:?} -> {:?}",
+                            applied,
+                            decision
+                        );
+                    }
+                }
+                None => proptest::prop_assert!(
+                    spec.yields.contains(&decision.kind()),
+                    "{:?} -> {:?}",
+                    first,
+                    decision
+                ),
+            }
+
+            // カウンターアタックの脅威がある自陣では、どの経路でもフィールド内へ蹴らない
+            let threatened = matches!(state.position, FieldPosition::Own22 | FieldPosition::OwnHalf)
+                && state.opposition_counterattack_threat > engine.counterattack_threshold;
+            proptest::prop_assert!(
+                !threatened
+                    || !matches!(
+                        decision,
+                        TacticalDecision::Kick { kick_type } if kick_type != KickType::Touch
+                    ),
+                "{:?}",
+                decision
+            );
+        }
+
+        #[test]
+        fn forbidden_kinds_are_never_chosen(
+            state in strategies::game_state(),
+            mask in 0u32..(1 << 12)
+        ) {
+            let kinds: Vec<DecisionKind> = all_decision_kinds()
+                .into_iter()
+                .enumerate()
+                .filter(|(index, _)| mask & (1 << index) != 0)
+                .map(|(_, kind)| kind)
+                .collect();
+            if let Ok(engine) = DecisionEngine::new().forbid(kinds.clone()) {
+                let decision = engine.decide(&state);
+                proptest::prop_assert!(!kinds.contains(&decision.kind()), "{:?}", decision);
+            }
+        }
+    }
+
+    /// すべての判断の種類（キックは種類ごと）
+    fn all_decision_kinds() -> Vec<DecisionKind> {
+        KickType::ALL
+            .into_iter()
+            .map(DecisionKind::Kick)
+            .chain([
+                DecisionKind::PassSpread,
+                DecisionKind::Crash,
+                DecisionKind::QuickTap,
+                DecisionKind::Maul,
+                DecisionKind::Scrum,
+                DecisionKind::Lineout,
+                DecisionKind::DropGoal,
+            ])
+            .collect()
+    }
+
+    /// 自陣22mでプレッシャーを受け、指定した風速の向かい風を受ける状況
+    fn own22_headwind_state(speed: f32) -> GameState {
+        let mut state = neutral_state();
+        state.position = FieldPosition::Own22;
+        state.defense.pressure_side = Some(Direction::Center);
+        state.wind = Wind {
+            speed,
+            direction: 0.0,
+            gust: speed,
+        };
+        state
+    }
+
+    #[test]
+    fn strong_headwind_in_own22_switches_touch_to_retention() {
+        let engine = DecisionEngine::default();
+        assert_eq!(
+            engine.decide(&own22_headwind_state(4.0)),
+            TacticalDecision::Kick {
+                kick_type: KickType::Touch
+            }
+        );
+        let strong = own22_headwind_state(12.0);
+        assert!(strong.headwind() > engine.headwind_threshold);
+        assert!(matches!(
+            engine.decide(&strong),
+            TacticalDecision::Maul | TacticalDecision::Crash
+        ));
+
+        let tolerant = DecisionEngine {
+            headwind_threshold: 15.0,
+            ..DecisionEngine::default()
+        };
+        assert_eq!(
+            tolerant.decide(&strong),
+            TacticalDecision::Kick {
+                kick_type: KickType::Touch
+            }
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test(start_paused = true)]
+    async fn async_scoring_reports_monotonic_progress_ending_at_one() {
+        let state = rainy_red_zone();
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(32);
+        let scored = score_decisions_async(&state, sender, &Timing::realistic()).await;
+
+        let mut progress = Vec::new();
+        while let Some(value) = receiver.recv().await {
+            progress.push(value);
+        }
+        assert!(progress.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(progress.first(), Some(&0.0));
+        assert_eq!(progress.last(), Some(&1.0));
+        assert_eq!(scored, score_decisions(&state));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test(start_paused = true)]
+    async fn async_scoring_continues_after_receiver_is_dropped() {
+        let state = close_game_late();
+        let (sender, receiver) = tokio::sync::mpsc::channel(1);
+        drop(receiver);
+        let scored = score_decisions_async(&state, sender, &Timing::realistic()).await;
+        assert_eq!(scored, score_decisions(&state));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test(start_paused = true)]
+    async fn drop_oldest_queue_discards_the_stalest_state() {
+        let at = |minute: u32| GameState {
+            elapsed_time_secs: minute * 60,
+            ..close_game_late()
+        };
+        let engine = DecisionEngine::default();
+        let (queue, mut decisions) = DecisionQueue::new(
+            engine.clone(),
+            2,
+            OverflowPolicy::DropOldest,
+            Duration::from_millis(500),
+        );
+
+        assert_eq!(queue.push(at(70)).await.expect("受け付ける"), None);
+        assert_eq!(queue.push(at(71)).await.expect("受け付ける"), None);
+        let dropped = queue.push(at(72)).await.expect("受け付ける");
+        assert_eq!(dropped, Some(at(70)));
+        queue.close().await;
+
+        let mut decided = Vec::new();
+        while let Some((state, decision)) = decisions.recv().await {
+            assert_eq!(decision, engine.decide(&state));
+            decided.push(state.elapsed_time_secs);
+        }
+        assert_eq!(decided, vec![71 * 60, 72 * 60]);
+    }
+
+    #[test]
+    fn contextual_urgency_contrasts_red_zone_and_own22() {
+        let score = Score {
+            own: 21,
+            opposition: 24,
+        };
+        let remaining = 5 * 60;
+        let base = score.urgency(remaining);
+        let attacking = score.contextual_urgency(remaining, FieldPosition::Opposition22);
+        let pinned = score.contextual_urgency(remaining, FieldPosition::Own22);
+
+        assert!(attacking > base || attacking == 1.0);
+        assert!(pinned < base);
+        assert!(attacking > pinned);
+        assert_eq!(
+            score.contextual_urgency(remaining, FieldPosition::Midfield),
+            base
+        );
+
+        // 前半はフィールドポジションに関わらず`urgency`と同じ
+        let early = 60 * 60;
+        assert_eq!(
+            score.contextual_urgency(early, FieldPosition::Own22),
+            score.urgency(early)
+        );
+    }
+
+    #[test]
+    fn presets_resolve_by_name_and_unknown_names_error() {
+        let close = GameState::try_from("close_game_late").expect("組み込みプリセット");
+        assert_eq!((close.score.own, close.score.opposition), (21, 24));
+        let rainy = GameState::try_from("rainy_red_zone").expect("組み込みプリセット");
+        assert_eq!(rainy.position, FieldPosition::Opposition22);
+
+        let error = GameState::try_from("beach_rugby").expect_err("不明なプリセット");
+        assert_eq!(error.name, "beach_rugby");
+        assert!(error.to_string().contains("close_game_late"));
+    }
+
+    #[test]
+    fn preset_flag_resolves_from_arguments() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert!(preset_option(&args(&["--verbose"])).is_none());
+        let rainy = preset_option(&args(&["--preset", "rainy_red_zone", "--verbose"]))
+            .expect("--presetがある")
+            .expect("組み込みプリセット");
+        assert_eq!(format!("{:?}", rainy), format!("{:?}", rainy_red_zone()));
+        let missing = preset_option(&args(&["--preset"])).expect("--presetがある");
+        assert!(matches!(missing, Err(UnknownPreset { name }) if name.is_empty()));
+    }
+
+    #[test]
+    fn tracking_with_left_hole_detects_left_gap() {
+        let defense =
+            DefenseLine::from_tracking(&[4.0, 22.0, 30.0, 38.0, 46.0, 54.0, 62.0, 68.0], 70.0);
+        assert!(defense.gap_on_left);
+        assert!(!defense.gap_on_right);
+        assert!(!defense.pressure());
+    }
+
+    #[test]
+    fn evenly_spaced_tracking_is_aligned_without_gaps() {
+        let even = DefenseLine::from_tracking(&[5.0, 15.0, 25.0, 35.0, 45.0, 55.0, 65.0], 70.0);
+        assert!(!even.gap_on_left && !even.gap_on_right);
+        assert!((even.alignment - 1.0).abs() < 1e-5);
+
+        let uneven = DefenseLine::from_tracking(&[5.0, 8.0, 25.0, 28.0, 45.0, 55.0, 65.0], 70.0);
+        assert!(uneven.alignment < even.alignment);
+    }
+
+    #[test]
+    fn organized_chase_against_tired_back_three_prefers_bomb() {
+        let mut state = neutral_state();
+        state.teammates.support_count = 6;
+        state.opposition_fatigue.backs = 0.9;
+        assert!(
+            estimate_kick_value(KickType::Bomb, &state)
+                > estimate_kick_value(KickType::Touch, &state)
+        );
+        assert_eq!(choose_kick_type(&state), KickType::Bomb);
+
+        // チェイサーが整っていなければボムは選ばない
+        state.teammates.backs_ready = false;
+        assert_ne!(choose_kick_type(&state), KickType::Bomb);
+    }
+
+    #[test]
+    fn bomb_has_display_entry() {
+        let bomb = TacticalDecision::Kick {
+            kick_type: KickType::Bomb,
+        };
+        assert_eq!(bomb.to_string(), "Bombキック");
+    }
+
+    /// 40分のカスタムルール
+    fn forty_minute_rules() -> RuleSet {
+        RuleSet {
+            name: "beach".to_string(),
+            duration_secs: 40 * 60,
+            fatigue_rate: 0.025,
+            players: 5,
+            substitutions: 3,
+            scoring: ScoringValues::default(),
+        }
+    }
+
+    #[test]
+    fn custom_forty_minute_format_simulates_to_its_own_full_time() {
+        let mut registry = RuleSetRegistry::new();
+        let rules = registry
+            .register(forty_minute_rules())
+            .expect("予約されていない名前");
+        assert_eq!(registry.get("beach"), Some(rules.clone()));
+        assert_eq!(rules.match_duration_secs(), 40 * 60);
+        assert_eq!(Duration::from(rules.clone()), Duration::from_secs(40 * 60));
+
+        let initial = GameState {
+            rules: rules.clone(),
+            elapsed_time_secs: 30 * 60,
+            ..neutral_state()
+        };
+        let summary = simulate_match(initial, &SimulationConfig::default(), |_, _| {});
+        assert_eq!(summary.final_state.rules, rules);
+        assert!(summary.final_state.elapsed_time_secs >= 40 * 60);
+        assert!(summary.final_state.elapsed_time_secs < 80 * 60);
+    }
+
+    #[test]
+    fn rule_set_equality_uses_full_contents_and_registration_shares_one_allocation() {
+        let mut registry = RuleSetRegistry::new();
+        let first = registry
+            .register(forty_minute_rules())
+            .expect("予約されていない名前");
+        let longer = registry
+            .register(RuleSet {
+                duration_secs: 50 * 60,
+                ..forty_minute_rules()
+            })
+            .expect("予約されていない名前");
+        // 同じ名前でも内容が違えば別のルール
+        assert_ne!(first, longer);
+        assert_eq!(registry.get("beach"), Some(longer.clone()));
+        assert_eq!(
+            first,
+            GameRules::Custom(std::sync::Arc::new(forty_minute_rules()))
+        );
+
+        // 登録簿と返された`GameRules`が同じ定義を共有し、参照がなくなれば解放される
+        let GameRules::Custom(shared) = &longer else {
+            panic!("カスタムルール");
+        };
+        assert_eq!(std::sync::Arc::strong_count(shared), 2);
+        drop(registry);
+        assert_eq!(std::sync::Arc::strong_count(shared), 1);
+    }
+
+    #[test]
+    fn builtin_rule_names_cannot_be_registered() {
+        let mut registry = RuleSetRegistry::new();
+        for &name in BUILTIN_RULE_NAMES {
+            let error = registry
+                .register(RuleSet {
+                    name: name.to_string(),
+                    ..forty_minute_rules()
+                })
+                .expect_err("組み込みの名前は予約済み");
+            assert_eq!(error.name, name);
+            assert!(error.to_string().contains(name));
+        }
+        assert_eq!(registry.get("fifteens"), Some(GameRules::Fifteens));
+        assert_eq!(registry.get("sevens"), Some(GameRules::Sevens));
+        assert_eq!(registry.get("tens"), Some(GameRules::Tens));
+    }
+
+    /// フォワード0.8、バックス0.3の疲労の偏りがある状況
+    fn forwards_tired_state() -> GameState {
+        let mut state = neutral_state();
+        state.fatigue = TeamFatigue {
+            forwards: 0.8,
+            backs: 0.3,
+        };
+        state
+    }
+
+    #[test]
+    fn tired_forwards_bias_toward_pass_spread_into_the_gap() {
+        let mut state = forwards_tired_state();
+        state.defense.gap_on_right = true;
+        assert!(state.fatigue.imbalance() > 0.3);
+        assert_eq!(
+            DecisionEngine::default().decide(&state),
+            TacticalDecision::PassSpread {
+                direction: Direction::Right,
+                rushed: false,
+            }
+        );
+    }
+
+    #[test]
+    fn tired_forwards_without_a_gap_fall_through() {
+        let state = forwards_tired_state();
+        assert!(!matches!(
+            DecisionEngine::default().decide(&state),
+            TacticalDecision::PassSpread { .. }
+        ));
+    }
+
+    #[test]
+    fn tired_backs_bias_toward_forward_carries() {
+        let mut state = neutral_state();
+        state.fatigue = TeamFatigue {
+            forwards: 0.3,
+            backs: 0.8,
+        };
+        state.defense.gap_on_left = true;
+        assert_eq!(
+            DecisionEngine::default().decide(&state),
+            TacticalDecision::Crash
+        );
+    }
+
+    /// 50分に自チームにレッドカードが出た状況
+    fn red_card_state(card_law: CardLaw, elapsed_time_secs: u32) -> GameState {
+        GameState {
+            card_law,
+            red_cards_at_secs: vec![50 * 60],
+            elapsed_time_secs,
+            ..neutral_state()
+        }
+    }
+
+    #[test]
+    fn replaceable_red_restores_numbers_after_twenty_minutes() {
+        let before = red_card_state(CardLaw::ReplaceableRed20, 69 * 60 + 59);
+        assert_eq!(before.effective_players(), 14);
+        let after = red_card_state(CardLaw::ReplaceableRed20, 70 * 60);
+        assert_eq!(after.effective_players(), 15);
+    }
+
+    #[test]
+    fn permanent_red_is_never_restored() {
+        for minute in [51, 70, 79] {
+            let state = red_card_state(CardLaw::PermanentRed, minute * 60);
+            assert_eq!(state.effective_players(), 14, "{}分", minute);
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test(start_paused = true)]
+    async fn warm_up_completes_in_bounded_virtual_time() {
+        let start = tokio::time::Instant::now();
+        let (fatigue, teammates) =
+            warm_up(&Squad::for_rules(GameRules::Fifteens), &Timing::realistic()).await;
+
+        // フォワードとバックスのドリルは並行するため、長い方の600msで終わる
+        assert_eq!(start.elapsed(), Duration::from_millis(600));
+        assert!(teammates.forwards_ready && teammates.backs_ready);
+        for value in [fatigue.forwards, fatigue.backs] {
+            assert!(value > 0.0 && value < 0.1, "{}", value);
+        }
+    }
+
+    #[test]
+    fn rationale_tree_starts_with_the_safety_rules() {
+        // パニックモードを有効にすると安全ルールより優先される
+        let tree = DecisionEngine {
+            panic_mode: Some(PanicMode::default()),
+            ..DecisionEngine::default()
+        }
+        .rationale_tree();
+        assert!(matches!(
+            tree[0].condition,
+            BranchCondition::PanicMode { .. }
+        ));
+
+        // フェーズ数のハード上限は自陣22mの安全ルールより先に評価される
+        let tree = DecisionEngine::default().rationale_tree();
+        assert!(matches!(
+            tree[0].condition,
+            BranchCondition::PhasesOverHardLimit { limit: 15 }
+        ));
+        assert!(matches!(
+            tree[1].condition,
+            BranchCondition::Own22UnderPressure { .. }
+        ));
+        assert_eq!(
+            tree.last().map(|spec| &spec.condition),
+            Some(&BranchCondition::Otherwise)
+        );
+    }
+
+    #[test]
+    fn hard_limit_takes_precedence_over_own22_pressure() {
+        let engine = DecisionEngine::default();
+
+        // 強い向かい風の自陣22mでも、フェーズ数が上限を超えていればキックする
+        // 16フェーズ目でサポートが3人減っても、モールに必要な3人は残っている
+        let mut windy = own22_headwind_state(12.0);
+        windy.teammates.support_count = 6;
+        assert_eq!(engine.decide(&windy), TacticalDecision::Maul);
+        windy.consecutive_phases = engine.phase_budget.hard_limit + 1;
+        assert_eq!(
+            engine.decide(&windy),
+            TacticalDecision::Kick {
+                kick_type: choose_kick_type(&windy)
+            }
+        );
+    }
+
+    fn trailing_by(deficit: u32) -> Score {
+        Score {
+            own: 10,
+            opposition: 10 + deficit,
+        }
+    }
+
+    #[test]
+    fn scores_needed_by_deficit() {
+        let ten_minutes = 10 * 60;
+        assert_eq!(
+            trailing_by(3).scores_needed(ten_minutes),
+            ScorePlan::PenaltyGoal
+        );
+        assert_eq!(trailing_by(5).scores_needed(ten_minutes), ScorePlan::OneTry);
+        assert_eq!(
+            trailing_by(9).scores_needed(ten_minutes),
+            ScorePlan::TryAndPenalty
+        );
+        assert_eq!(ScorePlan::TryAndPenalty.scores(), 2);
+        assert_eq!(
+            trailing_by(12).scores_needed(ten_minutes),
+            ScorePlan::TwoTries
+        );
+        let leading = Score {
+            own: 14,
+            opposition: 10,
+        };
+        assert_eq!(leading.scores_needed(ten_minutes), ScorePlan::Comfortable);
+    }
+
+    #[test]
+    fn two_scores_are_out_of_reach_with_two_minutes_left() {
+        assert_eq!(trailing_by(9).scores_needed(2 * 60), ScorePlan::OutOfReach);
+        assert_eq!(trailing_by(3).scores_needed(2 * 60), ScorePlan::PenaltyGoal);
+    }
+
+    #[test]
+    fn urgency_bands_match_documented_values_within_epsilon() {
+        let cases = [
+            (
+                Score {
+                    own: 30,
+                    opposition: 10,
+                },
+                20 * 60,
+                0.2,
+            ),
+            (
+                Score {
+                    own: 20,
+                    opposition: 10,
+                },
+                20 * 60,
+                0.4,
+            ),
+            (
+                Score {
+                    own: 10,
+                    opposition: 10,
+                },
+                20 * 60,
+                0.6,
+            ),
+            (
+                Score {
+                    own: 10,
+                    opposition: 20,
+                },
+                20 * 60,
+                0.7,
+            ),
+            (
+                Score {
+                    own: 10,
+                    opposition: 20,
+                },
+                5 * 60,
+                0.9,
+            ),
+        ];
+        for (score, remaining, expected) in cases {
+            assert_approx_eq(score.urgency(remaining), expected);
+        }
+    }
+
+    #[test]
+    fn contextual_urgency_products_compare_within_epsilon() {
+        let score = Score {
+            own: 10,
+            opposition: 20,
+        };
+        // 0.9 * 0.7などの積は丸め誤差を含むため、厳密な比較ではなく近似比較を使う
+        assert_approx_eq(score.contextual_urgency(5 * 60, FieldPosition::Own22), 0.63);
+        assert_approx_eq(
+            score.contextual_urgency(5 * 60, FieldPosition::OwnHalf),
+            0.765,
+        );
+        assert_approx_eq(
+            score.contextual_urgency(5 * 60, FieldPosition::Opposition22),
+            1.0,
+        );
+        assert!(!approx_eq(
+            score.contextual_urgency(5 * 60, FieldPosition::Own22),
+            0.64
+        ));
+    }
+
+    #[test]
+    fn wind_and_fatigue_approx_eq_tolerate_rounding_only() {
+        let wind = Wind {
+            speed: 5.0,
+            direction: 90.0,
+            gust: 8.0,
+        };
+        let rounded = Wind {
+            speed: 5.0 + DEFAULT_EPSILON / 2.0,
+            ..wind
+        };
+        assert!(wind.approx_eq(&rounded));
+        assert!(!wind.approx_eq(&Wind {
+            direction: 90.1,
+            ..wind
+        }));
+        assert!(!wind.approx_eq(&Wind { gust: 8.1, ..wind }));
+
+        let fatigue = TeamFatigue {
+            forwards: 0.1 + 0.2,
+            backs: 0.5,
+        };
+        assert!(fatigue.approx_eq(&TeamFatigue {
+            forwards: 0.3,
+            backs: 0.5,
+        }));
+        assert!(!fatigue.approx_eq(&TeamFatigue {
+            forwards: 0.31,
+            backs: 0.5,
+        }));
+    }
+
+    #[test]
+    fn decisions_match_committed_golden_corpus() {
+        let diffs = check_golden().expect("ゴールデンファイルを読み込める");
+        assert!(
+            diffs.is_empty(),
+            "判断が変化しました（意図した変更なら`--regenerate-golden`で更新してください）:\n{}",
+            diffs.join("\n")
+        );
+        assert_eq!(golden_corpus().lines().count(), GOLDEN_CORPUS_SIZE as usize);
+    }
+
+    #[test]
+    fn sampled_wind_speed_stays_between_speed_and_gust() {
+        let wind = Wind {
+            speed: 4.0,
+            direction: 90.0,
+            gust: 12.0,
+        };
+        let mut rng = SimRng::new(7);
+        let samples: Vec<f32> = (0..1000)
+            .map(|_| wind.sample_instantaneous(&mut rng))
+            .collect();
+        assert!(samples.iter().all(|speed| (4.0..=12.0).contains(speed)));
+        // u²の分布なので平均は speed + (gust - speed) / 3 付近になる
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        assert!((mean - (4.0 + 8.0 / 3.0)).abs() < 0.5, "mean={}", mean);
+
+        let calm = Wind { gust: 2.0, ..wind };
+        assert_eq!(calm.sample_instantaneous(&mut rng), 4.0);
+    }
+
+    #[test]
+    fn gusty_kick_gain_varies_but_is_reproducible_per_seed() {
+        let state = GameState {
+            wind: Wind {
+                speed: 3.0,
+                direction: 90.0,
+                gust: 15.0,
+            },
+            ..neutral_state()
+        };
+        let gains = |seed| {
+            let mut rng = SimRng::new(seed);
+            (0..20)
+                .map(|_| estimate_kick_gain_in_gust(KickType::Touch, &state, &mut rng))
+                .collect::<Vec<f32>>()
+        };
+        assert_eq!(gains(11), gains(11));
+        let first = gains(11);
+        assert!(first.iter().any(|gain| *gain != first[0]));
+    }
+
+    /// 終盤に15点差を追う状況
+    fn chasing_late() -> GameState {
+        GameState {
+            elapsed_time_secs: 75 * 60,
+            score: Score {
+                own: 10,
+                opposition: 25,
+            },
+            ..neutral_state()
+        }
+    }
+
+    #[test]
+    fn forbidden_quick_tap_falls_through_to_best_allowed_decision() {
+        let state = chasing_late();
+        assert_eq!(
+            DecisionEngine::new().decide(&state),
+            TacticalDecision::QuickTap
+        );
+
+        let engine = DecisionEngine::new()
+            .forbid([
+                DecisionKind::QuickTap,
+                DecisionKind::Kick(KickType::Grubber),
+            ])
+            .expect("許可された判断が残る");
+        let (decision, reason) = engine.decide_with_reason(&state);
+        let best_allowed = score_decisions(&state)
+            .into_iter()
+            .filter(|(candidate, _)| !engine.forbidden().contains(&candidate.kind()))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(candidate, _)| candidate)
+            .expect("候補がある");
+        assert_ne!(decision.kind(), DecisionKind::QuickTap);
+        assert_eq!(decision, best_allowed);
+        assert!(reason.starts_with("🚫"));
+    }
+
+    #[test]
+    fn forbidding_every_fallback_is_rejected_at_config_time() {
+        assert_eq!(
+            DecisionEngine::new().forbid(all_decision_kinds()).err(),
+            Some(EngineError::AllDecisionsForbidden)
+        );
+
+        // キックがすべて許可されていれば、状況に応じたキックが必ず候補に残る
+        let fixed = [
+            DecisionKind::Crash,
+            DecisionKind::QuickTap,
+            DecisionKind::Maul,
+            DecisionKind::DropGoal,
+        ];
+        let engine = DecisionEngine::new()
+            .forbid(fixed)
+            .expect("キックは許可されている");
+        assert!(matches!(
+            engine.decide(&chasing_late()),
+            TacticalDecision::Kick { .. }
+        ));
+
+        let one_kick = fixed
+            .into_iter()
+            .chain([DecisionKind::Kick(KickType::Touch)]);
+        assert_eq!(
+            DecisionEngine::new().forbid(one_kick).err(),
+            Some(EngineError::AllDecisionsForbidden)
+        );
+    }
+
+    #[test]
+    fn mirror_of_left_gap_pass_is_right_gap_pass() {
+        let state = GameState {
+            wind: Wind {
+                speed: 6.0,
+                direction: 30.0,
+                gust: 9.0,
+            },
+            defense: DefenseLine {
+                gap_on_left: true,
+                ..neutral_state().defense
+            },
+            ..neutral_state()
+        };
+        let mirrored = state.mirror();
+        assert!(mirrored.defense.gap_on_right && !mirrored.defense.gap_on_left);
+        assert_eq!(mirrored.wind.direction, 330.0);
+        assert_eq!(
+            (mirrored.score.own, mirrored.score.opposition),
+            (state.score.own, state.score.opposition)
+        );
+        assert_eq!(format!("{:?}", mirrored.mirror()), format!("{:?}", state));
+
+        let engine = DecisionEngine::new();
+        assert_eq!(
+            engine.decide(&state),
+            TacticalDecision::PassSpread {
+                direction: Direction::Left,
+                rushed: false,
+            }
+        );
+        assert_eq!(
+            engine.decide(&mirrored),
+            TacticalDecision::PassSpread {
+                direction: Direction::Right,
+                rushed: false,
+            }
+        );
+    }
+
+    #[test]
+    fn highest_urgency_decision_follows_late_deficit() {
+        // 15点ビハインドで残り15分から始め、残り10分を切ると緊急（0.9）になる
+        let initial = GameState {
+            elapsed_time_secs: 65 * 60,
+            score: trailing_by(15),
+            ..neutral_state()
+        };
+        let mut phases = Vec::new();
+        let summary = simulate_match(initial, &SimulationConfig::default(), |state, decision| {
+            phases.push((
+                state.elapsed_time_secs,
+                state.score.urgency(state.time_remaining_secs()),
+                state.consecutive_phases,
+                *decision,
+            ));
+        });
+
+        let highest = summary.highest_urgency_decision.expect("判断がある");
+        assert_eq!(highest.urgency, 0.9);
+        assert!(highest.score.difference() < -7);
+        assert!(highest.at_secs > 70 * 60);
+        let first_highest = phases
+            .iter()
+            .find(|(_, urgency, _, _)| *urgency == highest.urgency)
+            .expect("記録したフェーズに含まれる");
+        assert_eq!(highest.at_secs, first_highest.0);
+        assert_eq!(highest.decision, first_highest.3);
+        assert!(
+            phases
+                .iter()
+                .all(|(_, urgency, _, _)| *urgency <= highest.urgency)
+        );
+
+        let longest = summary.longest_phase_sequence.expect("判断がある");
+        let max_phases = phases
+            .iter()
+            .map(|phase| phase.2)
+            .max()
+            .expect("判断がある");
+        assert_eq!(longest.phases, max_phases);
+        let first_longest = phases
+            .iter()
+            .find(|phase| phase.2 == max_phases)
+            .expect("記録したフェーズに含まれる");
+        assert_eq!(longest.at_secs, first_longest.0);
+    }
+
+    #[test]
+    fn own_scores_are_classified_by_the_scoring_decision() {
+        let before = Score {
+            own: 0,
+            opposition: 0,
+        };
+        let three_up = Score {
+            own: 3,
+            opposition: 0,
+        };
+        let kinds = |decision: &TacticalDecision| -> Vec<KeyMomentKind> {
+            detect_key_moments(Some(decision), before, three_up, 600)
+                .iter()
+                .map(|moment| moment.kind)
+                .collect()
+        };
+        assert_eq!(
+            kinds(&TacticalDecision::DropGoal),
+            vec![KeyMomentKind::DropGoalScored, KeyMomentKind::LeadChange]
+        );
+        // 3点の得点でもドロップゴール以外ならトライとして扱う
+        assert_eq!(
+            kinds(&TacticalDecision::Crash),
+            vec![KeyMomentKind::TryScored, KeyMomentKind::LeadChange]
+        );
+
+        // 守備のフェーズ（判断なし）での失トライ
+        let conceded = detect_key_moments(
+            None,
+            three_up,
+            Score {
+                own: 3,
+                opposition: 7,
+            },
+            900,
+        );
+        assert_eq!(
+            conceded
+                .iter()
+                .map(|moment| moment.kind)
+                .collect::<Vec<_>>(),
+            vec![KeyMomentKind::TryConceded, KeyMomentKind::LeadChange]
+        );
+    }
+
+    #[test]
+    fn all_fixtures_yields_both_example_scenarios_with_their_scores() {
+        let fixtures: Vec<(&str, GameState)> = all_fixtures().collect();
+        assert_eq!(fixtures.len(), PRESET_NAMES.len());
+
+        let score_of = |name: &str| {
+            fixtures
+                .iter()
+                .find(|(fixture, _)| *fixture == name)
+                .map(|(_, state)| (state.score.own, state.score.opposition))
+        };
+        assert_eq!(score_of("close_game_late"), Some((21, 24)));
+        assert_eq!(score_of("rainy_red_zone"), Some((14, 10)));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn instant_timing_completes_in_near_zero_wall_time() {
+        let timing = Timing::instant();
+        let state = close_game_late();
+        let start = std::time::Instant::now();
+        analyze_game_state_to(&state, &mut Vec::new(), Locale::Japanese, &timing).await;
+        let decision = make_complex_decision(&state, &timing).await;
+        let _ = warm_up(&Squad::for_rules(GameRules::Fifteens), &timing).await;
+        let (sender, _receiver) = tokio::sync::mpsc::channel(32);
+        let _ = score_decisions_async(&state, sender, &timing).await;
+        let elapsed = start.elapsed();
+
+        assert_eq!(decision, DecisionEngine::new().decide(&state));
+        // realistic()なら分析と判断だけで800msかかる
+        assert!(
+            elapsed < Duration::from_millis(100),
+            "elapsed={:?}",
+            elapsed
+        );
+    }
+
+    #[test]
+    fn season_histogram_reports_modal_decision_per_cell() {
+        // 残り5分で15点ビハインドの中盤から、2試合を4フェーズずつ
+        let short = |seed| {
+            let config = SimulationConfig {
+                seed,
+                defensive_phase_secs: 10,
+                stop_when: Some(Box::new(|state: &GameState| {
+                    state.elapsed_time_secs >= 75 * 60 + 40
+                })),
+                ..Default::default()
+            };
+            simulate_match(chasing_late(), &config, |_, _| {})
+        };
+        let matches = [short(1), short(2)];
+        let histogram = season_histogram(&matches);
+
+        let cell = (FieldPosition::Midfield, ScoreState::DesperateBehind);
+        assert!(histogram.total(cell) >= 2);
+        assert_eq!(histogram.modal_decision(cell), Some(DecisionKind::QuickTap));
+
+        let recorded: usize = matches.iter().map(|summary| summary.decisions.len()).sum();
+        let counted: u32 = histogram.cells().map(|cell| histogram.total(cell)).sum();
+        assert_eq!(counted as usize, recorded);
+        assert_eq!(
+            histogram.modal_decision((FieldPosition::Own22, ScoreState::CommandingLead)),
+            None
+        );
+    }
+
+    #[test]
+    fn phase_contexts_describe_the_state_each_decision_was_made_in() {
+        let mut seen = Vec::new();
+        let summary = simulate_match(
+            close_game_late(),
+            &SimulationConfig::default(),
+            |state, _| {
+                seen.push((
+                    state.elapsed_time_secs,
+                    state.position,
+                    state.score.difference(),
+                ))
+            },
+        );
+        let contexts: Vec<_> = summary
+            .contexts
+            .iter()
+            .map(|context| {
+                (
+                    context.at_secs,
+                    context.position,
+                    context.score.difference(),
+                )
+            })
+            .collect();
+        assert_eq!(contexts, seen);
+    }
+
+    #[test]
+    fn score_state_boundaries() {
+        let ten_minutes = 10 * 60;
+        let state =
+            |own: u32, opposition: u32, remaining: u32| Score { own, opposition }.state(remaining);
+        assert_eq!(state(25, 10, ten_minutes), ScoreState::CommandingLead);
+        assert_eq!(state(24, 10, ten_minutes), ScoreState::Lead);
+        assert_eq!(state(17, 10, ten_minutes), ScoreState::Close);
+        assert_eq!(state(10, 17, ten_minutes), ScoreState::Close);
+        assert_eq!(state(10, 18, ten_minutes), ScoreState::Behind);
+        assert_eq!(state(10, 18, ten_minutes - 1), ScoreState::DesperateBehind);
+    }
+
+    #[test]
+    fn late_branches_switch_exactly_at_score_state_boundaries() {
+        let full_time = 80 * 60;
+        let at = |own: u32, opposition: u32, remaining: u32| GameState {
+            elapsed_time_secs: full_time - remaining,
+            score: Score { own, opposition },
+            ..neutral_state()
+        };
+        let engine = DecisionEngine::default();
+        let matched = |state: &GameState, wanted: fn(&BranchCondition) -> bool| {
+            engine
+                .audit(state)
+                .iter()
+                .find(|eval| wanted(&eval.condition))
+                .expect("分岐が評価されている")
+                .matched
+        };
+        let leading = |c: &BranchCondition| matches!(c, BranchCondition::LeadingLate { .. });
+        let trailing = |c: &BranchCondition| matches!(c, BranchCondition::TrailingLate);
+        let five_minutes = 5 * 60;
+        let late = ScoreState::LATE_UNDER_SECS;
+
+        // 8点差のリードは`Lead`で、大量リードの分岐には入らない
+        let lead_of_eight = at(18, 10, five_minutes);
+        assert_eq!(lead_of_eight.score.state(five_minutes), ScoreState::Lead);
+        assert!(!matched(&lead_of_eight, leading));
+        assert!(!matched(&at(24, 10, five_minutes), leading));
+        let lead_of_fifteen = at(25, 10, five_minutes);
+        assert!(matched(&lead_of_fifteen, leading));
+        assert_eq!(
+            engine.decide(&lead_of_fifteen),
+            TacticalDecision::Kick {
+                kick_type: KickType::Touch
+            }
+        );
+
+        // 8点差のビハインドは残り10分を切った時点で終盤の分岐に入る
+        assert!(matched(&at(10, 18, late - 1), trailing));
+        assert!(!matched(&at(10, 18, late), trailing));
+        assert!(!matched(&at(10, 17, late - 1), trailing));
+
+        // 文脈付きの緊急性も同じ残り時間で切り替わる（1点差でも）
+        let one_behind = Score {
+            own: 9,
+            opposition: 10,
+        };
+        let red_zone = FieldPosition::Opposition22;
+        assert!(one_behind.contextual_urgency(late - 1, red_zone) > one_behind.urgency(late - 1));
+        assert_eq!(
+            one_behind.contextual_urgency(late, red_zone),
+            one_behind.urgency(late)
+        );
+    }
+
+    #[test]
+    fn narrow_lead_branch_and_engine_share_the_same_window() {
+        let full_time = 80 * 60;
+        let at = |lead: u32, remaining: u32| GameState {
+            elapsed_time_secs: full_time - remaining,
+            score: Score {
+                own: 10 + lead,
+                opposition: 10,
+            },
+            ..neutral_state()
+        };
+        let engine = DecisionEngine::default();
+        let up_to = ScoreState::NARROW_LEAD_UP_TO as u32;
+        let closing = ScoreState::CLOSING_UNDER_SECS;
+
+        for lead in 0..=up_to + 1 {
+            for remaining in [closing - 1, closing] {
+                let state = at(lead, remaining);
+                let inside = state.score.is_narrow_lead_closing(remaining);
+                assert_eq!(inside, (1..=up_to).contains(&lead) && remaining < closing);
+                let branch = engine
+                    .audit(&state)
+                    .into_iter()
+                    .find(|eval| matches!(eval.condition, BranchCondition::NarrowLeadLate { .. }))
+                    .expect("僅差のリードの分岐が評価されている");
+                assert_eq!(
+                    branch.matched, inside,
+                    "{}点リード、残り{}秒",
+                    lead, remaining
+                );
+                if inside {
+                    assert_eq!(engine.decide(&state), TacticalDecision::Crash);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn meter_ranges_are_contiguous_and_cover_the_pitch() {
+        let ranges: Vec<std::ops::RangeInclusive<f32>> = FieldPosition::ALL
+            .iter()
+            .map(FieldPosition::meter_range)
+            .collect();
+        assert_eq!(*ranges[0].start(), 0.0);
+        assert_eq!(*ranges[ranges.len() - 1].end(), 100.0);
+        for pair in ranges.windows(2) {
+            assert_eq!(pair[0].end(), pair[1].start());
+        }
+        let total: f32 = ranges.iter().map(|range| range.end() - range.start()).sum();
+        assert_eq!(total, 100.0);
+
+        for position in FieldPosition::ALL {
+            assert!(position.meter_range().contains(&position.midpoint()));
+        }
+    }
+
+    #[test]
+    fn audit_of_scenario_two_rejects_safety_branches_and_matches_gap() {
+        let state = rainy_red_zone();
+        let engine = DecisionEngine::new();
+        let audit = engine.audit(&state);
+        let eval = |wanted: fn(&BranchCondition) -> bool| {
+            audit
+                .iter()
+                .find(|eval| wanted(&eval.condition))
+                .expect("分岐が評価されている")
+        };
+
+        let own22 = eval(|c| matches!(c, BranchCondition::Own22UnderPressure { .. }));
+        assert!(!own22.matched);
+        assert!(own22.observed.contains("位置=Opposition22"));
+        let trailing = eval(|c| matches!(c, BranchCondition::TrailingLate));
+        assert!(!trailing.matched);
+        assert!(trailing.observed.contains("点差=+4"));
+        let hard_limit = eval(|c| matches!(c, BranchCondition::PhasesOverHardLimit { .. }));
+        assert!(!hard_limit.matched);
+        assert!(hard_limit.observed.contains("連続フェーズ=12"));
+
+        let gap = eval(|c| matches!(c, BranchCondition::GapInOpposition22));
+        assert!(gap.matched);
+        assert!(gap.observed.contains("ギャップ=Some(Left)"));
+
+        // 最初に当てはまった分岐（雨天）が判断を決める
+        let first = audit
+            .iter()
+            .find(|eval| eval.matched)
+            .expect("Otherwiseは常に当てはまる");
+        assert!(matches!(
+            first.condition,
+            BranchCondition::PoorHandling { .. }
+        ));
+        assert_eq!(engine.decide(&state), TacticalDecision::Crash);
+    }
+
+    /// 判断を捨てると`unused_must_use`が出ることをビルド時に検査する
+    ///
+    /// 捨てる側の関数は`#[expect]`で警告を期待し、警告が出なくなると
+    /// `unfulfilled_lint_expectations`でビルドが失敗します。
+    #[test]
+    #[deny(unused_must_use, unfulfilled_lint_expectations)]
+    fn discarding_a_decision_triggers_unused_must_use() {
+        #[expect(unused_must_use)]
+        fn discard_decide(engine: &DecisionEngine, state: &GameState) {
+            engine.decide(state);
+        }
+        #[expect(unused_must_use)]
+        fn discard_scores(state: &GameState) {
+            score_decisions(state);
+        }
+        // 属性のない関数が返す判断も、型の`#[must_use]`で警告される
+        #[expect(unused_must_use)]
+        fn discard_returned_decision() {
+            fn returns_decision() -> TacticalDecision {
+                TacticalDecision::Crash
+            }
+            returns_decision();
+        }
+
+        let state = neutral_state();
+        let engine = DecisionEngine::new();
+        discard_decide(&engine, &state);
+        discard_scores(&state);
+        discard_returned_decision();
+
+        // 意図して捨てる書き方は警告されない
+        let _ = engine.decide(&state);
+        let _ = engine.batch(std::slice::from_ref(&state));
+    }
+
+    #[test]
+    fn score_only_patch_changes_score_and_nothing_else() {
+        let original = close_game_late();
+        let mut state = original.clone();
+        let score = Score {
+            own: 28,
+            opposition: 24,
+        };
+        let changed = state.apply(GameStatePatch {
+            score: Some(score),
+            ..GameStatePatch::default()
+        });
+
+        assert_eq!(changed, [GameStateField::Score]);
+        assert_eq!(state.score, score);
+        assert_eq!(
+            format!(
+                "{:?}",
+                GameState {
+                    score: original.score,
+                    ..state.clone()
+                }
+            ),
+            format!("{:?}", original)
+        );
+
+        // 同じ値の差分は変更として数えない
+        assert!(
+            state
+                .apply(GameStatePatch {
+                    score: Some(score),
+                    ..GameStatePatch::default()
+                })
+                .is_empty()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn patch_fields_omitted_from_json_are_left_unchanged() {
+        let patch: GameStatePatch =
+            serde_json::from_str(r#"{ "score": { "own": 24, "opposition": 24 } }"#)
+                .expect("一部のフィールドだけの差分を読める");
+        assert!(patch.position.is_none() && patch.defense.is_none());
+
+        let mut state = close_game_late();
+        assert_eq!(state.apply(patch), [GameStateField::Score]);
+        assert_eq!(state.score.difference(), 0);
+        assert_eq!(state.position, close_game_late().position);
+    }
+
+    #[test]
+    fn successful_touch_kick_from_own22_advances_and_hands_over_possession() {
+        let state = GameState {
+            position: FieldPosition::Own22,
+            possession: Possession::Own,
+            ..neutral_state()
+        };
+        let touch = TacticalDecision::Kick {
+            kick_type: KickType::Touch,
+        };
+        for seed in 0..20 {
+            let mut rng = SimRng::new(seed);
+            let outcome = sample_kick_outcome(KickType::Touch, &state, &mut rng);
+            assert!(!outcome.retained, "タッチキックは再獲得しない");
+
+            let mut after = state.clone();
+            apply_outcome(&mut after, &touch, true, Some(outcome));
+            assert_eq!(
+                after.position,
+                FieldPosition::from_meters(FieldPosition::Own22.midpoint() + outcome.gain_m)
+            );
+            assert_ne!(after.position, FieldPosition::Own22);
+            assert_eq!(after.possession, Possession::Opposition);
+            assert_eq!(after.consecutive_phases, 0);
+        }
+    }
+
+    #[test]
+    fn retained_bomb_keeps_possession() {
+        let mut state = GameState {
+            possession: Possession::Own,
+            ..neutral_state()
+        };
+        let bomb = TacticalDecision::Kick {
+            kick_type: KickType::Bomb,
+        };
+        let outcome = KickOutcome {
+            gain_m: 25.0,
+            retained: true,
+        };
+        apply_outcome(&mut state, &bomb, true, Some(outcome));
+        assert_eq!(state.position, FieldPosition::OppositionHalf);
+        assert_eq!(state.possession, Possession::Own);
+    }
+
+    #[test]
+    fn repeated_red_zone_penalties_card_a_player_and_change_later_decisions() {
+        // 自陣22mで守備を始める
+        let initial = GameState {
+            position: FieldPosition::Own22,
+            possession: Possession::Opposition,
+            ..neutral_state()
+        };
+        let escalation = CardEscalation {
+            penalties: 2,
+            window_secs: 600,
+            sin_bin_secs: 600,
+        };
+        let run = |seed, card_escalation| {
+            let config = SimulationConfig {
+                seed,
+                card_escalation,
+                ..Default::default()
+            };
+            let mut phases = Vec::new();
+            let summary = simulate_match(initial.clone(), &config, |state, decision| {
+                phases.push((state.clone(), *decision));
+            });
+            (summary, phases)
+        };
+        // 同じ状況でカードがなかった場合の判断
+        let engine = DecisionEngine::default();
+        let without_card = |state: &GameState| {
+            engine.decide(&GameState {
+                yellow_cards: 0,
+                ..state.clone()
+            })
+        };
+        let changed_by_card = |phases: &[(GameState, TacticalDecision)]| {
+            phases
+                .iter()
+                .filter(|(state, decision)| {
+                    state.yellow_cards > 0 && without_card(state) != *decision
+                })
+                .map(|(_, decision)| *decision)
+                .collect::<Vec<_>>()
+        };
+        let (seed, (summary, phases)) = (0..2000)
+            .map(|seed| (seed, run(seed, Some(escalation))))
+            .find(|(_, (summary, phases))| {
+                summary
+                    .key_moments
+                    .iter()
+                    .any(|moment| moment.kind == KeyMomentKind::YellowCard)
+                    && !changed_by_card(phases).is_empty()
+            })
+            .expect("自陣22mでの反則の繰り返しでカードが出て、その後の判断が変わるシードがある");
+        let card_at = summary
+            .key_moments
+            .iter()
+            .find(|moment| moment.kind == KeyMomentKind::YellowCard)
+            .map(|moment| moment.at_secs)
+            .expect("カードが記録されている");
+
+        // カードが出るまでは基準がない場合と同じ判断が続く
+        let (uncarded, _) = run(seed, None);
+        let before_card = summary
+            .contexts
+            .iter()
+            .take_while(|context| context.at_secs <= card_at)
+            .count();
+        assert_eq!(
+            summary.decisions[..before_card],
+            uncarded.decisions[..before_card]
+        );
+
+        // シンビン中は数的不利が判断に渡され、戻った後は元に戻る
+        let sin_bin = card_at..card_at + escalation.sin_bin_secs;
+        let (during, after): (Vec<_>, Vec<_>) = phases
+            .iter()
+            .filter(|(state, _)| state.elapsed_time_secs > card_at)
+            .partition(|(state, _)| sin_bin.contains(&state.elapsed_time_secs));
+        assert!(!during.is_empty());
+        assert!(
+            during
+                .iter()
+                .all(|(state, _)| state.numbers_advantage() == -1)
+        );
+        assert!(
+            after
+                .iter()
+                .all(|(state, _)| state.numbers_advantage() == 0)
+        );
+
+        // 数的不利でキックの推奨度が上がり、カードがなければ選ばなかったキックに変わる
+        let changed = changed_by_card(&phases);
+        assert!(
+            changed
+                .iter()
+                .all(|decision| matches!(decision, TacticalDecision::Kick { .. })),
+            "{changed:?}"
+        );
+    }
+
+    #[test]
+    fn sevens_match_is_shorter_with_a_different_decision_mix() {
+        let results = compare_formats(neutral_state(), &[GameRules::Fifteens, GameRules::Sevens]);
+        let [(fifteens_rules, fifteens), (sevens_rules, sevens)] = &results[..] else {
+            panic!("ルールごとに1件ずつ結果がある");
+        };
+        assert_eq!(*fifteens_rules, GameRules::Fifteens);
+        assert_eq!(*sevens_rules, GameRules::Sevens);
+
+        // 試合時間に対する割合を保って開始し、それぞれの試合終了まで進む
+        assert_eq!(sevens.contexts[0].at_secs * 80, 30 * 60 * 14);
+        assert_eq!(
+            sevens.final_state.elapsed_time_secs,
+            GameRules::Sevens.match_duration_secs()
+        );
+        assert!(sevens.final_state.elapsed_time_secs < fifteens.final_state.elapsed_time_secs);
+        assert!(sevens.decisions.len() < fifteens.decisions.len());
+
+        // 判断の種類ごとの割合（%）
+        let mix = |summary: &MatchSummary| {
+            let mut shares: Vec<(DecisionKind, usize)> = Vec::new();
+            for decision in &summary.decisions {
+                match shares.iter_mut().find(|(kind, _)| *kind == decision.kind()) {
+                    Some((_, count)) => *count += 1,
+                    None => shares.push((decision.kind(), 1)),
+                }
+            }
+            let total = summary.decisions.len();
+            let mut shares: Vec<(String, usize)> = shares
+                .into_iter()
+                .map(|(kind, count)| (format!("{:?}", kind), count * 100 / total))
+                .collect();
+            shares.sort();
+            shares
+        };
+        assert_ne!(mix(sevens), mix(fifteens));
+    }
+
+    #[test]
+    fn rewarding_pass_spread_raises_the_weights_that_favor_it() {
+        let pass = TacticalDecision::PassSpread {
+            direction: Direction::Left,
+            rushed: false,
+        };
+        let state = GameState {
+            defense: DefenseLine {
+                gap_on_left: true,
+                ..neutral_state().defense
+            },
+            ..neutral_state()
+        };
+        let pass_score = |engine: &DecisionEngine| {
+            engine
+                .weighted_scores(&state)
+                .into_iter()
+                .find(|(candidate, _)| *candidate == pass)
+                .map(|(_, score)| score)
+                .expect("パス展開は候補に含まれる")
+        };
+        let mut engine = DecisionEngine::new();
+        let before = engine.weights;
+        let score_before = pass_score(&engine);
+        for _ in 0..10 {
+            engine.update_weights(&pass, Outcome::Scored { points: 7 }, 0.1);
+        }
+        let after = engine.weights;
+
+        // パス展開と相性の良いスコアの緊急性は上がり、相性の悪い要素は下がる
+        assert!(after.score_urgency > before.score_urgency + 0.9);
+        assert!(after.position_risk < before.position_risk);
+        assert!(after.weather < before.weather);
+        assert_eq!(after.time_pressure, before.time_pressure);
+        assert!(pass_score(&engine) > score_before);
+
+        // 失点で同じだけ罰すると元に戻る
+        for _ in 0..10 {
+            engine.update_weights(&pass, Outcome::Conceded { points: 7 }, 0.1);
+        }
+        assert!((engine.weights.score_urgency - before.score_urgency).abs() < 1e-4);
+    }
+
+    #[test]
+    fn sevens_weighting_lowers_overall_for_tired_forwards() {
+        let fatigue = TeamFatigue {
+            forwards: 0.8,
+            backs: 0.2,
+        };
+        assert_eq!(fatigue.overall(), fatigue.overall_weighted(0.6));
+        assert!((fatigue.overall() - 0.56).abs() < 1e-6);
+        assert!((fatigue.overall_weighted(0.4) - 0.44).abs() < 1e-6);
+
+        let fifteens = GameState {
+            fatigue: fatigue.clone(),
+            ..neutral_state()
+        };
+        let sevens = GameState {
+            rules: GameRules::Sevens,
+            ..fifteens.clone()
+        };
+        assert!(sevens.fatigue_overall() < fifteens.fatigue_overall());
+        assert_eq!(
+            sevens.fatigue_overall(),
+            fatigue.overall_weighted(GameRules::Sevens.forward_fatigue_weight())
+        );
+        assert_eq!(fifteens.fatigue_level(), fatigue.level());
+        assert_eq!(sevens.fatigue_level(), fatigue.level_weighted(0.4));
+    }
+
+    #[test]
+    fn defensive_regain_uses_format_weighted_fatigue() {
+        // 自チームはFWが、相手はBKが疲れている: 15人制では自チームの方が疲れていて、
+        // 7人制（BKの重みが大きい）では相手の方が疲れている
+        let fifteens = GameState {
+            possession: Possession::Opposition,
+            fatigue: TeamFatigue {
+                forwards: 0.8,
+                backs: 0.2,
+            },
+            opposition_fatigue: TeamFatigue {
+                forwards: 0.2,
+                backs: 0.8,
+            },
+            ..neutral_state()
+        };
+        let sevens = GameState {
+            rules: GameRules::Sevens,
+            ..fifteens.clone()
+        };
+        assert!(fifteens.opposition_fatigue_overall() < fifteens.fatigue_overall());
+        assert!(sevens.opposition_fatigue_overall() > sevens.fatigue_overall());
+
+        let regains = |state: &GameState| {
+            (0..1000)
+                .filter(|&seed| {
+                    let mut state = state.clone();
+                    let penalized = defend_phase(&mut state, &mut SimRng::new(seed));
+                    !penalized && state.possession == Possession::Own
+                })
+                .count()
+        };
+        assert!(regains(&sevens) > regains(&fifteens));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn cancelled_simulation_returns_consistent_partial_summary() {
+        let token = CancellationToken::new();
+        let trigger = token.clone();
+        let mut seen = 0;
+        let partial = simulate_match_cancellable(
+            neutral_state(),
+            &SimulationConfig::default(),
+            token,
+            |_, _| {
+                seen += 1;
+                if seen == 10 {
+                    trigger.cancel();
+                }
+            },
+        )
+        .await;
+
+        assert!(partial.cancelled);
+        assert!(!partial.stopped_early);
+        assert!(
+            partial.final_state.elapsed_time_secs < partial.final_state.rules.match_duration_secs()
+        );
+        assert_eq!(partial.decisions.len(), 10);
+        assert_eq!(partial.contexts.len(), partial.decisions.len());
+
+        // 中断時点の状態は、同じ時刻で打ち切ったシミュレーションと一致する
+        let cancelled_at = partial.final_state.elapsed_time_secs;
+        let stopped = simulate_match(
+            neutral_state(),
+            &SimulationConfig {
+                stop_when: Some(Box::new(move |state: &GameState| {
+                    state.elapsed_time_secs >= cancelled_at
+                })),
+                ..Default::default()
+            },
+            |_, _| {},
+        );
+        assert_eq!(
+            format!("{:?}", partial.final_state),
+            format!("{:?}", stopped.final_state)
+        );
+        let full = simulate_match(neutral_state(), &SimulationConfig::default(), |_, _| {});
+        assert_eq!(partial.decisions[..], full.decisions[..10]);
+
+        let uncancelled = simulate_match_cancellable(
+            neutral_state(),
+            &SimulationConfig::default(),
+            CancellationToken::new(),
+            |_, _| {},
+        )
+        .await;
+        assert!(!uncancelled.cancelled);
+        assert_eq!(uncancelled.decisions, full.decisions);
+    }
+
+    #[test]
+    fn representative_fraction_round_trips_for_every_level() {
+        for level in [
+            FatigueLevel::Fresh,
+            FatigueLevel::Moderate,
+            FatigueLevel::Tired,
+            FatigueLevel::Exhausted,
+        ] {
+            assert_eq!(
+                FatigueLevel::from_percentage(level.representative_fraction()),
+                level
+            );
+        }
+    }
+
+    #[test]
+    fn kick_everything_decider_always_kicks_in_a_dyn_decider_harness() {
+        let deciders: Vec<Box<dyn Decider>> = vec![
+            Box::new(DecisionEngine::default()),
+            Box::new(RandomDecider { seed: 7 }),
+            Box::new(KickEverythingDecider),
+        ];
+        let states = random_states(200);
+
+        let mut results = std::collections::HashMap::new();
+        for decider in &deciders {
+            let decisions: Vec<TacticalDecision> =
+                states.iter().map(|state| decider.decide(state)).collect();
+            results.insert(decider.name().to_string(), decisions);
+        }
+
+        assert_eq!(results.len(), deciders.len());
+        assert!(
+            results["KickEverythingDecider"]
+                .iter()
+                .all(|decision| matches!(decision, TacticalDecision::Kick { .. }))
+        );
+        let engine = DecisionEngine::default();
+        let expected: Vec<TacticalDecision> = states.iter().map(|s| engine.decide(s)).collect();
+        assert_eq!(results["DecisionEngine"], expected);
+        assert_ne!(results["KickEverythingDecider"], results["DecisionEngine"]);
+    }
+
+    #[test]
+    fn scoring_opportunity_reports_each_variant() {
+        let mut immediate = neutral_state();
+        immediate.position = FieldPosition::Opposition22;
+        immediate.defense.gap_on_left = true;
+        immediate.teammates.backs_ready = true;
+        assert_eq!(
+            immediate.scoring_opportunity(),
+            ScoringOpportunity::Immediate
+        );
+
+        // バックスの準備ができていなければ、連続攻撃中でも`Building`に留まる
+        let mut building = immediate.clone();
+        building.teammates.backs_ready = false;
+        building.consecutive_phases = 3;
+        assert_eq!(building.scoring_opportunity(), ScoringOpportunity::Building);
+        building.position = FieldPosition::OppositionHalf;
+        building.defense.gap_on_left = false;
+        assert_eq!(building.scoring_opportunity(), ScoringOpportunity::Building);
+
+        let mut none = building.clone();
+        none.consecutive_phases = 2;
+        assert_eq!(none.scoring_opportunity(), ScoringOpportunity::None);
+        let mut own_half = building;
+        own_half.position = FieldPosition::Midfield;
+        assert_eq!(own_half.scoring_opportunity(), ScoringOpportunity::None);
+    }
+
+    #[test]
+    fn same_wind_is_headwind_before_half_time_and_tailwind_after() {
+        let wind = Wind {
+            speed: 8.0,
+            direction: 0.0,
+            gust: 0.0,
+        };
+        let mut initial = neutral_state();
+        initial.elapsed_time_secs = 0;
+        initial.wind = wind;
+        initial.attacking_bearing = 0.0;
+        let half_time = initial.rules.match_duration_secs() / 2;
+
+        let (mut first_half, mut second_half) = (0, 0);
+        simulate_match(initial, &SimulationConfig::default(), |state, _| {
+            let headwind = state.headwind();
+            if state.elapsed_time_secs < half_time {
+                assert!(headwind > 7.9, "前半は向かい風: {headwind}");
+                first_half += 1;
+            } else {
+                assert!(headwind < -7.9, "後半は追い風: {headwind}");
+                second_half += 1;
+            }
+        });
+        assert!(first_half > 0 && second_half > 0);
+    }
+
+    #[test]
+    fn heatmap_counts_phases_by_position_and_score_state() {
+        // 残り5分で15点ビハインドの中盤から、開始直後に2トライを返して接戦にする
+        let own_try = Event::ScoreEvent {
+            team: Team::Own,
+            kind: ScoreKind::Try,
+        };
+        let config = SimulationConfig {
+            defensive_phase_secs: 10,
+            stop_when: Some(Box::new(|state: &GameState| {
+                state.elapsed_time_secs >= 75 * 60 + 40
+            })),
+            ..Default::default()
+        }
+        .with_scripted_events([(0, own_try.clone()), (0, own_try)]);
+        let summary = simulate_match(chasing_late(), &config, |_, _| {});
+        let heatmap = heatmap(&summary);
+
+        assert_eq!(heatmap.count(FieldPosition::Midfield, ScoreState::Close), 2);
+        assert_eq!(
+            heatmap.count(FieldPosition::Midfield, ScoreState::DesperateBehind),
+            0
+        );
+        assert_eq!(heatmap.total() as usize, summary.decisions.len());
+
+        let mut buffer = Vec::new();
+        heatmap
+            .to_csv(&mut buffer)
+            .expect("バッファへの書き出しは失敗しない");
+        let csv = String::from_utf8(buffer).expect("UTF-8で書き出される");
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "position,CommandingLead,Lead,Close,Behind,DesperateBehind"
+        );
+        assert_eq!(lines.len(), 1 + FieldPosition::ALL.len());
+        assert!(lines[3].starts_with("Midfield,0,0,2,"), "{}", lines[3]);
+    }
+
+    #[test]
+    fn narrative_mentions_final_score_and_a_key_moment() {
+        let config = SimulationConfig::default().with_scripted_events([(
+            77 * 60,
+            Event::ScoreEvent {
+                team: Team::Opposition,
+                kind: ScoreKind::Try,
+            },
+        )]);
+        let summary = simulate_match(close_game_late(), &config, |_, _| {});
+        let score = summary.final_state.score;
+        assert!(!summary.key_moments.is_empty());
+
+        let english = summary.to_narrative(Locale::English);
+        assert!(
+            english.contains(&format!("{}–{}", score.own, score.opposition)),
+            "{english}"
+        );
+        assert!(english.contains("Key moments: "), "{english}");
+        assert!(english.contains("a try conceded"), "{english}");
+        let urgent = summary
+            .highest_urgency_decision
+            .as_ref()
+            .expect("判断が1つ以上ある試合");
+        assert!(
+            english.contains(&format!(
+                "the team chose {}.",
+                english_decision_name(&urgent.decision)
+            )),
+            "{english}"
+        );
+        for debug_name in ["PassSpread", "Kick {", "Lineout {", "DropGoal"] {
+            assert!(!english.contains(debug_name), "{english}");
+        }
+
+        let japanese = summary.to_narrative(Locale::Japanese);
+        assert!(
+            japanese.contains(&format!("{}対{}", score.own, score.opposition)),
+            "{japanese}"
+        );
+        assert!(japanese.contains("主な場面は"), "{japanese}");
+    }
+
+    #[test]
+    fn batch_report_csv_writes_header_and_example_rows() {
+        let states = [close_game_late(), rainy_red_zone()];
+        let mut buffer = Vec::new();
+        batch_report_csv(&states, &mut buffer).expect("バッファへの書き出しは失敗しない");
+        let csv = String::from_utf8(buffer).expect("UTF-8で書き出される");
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "own_score,opposition_score,position,weather,decision,reason,top1,top1_score,top2,top2_score"
+        );
+        assert_eq!(lines.len(), 1 + states.len());
+        let engine = DecisionEngine::default();
+        for (line, state) in lines[1..].iter().zip(&states) {
+            let columns: Vec<&str> = line.split(',').collect();
+            assert_eq!(columns.len(), 10, "{line}");
+            assert_eq!(columns[0], state.score.own.to_string());
+            assert_eq!(columns[1], state.score.opposition.to_string());
+            assert_eq!(columns[2], format!("{:?}", state.position));
+            assert_eq!(columns[3], format!("{:?}", state.weather));
+            let (decision, reason) = engine.decide_with_reason(state);
+            assert_eq!(columns[4], decision.to_string());
+            assert_eq!(columns[5], reason);
+            let top1: f32 = columns[7].parse().expect("評価は数値");
+            let top2: f32 = columns[9].parse().expect("評価は数値");
+            assert!(top1 >= top2);
+        }
+        assert!(lines[2].starts_with("14,10,Opposition22,"));
+    }
+
+    #[test]
+    fn maul_needs_minimum_support_even_with_forwards_ready() {
+        let short = Teammates {
             backs_ready: true,
             forwards_ready: true,
-            support_count: 5,
-        },
-    };
+            support_count: MAUL_MIN_SUPPORT - 1,
+        };
+        assert!(!short.support_adequate_for(&TacticalDecision::Maul));
+        assert!(!short.support_adequate_for(&TacticalDecision::Lineout {
+            throw_to: LineoutTarget::Maul
+        }));
+        let enough = Teammates {
+            support_count: MAUL_MIN_SUPPORT,
+            ..short.clone()
+        };
+        assert!(enough.support_adequate_for(&TacticalDecision::Maul));
 
-    analyze_game_state(&state1).await;
-    let decision1 = make_complex_decision(&state1).await;
-    println!("\n✨ 最終判断: {}", decision1);
+        // ラインアウトでもサポートが足りなければモールを選ばない
+        let mut state = lineout_state();
+        state.teammates = short;
+        assert_ne!(
+            DecisionEngine::default().decide(&state),
+            TacticalDecision::Lineout {
+                throw_to: LineoutTarget::Maul
+            }
+        );
+        state.teammates = enough;
+        assert_eq!(
+            DecisionEngine::default().decide(&state),
+            TacticalDecision::Lineout {
+                throw_to: LineoutTarget::Maul
+            }
+        );
+    }
 
-    println!("\n{}", "=".repeat(60));
+    #[test]
+    fn session_step_back_restores_the_stored_phase_snapshot() {
+        let config = SimulationConfig::default();
+        let mut session = SimulationSession::new(neutral_state(), &config);
+        let mut snapshots = vec![format!("{:?}", session.state())];
+        for _ in 0..5 {
+            assert!(session.step());
+            snapshots.push(format!("{:?}", session.state()));
+        }
+        assert_eq!(session.phase(), 5);
 
-    // シナリオ2: 悪天候、得点圏内
-    println!("\n【シナリオ2】雨天、敵陣22m内でチャンス");
-    let state2 = GameState {
-        rules: GameRules::Fifteens,
-        elapsed_time_secs: 35 * 60,
-        score: Score {
-            own: 14,
+        assert!(session.step_back());
+        assert!(session.step_back());
+        assert_eq!(session.phase(), 3);
+        assert_eq!(format!("{:?}", session.state()), snapshots[3]);
+
+        // 再び進めても同じ流れになる
+        assert!(session.step());
+        assert_eq!(format!("{:?}", session.state()), snapshots[4]);
+        assert!(session.goto(0));
+        assert_eq!(format!("{:?}", session.state()), snapshots[0]);
+        assert!(session.goto(5));
+        assert_eq!(format!("{:?}", session.state()), snapshots[5]);
+
+        // 試合終了まで進めると、simulate_matchと同じ結果になる
+        assert!(!session.goto(usize::MAX));
+        let full = simulate_match(neutral_state(), &SimulationConfig::default(), |_, _| {});
+        assert_eq!(session.summary().decisions, full.decisions);
+        assert_eq!(
+            format!("{:?}", session.state()),
+            format!("{:?}", full.final_state)
+        );
+    }
+
+    #[test]
+    fn exhausted_kicker_finds_touch_less_often_than_fresh_one() {
+        let mut fresh = neutral_state();
+        fresh.fatigue.backs = 0.0;
+        let mut exhausted = fresh.clone();
+        exhausted.fatigue.backs = 1.0;
+
+        let fresh_touch = estimate_kick_accuracy(KickType::Touch, &fresh);
+        let tired_touch = estimate_kick_accuracy(KickType::Touch, &exhausted);
+        assert!((fresh_touch - 1.0).abs() < 1e-6);
+        assert!((tired_touch - 0.4).abs() < 1e-6);
+
+        // 精度を求めるキックほど疲労で大きく落ちる
+        let tired_punt = estimate_kick_accuracy(KickType::HighPunt, &exhausted);
+        assert!(fresh_touch - tired_touch > 1.0 - tired_punt);
+        assert!(
+            estimate_kick_value(KickType::Touch, &exhausted)
+                < estimate_kick_value(KickType::Touch, &fresh)
+        );
+        let touch = TacticalDecision::Kick {
+            kick_type: KickType::Touch,
+        };
+        assert!(touch.success_probability(&exhausted) < touch.success_probability(&fresh));
+    }
+
+    #[test]
+    fn scores_add_component_wise() {
+        let mut total = Score {
+            own: 7,
+            opposition: 3,
+        } + Score {
+            own: 5,
             opposition: 10,
-        },
-        position: FieldPosition::Opposition22,
-        weather: Weather::Rainy,
-        wind: Wind {
-            speed: 8.0,
-            direction: 180.0,
-        },
-        fatigue: TeamFatigue {
-            forwards: 0.40,
-            backs: 0.35,
-        },
-        consecutive_phases: 12,
-        penalties_conceded: 3,
-        yellow_cards: 0,
-        defense: DefenseLine {
-            pressure: false,
-            gap_on_left: true,
-            gap_on_right: false,
-            alignment: 0.6,
-        },
-        teammates: Teammates {
-            backs_ready: true,
-            forwards_ready: true,
-            support_count: 7,
-        },
-    };
+        };
+        assert_eq!(
+            total,
+            Score {
+                own: 12,
+                opposition: 13,
+            }
+        );
+        total += Score {
+            own: 3,
+            opposition: 0,
+        };
+        assert_eq!(
+            total,
+            Score {
+                own: 15,
+                opposition: 13,
+            }
+        );
+    }
 
-    analyze_game_state(&state2).await;
-    let decision2 = make_complex_decision(&state2).await;
-    println!("\n✨ 最終判断: {}", decision2);
+    #[test]
+    fn leader_reports_lead_deficit_and_tie() {
+        let score = |own, opposition| Score { own, opposition };
+        assert_eq!(score(21, 17).leader(), Some(Team::Own));
+        assert_eq!(score(10, 24).leader(), Some(Team::Opposition));
+        assert_eq!(score(13, 13).leader(), None);
+    }
 
-    println!("\n{}", "=".repeat(60));
-    println!("\n✅ シミュレーション完了！");
-    println!("\n💡 このシミュレーションは、複数の変数を考慮した");
-    println!("   現実的な意思決定プロセスを示しています。");
+    #[test]
+    fn panic_mode_never_kicks_possession_away_when_down_four_late() {
+        let engine = DecisionEngine {
+            panic_mode: Some(PanicMode::default()),
+            ..DecisionEngine::default()
+        };
+        let without_panic = DecisionEngine::default();
+        let mut kicks_without_panic = 0;
+        for mut state in random_states(500) {
+            state.score = Score {
+                own: 20,
+                opposition: 24,
+            };
+            state.elapsed_time_secs = state.rules.match_duration_secs() - 120;
+            assert!(engine.panic_mode.unwrap().is_active(&state));
+            let decision = engine.decide(&state);
+            assert!(
+                !matches!(decision, TacticalDecision::Kick { .. }),
+                "{decision:?} @ {:?}",
+                state.position
+            );
+            if matches!(without_panic.decide(&state), TacticalDecision::Kick { .. }) {
+                kicks_without_panic += 1;
+            }
+        }
+        assert!(kicks_without_panic > 0);
+
+        // キック以外がすべて禁止されていれば、パニックモードでもキックを選ぶ
+        let kicks_only = DecisionEngine::default()
+            .forbid([
+                DecisionKind::Crash,
+                DecisionKind::QuickTap,
+                DecisionKind::Maul,
+                DecisionKind::DropGoal,
+                DecisionKind::PassSpread,
+                DecisionKind::Scrum,
+                DecisionKind::Lineout,
+            ])
+            .expect("キックが残るため禁止できる");
+        for mut state in random_states(100) {
+            state.score = Score {
+                own: 20,
+                opposition: 24,
+            };
+            state.elapsed_time_secs = state.rules.match_duration_secs() - 120;
+            assert!(matches!(
+                kicks_only.decide(&state),
+                TacticalDecision::Kick { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn left_side_pressure_with_right_gap_attacks_right() {
+        let mut state = neutral_state();
+        state.defense.pressure_side = Some(Direction::Left);
+        state.defense.gap_on_right = true;
+        assert_eq!(state.defense.optimal_direction(), Some(Direction::Right));
+
+        // 両側にギャップがあっても、プレッシャーの反対側を攻める
+        state.defense.gap_on_left = true;
+        assert_eq!(state.defense.optimal_direction(), Some(Direction::Right));
+        assert!(matches!(
+            DecisionEngine::default().decide(&state),
+            TacticalDecision::PassSpread {
+                direction: Direction::Right,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn default_config_reproduces_baseline_simulation() {
+        let explicit = SimulationConfig::default()
+            .with_engine(DecisionEngine::default())
+            .with_defensive_phase_secs(90)
+            .with_seed(2024)
+            .with_weather_interval_secs(None)
+            .with_card_escalation(Some(CardEscalation::default()));
+        #[cfg(feature = "async")]
+        let explicit = explicit.with_timing(Timing::default());
+        let baseline = simulate_match(neutral_state(), &SimulationConfig::default(), |_, _| {});
+        let rebuilt = simulate_match(neutral_state(), &explicit, |_, _| {});
+        assert_eq!(baseline.decisions, rebuilt.decisions);
+        assert_eq!(
+            format!("{:?}", baseline.final_state),
+            format!("{:?}", rebuilt.final_state)
+        );
+
+        // 既定の設定での基準となる結果（シミュレーションの挙動を変えたら更新する）
+        assert_eq!(
+            baseline.final_state.score,
+            Score {
+                own: 17,
+                opposition: 31,
+            }
+        );
+        assert_eq!(baseline.decisions.len(), 271);
+        assert!(!baseline.stopped_early && !baseline.cancelled);
+    }
+
+    #[test]
+    fn replay_folds_score_and_position_events() {
+        let initial = neutral_state();
+        let state = GameState::replay(
+            initial.clone(),
+            &[
+                Event::PhaseStarted { at_secs: 600 },
+                Event::PositionChanged {
+                    position: FieldPosition::Opposition22,
+                },
+                Event::ScoreEvent {
+                    team: Team::Own,
+                    kind: ScoreKind::Try,
+                },
+                Event::CardIssued {
+                    team: Team::Opposition,
+                    card: Card::Yellow,
+                },
+            ],
+        );
+        assert_eq!(
+            state.score,
+            Score {
+                own: initial.score.own + 5,
+                opposition: initial.score.opposition,
+            }
+        );
+        assert_eq!(state.position, FieldPosition::Opposition22);
+        assert_eq!(state.elapsed_time_secs, 600);
+        assert_eq!(state.consecutive_phases, 0);
+        assert_eq!(
+            state.opposition_yellow_cards,
+            initial.opposition_yellow_cards + 1
+        );
+    }
+
+    #[test]
+    fn stormy_rain_with_strong_crosswind_is_near_maximum_difficulty() {
+        let crosswind = Wind {
+            speed: 20.0,
+            direction: 90.0,
+            gust: 0.0,
+        };
+        assert!(handling_difficulty(Weather::StormyRain, &crosswind) > 0.99);
+
+        // 状態からは攻撃方向に対する横風で評価する
+        let mut state = neutral_state();
+        state.weather = Weather::Sunny;
+        state.wind = crosswind;
+        state.attacking_bearing = 90.0;
+        assert!(state.handling_difficulty() < 0.01);
+        state.attacking_bearing = 0.0;
+        assert!(state.handling_difficulty() > 0.99);
+    }
+
+    #[test]
+    fn sunny_and_calm_is_near_zero_difficulty() {
+        let calm = Wind {
+            speed: 0.0,
+            direction: 0.0,
+            gust: 0.0,
+        };
+        assert!(handling_difficulty(Weather::Sunny, &calm) < 0.01);
+
+        // 乾いていても横風が強ければ難しくなる
+        let windy_dry = handling_difficulty(
+            Weather::Sunny,
+            &Wind {
+                speed: 10.0,
+                direction: 90.0,
+                gust: 0.0,
+            },
+        );
+        assert!(windy_dry > handling_difficulty(Weather::Sunny, &calm));
+        assert!(handling_difficulty(Weather::Rainy, &calm) > 0.0);
+    }
+
+    #[test]
+    fn dry_day_with_strong_crosswind_switches_to_forward_play() {
+        let engine = DecisionEngine::default();
+        let mut state = neutral_state();
+        state.defense.gap_on_left = true;
+        state.teammates.backs_ready = true;
+        state.teammates.forwards_ready = true;
+        assert!(matches!(
+            engine.decide(&state),
+            TacticalDecision::PassSpread { .. }
+        ));
+
+        // 乾いた晴天でも、攻撃方向に強い横風が吹けば雨天と同じくフォワードで前進する
+        state.wind = Wind {
+            speed: 10.0,
+            direction: 90.0,
+            gust: 0.0,
+        };
+        assert_eq!(state.weather, Weather::Sunny);
+        assert!(state.handling_difficulty() >= POOR_HANDLING_DIFFICULTY);
+        assert_eq!(engine.decide(&state), TacticalDecision::Crash);
+        let first = engine
+            .audit(&state)
+            .into_iter()
+            .find(|eval| eval.matched)
+            .expect("Otherwiseは常に当てはまる");
+        assert!(matches!(
+            first.condition,
+            BranchCondition::PoorHandling { .. }
+        ));
+
+        // 無風の雨天はちょうど閾値に達する
+        let calm_rain = GameState {
+            weather: Weather::Rainy,
+            wind: neutral_state().wind,
+            ..state
+        };
+        assert!(calm_rain.handling_difficulty() >= POOR_HANDLING_DIFFICULTY);
+        assert_eq!(engine.decide(&calm_rain), TacticalDecision::Crash);
+    }
+
+    #[test]
+    fn requires_set_piece_classifies_every_decision() {
+        let mut expected = vec![
+            (TacticalDecision::Scrum, true),
+            (TacticalDecision::QuickTap, true),
+            (TacticalDecision::Crash, false),
+            (TacticalDecision::Maul, false),
+            (TacticalDecision::DropGoal, false),
+        ];
+        for throw_to in [
+            LineoutTarget::Front,
+            LineoutTarget::Middle,
+            LineoutTarget::Back,
+            LineoutTarget::Maul,
+        ] {
+            expected.push((TacticalDecision::Lineout { throw_to }, true));
+        }
+        for direction in [Direction::Left, Direction::Right, Direction::Center] {
+            for rushed in [false, true] {
+                expected.push((TacticalDecision::PassSpread { direction, rushed }, false));
+            }
+        }
+        for kick_type in [
+            KickType::HighPunt,
+            KickType::Touch,
+            KickType::Grubber,
+            KickType::Crossfield,
+            KickType::Bomb,
+        ] {
+            expected.push((TacticalDecision::Kick { kick_type }, false));
+        }
+
+        for (decision, set_piece) in expected {
+            assert_eq!(decision.requires_set_piece(), set_piece, "{decision:?}");
+        }
+    }
+
+    #[test]
+    fn hysteresis_stops_decision_flapping_on_borderline_jitter() {
+        let engine = DecisionEngine::default();
+        let scored = |state: &GameState, decision: &TacticalDecision| {
+            engine
+                .weighted_scores(state)
+                .iter()
+                .any(|(candidate, _)| candidate == decision)
+        };
+        // 前衛の疲労度がわずかに揺れるだけで判断が入れ替わる境目の状況を探す
+        let (low, high) = random_states(500)
+            .into_iter()
+            .find_map(|state| {
+                (0..100).find_map(|step| {
+                    let mut low = state.clone();
+                    low.fatigue.forwards = step as f32 / 100.0;
+                    let mut high = low.clone();
+                    high.fatigue.forwards += 0.01;
+                    let (a, b) = (engine.decide(&low), engine.decide(&high));
+                    (a != b
+                        && !engine.decided_by_safety_rule(&low)
+                        && !engine.decided_by_safety_rule(&high)
+                        && scored(&low, &b)
+                        && scored(&high, &a))
+                    .then_some((low, high))
+                })
+            })
+            .expect("境目の状況が見つかる");
+        let jitter: Vec<&GameState> = (0..20)
+            .map(|i| if i % 2 == 0 { &low } else { &high })
+            .collect();
+        let changes = |decisions: &[TacticalDecision]| {
+            decisions
+                .windows(2)
+                .filter(|pair| pair[0] != pair[1])
+                .count()
+        };
+
+        let plain: Vec<TacticalDecision> = jitter.iter().map(|s| engine.decide(s)).collect();
+        assert_eq!(changes(&plain), jitter.len() - 1);
+
+        let mut sticky = DecisionEngine {
+            hysteresis: 0.5,
+            ..DecisionEngine::default()
+        };
+        let held: Vec<TacticalDecision> = jitter.iter().map(|s| sticky.decide_sticky(s)).collect();
+        assert_eq!(changes(&held), 0, "{held:?}");
+        assert_eq!(held[0], plain[0]);
+    }
+
+    #[test]
+    fn variation_occasionally_picks_a_near_optimal_runner_up() {
+        let state = neutral_state();
+        let engine = DecisionEngine::default();
+        let decision = engine.decide(&state);
+        assert!(!engine.decided_by_safety_rule(&state));
+        let mut rng = SimRng::new(11);
+        for _ in 0..50 {
+            assert_eq!(engine.decide_varied(&state, &mut rng), decision);
+        }
+
+        let varied = DecisionEngine {
+            variation: 0.3,
+            ..DecisionEngine::default()
+        };
+        let scores = varied.weighted_scores(&state);
+        let top = scores
+            .iter()
+            .map(|(_, score)| *score)
+            .fold(f32::MIN, f32::max);
+        let score_of = |target: &TacticalDecision| {
+            scores
+                .iter()
+                .find(|(candidate, _)| candidate == target)
+                .map(|(_, score)| *score)
+                .expect("候補に含まれる")
+        };
+        let run = |seed: u64| {
+            let mut rng = SimRng::new(seed);
+            (0..50)
+                .map(|_| varied.decide_varied(&state, &mut rng))
+                .collect::<Vec<_>>()
+        };
+        let picks = run(11);
+        assert_eq!(picks, run(11));
+        let runner_ups: Vec<&TacticalDecision> =
+            picks.iter().filter(|pick| **pick != decision).collect();
+        assert!(!runner_ups.is_empty(), "{picks:?}");
+        assert!(runner_ups.len() < picks.len() / 2, "{picks:?}");
+        for pick in runner_ups {
+            assert!(score_of(pick) >= top - VARIATION_BAND, "{pick:?}");
+        }
+    }
+
+    #[test]
+    fn variation_never_brings_back_a_guarded_kick() {
+        // 自陣でカウンターの脅威が大きいと、フィールド内へのキックは保持に置き換わる
+        let varied = DecisionEngine {
+            variation: 1.0,
+            ..DecisionEngine::default()
+        };
+        let mut rng = SimRng::new(5);
+        for mut state in random_states(3000) {
+            state.position = FieldPosition::OwnHalf;
+            state.opposition_counterattack_threat = 0.9;
+            for _ in 0..5 {
+                let pick = varied.decide_varied(&state, &mut rng);
+                assert!(
+                    !matches!(
+                        pick,
+                        TacticalDecision::Kick { kick_type } if kick_type != KickType::Touch
+                    ),
+                    "{pick:?} {state:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn net_meters_sum_phase_advances_and_attribute_kicks_and_carries() {
+        // シード2024で自陣22mから6分間だけ進める。流れは固定で:
+        // タッチキック（自陣22m→中盤）、守備で後退（中盤→自陣）、奪い返して
+        // クラッシュで前進（自陣→中盤）、ボムを再獲得（中盤→敵陣）、
+        // クラッシュで前進（敵陣→敵陣22m）
+        let config =
+            SimulationConfig::default().with_stop_when(|state| state.elapsed_time_secs >= 36 * 60);
+        let mut initial = neutral_state();
+        initial.position = FieldPosition::Own22;
+        initial.defense.pressure_side = Some(Direction::Center);
+        let summary = simulate_match(initial, &config, |_, _| {});
+
+        assert_eq!(summary.final_state.score, neutral_state().score);
+        assert_eq!(summary.final_state.position, FieldPosition::Opposition22);
+        let kicks: Vec<_> = summary
+            .decisions
+            .iter()
+            .filter_map(|decision| match decision {
+                TacticalDecision::Kick { kick_type } => Some(*kick_type),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(kicks, [KickType::Touch, KickType::Bomb]);
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+        // キック: 11→50 と 50→69
+        assert!(close(summary.meters_kicked, 58.0));
+        // キャリー: 31→50 と 69→89（前進しなかったクラッシュは0m）
+        assert!(close(summary.meters_carried, 39.0));
+        // 守備での後退（50→31）も差し引く
+        assert!(close(summary.net_meters_gained, 58.0 + 39.0 - 19.0));
+    }
+
+    #[test]
+    fn seven_point_tries_without_conversions_change_chasing_math() {
+        let mut registry = RuleSetRegistry::new();
+        let power_try = registry
+            .register(RuleSet {
+                name: "power-try".to_string(),
+                duration_secs: 80 * 60,
+                fatigue_rate: 0.0125,
+                players: 15,
+                substitutions: 8,
+                scoring: ScoringValues {
+                    try_points: 7,
+                    conversion_points: 0,
+                    penalty_points: 3,
+                    drop_points: 3,
+                },
+            })
+            .expect("予約されていない名前");
+        // トライ1本の価値は既定のルールで6点（5点＋2点×50%）、power-tryでは確実に7点
+        let standard = GameState {
+            elapsed_time_secs: 77 * 60,
+            score: Score {
+                own: 14,
+                opposition: 20,
+            },
+            position: FieldPosition::Opposition22,
+            ..neutral_state()
+        };
+        let variant = GameState {
+            rules: power_try,
+            ..standard.clone()
+        };
+
+        assert!(
+            TacticalDecision::Crash.expected_points(&variant)
+                > TacticalDecision::Crash.expected_points(&standard)
+        );
+        assert!(
+            (TacticalDecision::DropGoal.expected_points(&variant)
+                - TacticalDecision::DropGoal.expected_points(&standard))
+            .abs()
+                < 1e-6,
+            "ドロップゴールの価値はどちらのルールでも3点"
+        );
+        // 7点トライでも6点差はトライ1本で足りる
+        assert_eq!(standard.scores_needed(), ScorePlan::OneTry);
+        assert_eq!(variant.scores_needed(), ScorePlan::OneTry);
+
+        // コンバージョンのない5点トライでは、6点差はトライとペナルティゴールが必要
+        let no_conversion = registry
+            .register(RuleSet {
+                name: "no-conversion".to_string(),
+                duration_secs: 80 * 60,
+                fatigue_rate: 0.0125,
+                players: 15,
+                substitutions: 8,
+                scoring: ScoringValues {
+                    try_points: 5,
+                    conversion_points: 0,
+                    penalty_points: 3,
+                    drop_points: 3,
+                },
+            })
+            .expect("予約されていない名前");
+        let ten_minutes_left = GameState {
+            elapsed_time_secs: 70 * 60,
+            ..standard.clone()
+        };
+        let short_try = GameState {
+            rules: no_conversion,
+            ..ten_minutes_left.clone()
+        };
+        assert_eq!(
+            ten_minutes_left
+                .score
+                .scores_needed_with(10 * 60, &ScoringValues::default()),
+            ScorePlan::OneTry
+        );
+        assert_eq!(
+            short_try
+                .score
+                .scores_needed_with(10 * 60, &short_try.rules.scoring()),
+            ScorePlan::TryAndPenalty
+        );
+        assert_eq!(ten_minutes_left.scores_needed(), ScorePlan::OneTry);
+        assert_eq!(short_try.scores_needed(), ScorePlan::TryAndPenalty);
+        // 2回の得点が必要でもドロップゴールには逃げず、敵陣22mで攻め続ける
+        let engine = DecisionEngine::default();
+        assert_eq!(engine.decide(&short_try), TacticalDecision::Crash);
+        assert_eq!(engine.decide(&ten_minutes_left), TacticalDecision::Crash);
+    }
+
+    #[test]
+    fn high_counterattack_threat_in_own_half_turns_high_punt_into_retention() {
+        let engine = DecisionEngine::default();
+        let high_punt = TacticalDecision::Kick {
+            kick_type: KickType::HighPunt,
+        };
+        let mut state = random_states(2000)
+            .into_iter()
+            .map(|mut state| {
+                state.position = FieldPosition::OwnHalf;
+                state.opposition_counterattack_threat = 0.0;
+                state
+            })
+            .find(|state| engine.decide(state) == high_punt)
+            .expect("自陣でハイパントを選ぶ状況がある");
+
+        state.opposition_counterattack_threat = 0.9;
+        assert!(state.opposition_counterattack_threat > engine.counterattack_threshold);
+        let decision = engine.decide(&state);
+        assert!(
+            matches!(decision, TacticalDecision::Maul | TacticalDecision::Crash),
+            "{decision:?}"
+        );
+    }
+
+    /// 現在のスレッドで計測中のヒープ確保だけを数えるアロケーター
+    ///
+    /// テストは並列に走るため、計測の有無と回数はスレッドローカルに持ちます。
+    struct CountingAllocator;
+
+    std::thread_local! {
+        static COUNTING: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+        static ALLOCATIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    impl CountingAllocator {
+        fn record() {
+            if COUNTING
+                .try_with(|counting| counting.get())
+                .unwrap_or(false)
+            {
+                let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            }
+        }
+    }
+
+    unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+            Self::record();
+            unsafe { std::alloc::System.alloc(layout) }
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: std::alloc::Layout) -> *mut u8 {
+            Self::record();
+            unsafe { std::alloc::System.alloc_zeroed(layout) }
+        }
+
+        unsafe fn realloc(
+            &self,
+            ptr: *mut u8,
+            layout: std::alloc::Layout,
+            new_size: usize,
+        ) -> *mut u8 {
+            Self::record();
+            unsafe { std::alloc::System.realloc(ptr, layout, new_size) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+            unsafe { std::alloc::System.dealloc(ptr, layout) }
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// `f`の実行中に現在のスレッドで行われたヒープ確保の回数
+    fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+        ALLOCATIONS.with(|count| count.set(0));
+        COUNTING.with(|counting| counting.set(true));
+        let result = f();
+        COUNTING.with(|counting| counting.set(false));
+        (result, ALLOCATIONS.with(|count| count.get()))
+    }
+
+    #[test]
+    fn decide_makes_no_heap_allocations_over_fixtures() {
+        let engine = DecisionEngine::default();
+        let mut states: Vec<GameState> = all_fixtures().map(|(_, state)| state).collect();
+        states.extend(random_states(200));
+        for state in &states {
+            let (decision, allocations) = count_allocations(|| engine.decide(state));
+            assert_eq!(allocations, 0, "{decision:?} @ {state:?}");
+        }
+
+        // 計測できていることの確認
+        let (_, allocations) = count_allocations(|| vec![0u8; 16]);
+        assert_eq!(allocations, 1);
+    }
+
+    #[test]
+    fn first_decide_on_a_fresh_engine_needs_no_warm_up() {
+        // 作成直後のエンジンでも、初回の判断で初期化の処理（ヒープ確保）が走らない
+        for profile in ENGINE_PROFILES {
+            for (name, state) in all_fixtures() {
+                let engine = DecisionEngine::profile(profile).expect("既知のプロファイル");
+                let (first, allocations) = count_allocations(|| engine.decide(&state));
+                assert_eq!(allocations, 0, "{profile} / {name}");
+                assert_eq!(first, engine.decide(&state), "{profile} / {name}");
+            }
+        }
+    }
+
+    #[test]
+    fn warm_up_leaves_decisions_unchanged() {
+        let states: Vec<GameState> = all_fixtures()
+            .map(|(_, state)| state)
+            .chain(random_states(200))
+            .collect();
+        for profile in ENGINE_PROFILES {
+            let cold = DecisionEngine::profile(profile).expect("既知のプロファイル");
+            let warmed = DecisionEngine::profile(profile).expect("既知のプロファイル");
+            warmed.warm();
+            for state in &states {
+                assert_eq!(warmed.decide(state), cold.decide(state), "{profile}");
+            }
+        }
+    }
+
+    #[test]
+    fn compare_subcommand_prints_both_profiles_decisions_for_a_preset() {
+        let args: Vec<String> = [
+            "compare",
+            "rainy_red_zone",
+            "--a",
+            "conservative",
+            "--b",
+            "expansive",
+        ]
+        .map(String::from)
+        .to_vec();
+        let mut output = Vec::new();
+        assert_eq!(compare_command(&args, &mut output), Some(Ok(())));
+        let output = String::from_utf8(output).expect("UTF-8の出力");
+
+        let state = rainy_red_zone();
+        assert!(output.starts_with("【rainy_red_zone】"));
+        for profile in ["conservative", "expansive"] {
+            let decision = DecisionEngine::profile(profile)
+                .expect("既知のプロファイル")
+                .decide(&state);
+            assert!(output.contains(profile));
+            assert!(
+                output.contains(&decision.to_string()),
+                "{decision} / {output}"
+            );
+        }
+
+        let unknown: Vec<String> = ["compare", "rainy_red_zone", "--a", "reckless"]
+            .map(String::from)
+            .to_vec();
+        assert!(matches!(
+            compare_command(&unknown, Vec::new()),
+            Some(Err(_))
+        ));
+        assert_eq!(compare_command(&["--csv".to_string()], Vec::new()), None);
+    }
+
+    #[test]
+    fn lerp_endpoints_match_each_snapshot_for_continuous_fields() {
+        let start = neutral_state();
+        let end = GameState {
+            elapsed_time_secs: 41 * 60,
+            score: Score {
+                own: 17,
+                opposition: 10,
+            },
+            position: FieldPosition::Opposition22,
+            wind: Wind {
+                speed: 12.0,
+                direction: 350.0,
+                gust: 16.0,
+            },
+            fatigue: TeamFatigue {
+                forwards: 0.7,
+                backs: 0.55,
+            },
+            opposition_fatigue: TeamFatigue {
+                forwards: 0.65,
+                backs: 0.45,
+            },
+            attacking_bearing: 90.0,
+            opposition_counterattack_threat: 0.8,
+            ..neutral_state()
+        };
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        let continuous_match = |state: &GameState, expected: &GameState| {
+            state.elapsed_time_secs == expected.elapsed_time_secs
+                && state.position == expected.position
+                && close(state.fatigue.forwards, expected.fatigue.forwards)
+                && close(state.fatigue.backs, expected.fatigue.backs)
+                && close(
+                    state.opposition_fatigue.forwards,
+                    expected.opposition_fatigue.forwards,
+                )
+                && close(
+                    state.opposition_fatigue.backs,
+                    expected.opposition_fatigue.backs,
+                )
+                && close(state.wind.speed, expected.wind.speed)
+                && close(state.wind.direction, expected.wind.direction)
+                && close(state.wind.gust, expected.wind.gust)
+                && close(state.attacking_bearing, expected.attacking_bearing)
+                && close(
+                    state.opposition_counterattack_threat,
+                    expected.opposition_counterattack_threat,
+                )
+                && close(state.defense.alignment, expected.defense.alignment)
+        };
+
+        assert_eq!(start.lerp(&end, 0.0), start);
+        assert!(continuous_match(&start.lerp(&end, 1.0), &end));
+        assert_eq!(start.lerp(&end, 1.0).score, end.score);
+        // 範囲外の`t`は丸める
+        assert_eq!(start.lerp(&end, -1.0), start);
+        assert!(continuous_match(&start.lerp(&end, 2.0), &end));
+
+        // スコアは中間で切り替わり、風向きは近い回り方向に補間する
+        assert_eq!(start.lerp(&end, 0.49).score, start.score);
+        assert_eq!(start.lerp(&end, 0.5).score, end.score);
+        assert!(close(start.lerp(&end, 0.5).wind.direction, 355.0));
+    }
+
+    #[test]
+    fn short_phases_resolve_in_proportion_to_their_length() {
+        // 基準以上の長さのフェーズは乱数を使わずに必ず結果が決まる
+        let mut rng = SimRng::new(7);
+        let untouched = rng.clone().next_u64();
+        assert!(phase_resolves(REFERENCE_PHASE_SECS, &mut rng));
+        assert_eq!(rng.next_u64(), untouched);
+
+        let rate = |phase_secs: u32| {
+            let mut rng = SimRng::new(7);
+            (0..10_000)
+                .filter(|_| phase_resolves(phase_secs, &mut rng))
+                .count() as f32
+                / 10_000.0
+        };
+        let crash = TacticalDecision::Crash.typical_phase_secs();
+        assert!((rate(crash) - crash as f32 / REFERENCE_PHASE_SECS as f32).abs() < 0.02);
+        assert!((rate(45) - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn zero_second_defensive_phases_still_reach_full_time() {
+        // 0秒の守備フェーズは時計が進まず終わらないため、1秒として扱う
+        let config = SimulationConfig::default().with_defensive_phase_secs(0);
+        assert_eq!(config.defensive_phase_secs, 1);
+
+        let start = GameState {
+            possession: Possession::Opposition,
+            ..neutral_state()
+        };
+        let summary = simulate_match(start.clone(), &config, |_, _| {});
+        assert_eq!(summary.final_state.time_remaining_secs(), 0);
+
+        // 公開フィールドを直接0にしても同じく試合終了まで進む
+        let config = SimulationConfig {
+            defensive_phase_secs: 0,
+            ..SimulationConfig::default()
+        };
+        let summary = simulate_match(start, &config, |_, _| {});
+        assert_eq!(summary.final_state.time_remaining_secs(), 0);
+    }
+
+    #[test]
+    fn set_piece_heavy_match_uses_more_clock_per_phase_than_crash_heavy() {
+        // 各フェーズの経過時間と判断を記録しながら、試合終了まで進める
+        let play = |engine: DecisionEngine| {
+            let config = SimulationConfig::default().with_engine(engine);
+            let mut session = SimulationSession::new(neutral_state(), &config);
+            let mut own_phases = Vec::new();
+            loop {
+                let before = session.state().elapsed_time_secs;
+                let decided = session.summary().decisions.len();
+                if !session.step() {
+                    break;
+                }
+                let after = session.state().elapsed_time_secs;
+                let full_time = session.state().rules.match_duration_secs();
+                match session.summary().decisions.get(decided) {
+                    Some(decision) => {
+                        assert_eq!(
+                            after,
+                            (before + decision.typical_phase_secs()).min(full_time)
+                        );
+                        own_phases.push(after - before);
+                    }
+                    None => {
+                        assert_eq!(after, (before + config.defensive_phase_secs).min(full_time))
+                    }
+                }
+            }
+            own_phases.iter().sum::<u32>() as f32 / own_phases.len() as f32
+        };
+        let only = |allowed: &[DecisionKind]| DecisionEngine {
+            forbidden: [
+                DecisionKind::PassSpread,
+                DecisionKind::Crash,
+                DecisionKind::QuickTap,
+                DecisionKind::Maul,
+                DecisionKind::DropGoal,
+                DecisionKind::Lineout,
+                DecisionKind::Scrum,
+            ]
+            .into_iter()
+            .chain(
+                [
+                    KickType::HighPunt,
+                    KickType::Touch,
+                    KickType::Grubber,
+                    KickType::Crossfield,
+                    KickType::Bomb,
+                ]
+                .map(DecisionKind::Kick),
+            )
+            .filter(|kind| !allowed.contains(kind))
+            .collect(),
+            ..DecisionEngine::default()
+        };
+
+        // エンジンはスクラムを選ばないため、組み直しに時間のかかるモールとラインアウトで代用する
+        let set_piece_heavy = play(only(&[DecisionKind::Maul, DecisionKind::Lineout]));
+        let crash_heavy = play(only(&[DecisionKind::Crash]));
+        assert!(
+            set_piece_heavy > crash_heavy,
+            "set piece={set_piece_heavy} crash={crash_heavy}"
+        );
+        assert!(
+            TacticalDecision::Scrum.typical_phase_secs()
+                > TacticalDecision::Crash.typical_phase_secs()
+        );
+    }
+
+    #[test]
+    fn sanity_check_flags_forbidden_everything_and_inverted_thresholds() {
+        assert_eq!(DecisionEngine::default().sanity_check(), Ok(()));
+        for profile in ENGINE_PROFILES {
+            let engine = DecisionEngine::profile(profile).expect("既知のプロファイル");
+            assert_eq!(engine.sanity_check(), Ok(()), "{profile}");
+        }
+
+        let kicks = KickType::ALL.map(DecisionKind::Kick);
+        let keeps_ball = [
+            DecisionKind::PassSpread,
+            DecisionKind::Crash,
+            DecisionKind::QuickTap,
+            DecisionKind::Maul,
+            DecisionKind::DropGoal,
+        ];
+        // `forbid`を通さずに設定した禁止はsanity_checkで検出する
+        let everything = DecisionEngine {
+            forbidden: kicks
+                .into_iter()
+                .chain(keeps_ball)
+                .chain([DecisionKind::Scrum, DecisionKind::Lineout])
+                .collect(),
+            ..DecisionEngine::default()
+        };
+        assert_eq!(
+            everything.sanity_check(),
+            Err(vec![ConfigWarning::AllDecisionsForbidden])
+        );
+        let no_candidate = DecisionEngine {
+            forbidden: keeps_ball
+                .into_iter()
+                .chain([DecisionKind::Kick(KickType::Touch)])
+                .collect(),
+            ..DecisionEngine::default()
+        };
+        assert!(
+            DecisionEngine::default()
+                .forbid(no_candidate.forbidden().iter().copied())
+                .is_err()
+        );
+        assert_eq!(
+            no_candidate.sanity_check(),
+            Err(vec![ConfigWarning::NoCandidateLeft])
+        );
+        let only_kicks = DecisionEngine::default()
+            .forbid(keeps_ball)
+            .expect("キックはすべて許可されている");
+        assert_eq!(only_kicks.sanity_check(), Ok(()));
+        let only_kicks_with_panic = DecisionEngine {
+            panic_mode: Some(PanicMode::default()),
+            ..only_kicks
+        };
+        assert_eq!(
+            only_kicks_with_panic.sanity_check(),
+            Err(vec![ConfigWarning::PanicModeCannotKeepBall])
+        );
+
+        let inverted = DecisionEngine {
+            phase_budget: PhaseBudget {
+                soft_limit: 12,
+                hard_limit: 8,
+            },
+            headwind_threshold: -3.0,
+            fatigue_imbalance_threshold: 1.5,
+            panic_mode: Some(PanicMode {
+                max_deficit: 0,
+                remaining_under_secs: 180,
+            }),
+            ..DecisionEngine::default()
+        };
+        assert_eq!(
+            inverted.sanity_check(),
+            Err(vec![
+                ConfigWarning::PhaseLimitsInverted {
+                    soft_limit: 12,
+                    hard_limit: 8,
+                },
+                ConfigWarning::NegativeThreshold {
+                    name: "headwind_threshold",
+                    value: -3.0,
+                },
+                ConfigWarning::ThresholdOutOfRange {
+                    name: "fatigue_imbalance_threshold",
+                    value: 1.5,
+                },
+                ConfigWarning::PanicModeNeverActive,
+            ])
+        );
+
+        // 無効化のための値は警告しない
+        let disabled = DecisionEngine {
+            hysteresis: -1.0,
+            counterattack_threshold: 1.5,
+            ..DecisionEngine::default()
+        };
+        assert_eq!(disabled.sanity_check(), Ok(()));
+    }
+
+    #[test]
+    fn changing_one_threshold_diverges_at_the_first_affected_phase() {
+        let baseline_config = SimulationConfig::default();
+        let tightened = DecisionEngine {
+            phase_budget: PhaseBudget {
+                soft_limit: 2,
+                hard_limit: 3,
+            },
+            ..DecisionEngine::default()
+        };
+        let changed_config = SimulationConfig::default().with_engine(tightened.clone());
+        let baseline = MatchReplay::record(neutral_state(), &baseline_config);
+        let changed = MatchReplay::record(neutral_state(), &changed_config);
+
+        // 同じシードなら、変更したエンジンが初めて違う判断を下すフェーズまでは一致する
+        let expected_phase = (0..baseline.phases.len())
+            .find(|&phase| {
+                let before = match phase {
+                    0 => &baseline.initial,
+                    _ => &baseline.phases[phase - 1].state,
+                };
+                baseline.phases[phase]
+                    .decision
+                    .is_some_and(|decision| tightened.decide(before) != decision)
+            })
+            .expect("上限を下げると判断が変わるフェーズがある");
+        assert!(expected_phase > 0);
+
+        let divergences = diff_replays(&baseline, &changed);
+        assert_eq!(
+            divergences.first(),
+            Some(&ReplayDivergence::Decision {
+                phase: expected_phase,
+                a: baseline.phases[expected_phase].decision,
+                b: changed.phases[expected_phase].decision,
+            })
+        );
+        assert!(divergences.len() > 1, "食い違いは以降のフェーズに連鎖する");
+        assert!(diff_replays(&baseline, &baseline).is_empty());
+    }
+
+    #[test]
+    fn partially_ready_backs_produce_a_rushed_pass_instead_of_a_kick() {
+        let mut state = neutral_state();
+        state.defense.gap_on_right = true;
+        state.teammates = Teammates {
+            backs_ready: false,
+            forwards_ready: false,
+            support_count: 2,
+        };
+        let decision = DecisionEngine::default().decide(&state);
+        assert_eq!(
+            decision,
+            TacticalDecision::PassSpread {
+                direction: Direction::Right,
+                rushed: true,
+            }
+        );
+
+        // 急ぎのパス展開は準備の整った展開より成功しにくい
+        let prepared = TacticalDecision::PassSpread {
+            direction: Direction::Right,
+            rushed: false,
+        };
+        let penalty = RUSHED_PASS_PENALTY * state.fatigue_level().performance_multiplier();
+        assert!(
+            (prepared.success_probability(&state) - decision.success_probability(&state) - penalty)
+                .abs()
+                < 1e-6
+        );
+
+        // サポートもいなければ展開を諦めてキックする
+        state.teammates.support_count = 0;
+        assert!(matches!(
+            DecisionEngine::default().decide(&state),
+            TacticalDecision::Kick { .. }
+        ));
+    }
+
+    #[test]
+    fn sevens_state_with_fifteen_player_squad_fails_validation() {
+        let state = GameState {
+            rules: GameRules::Sevens,
+            elapsed_time_secs: 5 * 60,
+            consecutive_phases: 2,
+            ..neutral_state()
+        };
+        let fifteens_squad = Squad::for_rules(GameRules::Fifteens);
+        assert_eq!(
+            state.validate(&fifteens_squad),
+            Err(vec![StateViolation::SquadSize {
+                expected: 7,
+                actual: 15,
+            }])
+        );
+        let sevens_squad = Squad::for_rules(GameRules::Sevens);
+        assert_eq!(state.validate(&sevens_squad), Ok(()));
+
+        // 15人制では問題のないサポート人数も、7人制では多すぎる
+        let crowded = GameState {
+            teammates: Teammates {
+                support_count: 8,
+                ..state.teammates.clone()
+            },
+            ..state
+        };
+        assert_eq!(
+            crowded.validate(&sevens_squad),
+            Err(vec![StateViolation::SupportExceedsPlayers {
+                support: 8,
+                max: 6
+            }])
+        );
+    }
 }
@@ -22,7 +22,48 @@ pub enum GameError {
     /// タイムアウトエラー
     Timeout { action: String, limit_secs: u64 },
     /// 判断エラー
-    DecisionError { reason: String },
+    DecisionError {
+        reason: String,
+        /// 判断エラーの原因となったエラー（非同期処理の失敗など）
+        ///
+        /// `GameError`を`Clone`のまま保つため、`Arc`で共有します。
+        source: Option<std::sync::Arc<dyn std::error::Error + Send + Sync>>,
+    },
+}
+
+impl GameError {
+    /// 原因となったエラーを保持する判断エラーを作成
+    pub fn decision_from(
+        reason: impl Into<String>,
+        cause: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        GameError::DecisionError {
+            reason: reason.into(),
+            source: Some(std::sync::Arc::from(cause.into())),
+        }
+    }
+
+    /// エラーへの対処のヒント
+    ///
+    /// `{:#}`で表示した場合は、通常のメッセージの後にこのヒントが続きます。
+    pub fn hint(&self) -> String {
+        match self {
+            GameError::Timeout { action, .. } if action == "wait_for_ball" => {
+                "制限時間を延ばすか、スクラムハーフ（上流の処理）が止まっていないか確認してください"
+                    .to_string()
+            }
+            GameError::Timeout { action, .. } => {
+                format!(
+                    "制限時間を延ばすか、{}が完了しているか確認してください",
+                    action
+                )
+            }
+            GameError::DecisionError { .. } => {
+                "判断に渡した状態（ディフェンスライン、チームメイト）が正しいか確認してください"
+                    .to_string()
+            }
+        }
+    }
 }
 
 impl std::fmt::Display for GameError {
@@ -31,14 +72,28 @@ impl std::fmt::Display for GameError {
             GameError::Timeout { action, limit_secs } => {
                 write!(f, "タイムアウト: {} (制限: {}秒)", action, limit_secs)
             }
-            GameError::DecisionError { reason } => {
+            GameError::DecisionError { reason, .. } => {
                 write!(f, "判断エラー: {}", reason)
             }
+        }?;
+        if f.alternate() {
+            write!(f, "\nヒント: {}", self.hint())?;
         }
+        Ok(())
     }
 }
 
-impl std::error::Error for GameError {}
+impl std::error::Error for GameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GameError::DecisionError {
+                source: Some(source),
+                ..
+            } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 /// ディフェンスラインの状態
 ///
@@ -120,6 +175,7 @@ pub struct Teammates {
 }
 
 /// 攻撃の判断結果
+#[must_use]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Decision {
     /// パス展開
@@ -140,6 +196,73 @@ impl std::fmt::Display for Decision {
     }
 }
 
+// =============================================================================
+// 演出のタイミング
+// =============================================================================
+
+/// 各ステップの待ち時間の設定
+///
+/// デモの演出として入れている`sleep`の長さをまとめたものです。
+/// `Timing::instant()`では全て0になり、実際には待ちません。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    /// スクラムハーフからのパスが届くまでの時間
+    pub ball: Duration,
+    /// ディフェンスラインの分析にかかる時間
+    pub defense: Duration,
+    /// 味方のポジショニング確認にかかる時間
+    pub teammates: Duration,
+    /// スクランブル時にバックスがポジションにつくまでの時間
+    pub backs_positioning: Duration,
+    /// スクランブル時にフォワードがポジションにつくまでの時間
+    pub forwards_positioning: Duration,
+    /// サイン1つにかかる時間
+    pub signal: Duration,
+    /// Async Closuresのデモで各フェーズにかかる時間
+    pub phase: Duration,
+}
+
+impl Timing {
+    /// デモ用の現実的な待ち時間（デフォルト）
+    pub fn realistic() -> Self {
+        Timing {
+            ball: Duration::from_secs(2),
+            defense: Duration::from_secs(1),
+            teammates: Duration::from_millis(800),
+            backs_positioning: Duration::from_millis(500),
+            forwards_positioning: Duration::from_millis(900),
+            signal: Duration::from_millis(500),
+            phase: Duration::from_millis(300),
+        }
+    }
+
+    /// 待ち時間なし
+    pub fn instant() -> Self {
+        Timing {
+            ball: Duration::ZERO,
+            defense: Duration::ZERO,
+            teammates: Duration::ZERO,
+            backs_positioning: Duration::ZERO,
+            forwards_positioning: Duration::ZERO,
+            signal: Duration::ZERO,
+            phase: Duration::ZERO,
+        }
+    }
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Timing::realistic()
+    }
+}
+
+/// 指定時間だけ待つ（0の場合はタイマーを使わずに即座に戻る）
+async fn pause(duration: Duration) {
+    if !duration.is_zero() {
+        sleep(duration).await;
+    }
+}
+
 // =============================================================================
 // 非同期関数（Rust 2024 RPIT活用）
 // =============================================================================
@@ -153,15 +276,15 @@ impl std::fmt::Display for Decision {
 /// # Examples
 ///
 /// ```no_run
-/// # use modern_rugby_2024::wait_for_ball;
+/// # use modern_rugby_2024::{Timing, wait_for_ball};
 /// # tokio_test::block_on(async {
-/// let ball = wait_for_ball().await;
+/// let ball = wait_for_ball(&Timing::instant()).await;
 /// assert_eq!(ball, "ボール受領");
 /// # });
 /// ```
-pub async fn wait_for_ball() -> String {
+pub async fn wait_for_ball(timing: &Timing) -> String {
     println!("🏉 スクラムハーフからのパスを待機...");
-    sleep(Duration::from_secs(2)).await;
+    pause(timing.ball).await;
     println!("✓ ボール受け取り完了");
     "ボール受領".to_string()
 }
@@ -175,15 +298,15 @@ pub async fn wait_for_ball() -> String {
 /// # Examples
 ///
 /// ```no_run
-/// # use modern_rugby_2024::read_defense;
+/// # use modern_rugby_2024::{Timing, read_defense};
 /// # tokio_test::block_on(async {
-/// let defense = read_defense().await;
+/// let defense = read_defense(&Timing::instant()).await;
 /// assert!(defense.has_gap());
 /// # });
 /// ```
-pub async fn read_defense() -> DefenseLine {
+pub async fn read_defense(timing: &Timing) -> DefenseLine {
     println!("👀 ディフェンスラインを読む...");
-    sleep(Duration::from_secs(1)).await;
+    pause(timing.defense).await;
 
     let defense = DefenseLine {
         pressure: false,
@@ -205,9 +328,9 @@ pub async fn read_defense() -> DefenseLine {
 /// # Returns
 ///
 /// チームメイトの準備状態
-pub async fn check_teammates() -> Teammates {
+pub async fn check_teammates(timing: &Timing) -> Teammates {
     println!("👥 味方のポジショニング確認...");
-    sleep(Duration::from_millis(800)).await;
+    pause(timing.teammates).await;
 
     let teammates = Teammates {
         backs_ready: true,
@@ -218,20 +341,113 @@ pub async fn check_teammates() -> Teammates {
     teammates
 }
 
+/// 制限時間内にポジションにつけたチームメイトを確認
+///
+/// バックスは`timing.backs_positioning`（デフォルト500ms）、フォワードは再配置に
+/// 時間がかかるため`timing.forwards_positioning`（デフォルト900ms）で準備が整います。
+/// 制限時間までに準備が整わなかったユニットは`false`になります。
+///
+/// # Arguments
+///
+/// * `deadline` - 確認を打ち切るまでの制限時間
+/// * `timing` - 各ユニットがポジションにつくまでの時間
+///
+/// # Returns
+///
+/// 制限時間時点でのチームメイトの準備状態
+pub async fn check_teammates_within(deadline: Duration, timing: &Timing) -> Teammates {
+    println!("👥 スクランブル中の味方のポジショニング確認...");
+
+    let (backs, forwards) = tokio::join!(
+        tokio::time::timeout(deadline, pause(timing.backs_positioning)),
+        tokio::time::timeout(deadline, pause(timing.forwards_positioning)),
+    );
+
+    let teammates = Teammates {
+        backs_ready: backs.is_ok(),
+        forwards_ready: forwards.is_ok(),
+    };
+
+    println!(
+        "✓ 確認終了: バックス{} / フォワード{}",
+        if teammates.backs_ready {
+            "準備完了"
+        } else {
+            "未準備"
+        },
+        if teammates.forwards_ready {
+            "準備完了"
+        } else {
+            "未準備"
+        }
+    );
+    teammates
+}
+
 /// バックスに展開のサインを送る
-pub async fn signal_backs() {
+pub async fn signal_backs(timing: &Timing) {
     println!("📢 バックスに展開のサイン...");
-    sleep(Duration::from_millis(500)).await;
+    pause(timing.signal).await;
     println!("✓ バックス準備完了");
 }
 
 /// フォワードにサポートのサインを送る
-pub async fn signal_forwards() {
+pub async fn signal_forwards(timing: &Timing) {
     println!("📢 フォワードにサポートのサイン...");
-    sleep(Duration::from_millis(500)).await;
+    pause(timing.signal).await;
     println!("✓ フォワード準備完了");
 }
 
+// =============================================================================
+// サインの同時実行数の制限
+// =============================================================================
+
+/// サイン出し（`signal_*`）の同時実行数を制限するリミッター
+///
+/// 内部で`tokio::sync::Semaphore`を共有するため、クローンしたリミッターを
+/// 複数の攻撃に渡すと、全体で同時に走るサインが`max_concurrent`個までに制限されます。
+/// 上限に達している間、次のサインは先に走っているサインが終わるまで待ちます。
+/// デフォルトの同時実行数は2（バックスとフォワードへのサインを同時に出せる数）です。
+#[derive(Debug, Clone)]
+pub struct SignalLimiter {
+    permits: std::sync::Arc<tokio::sync::Semaphore>,
+}
+
+impl Default for SignalLimiter {
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+impl SignalLimiter {
+    /// 同時実行数の上限を指定してリミッターを作成
+    pub fn new(max_concurrent: usize) -> Self {
+        SignalLimiter {
+            permits: std::sync::Arc::new(tokio::sync::Semaphore::new(max_concurrent)),
+        }
+    }
+
+    /// 上限の範囲内で任意のサインを実行する
+    pub async fn run<F: Future>(&self, signal: F) -> F::Output {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .expect("セマフォは閉じられない");
+        signal.await
+    }
+
+    /// 上限の範囲内でバックスにサインを送る
+    pub async fn signal_backs(&self, timing: &Timing) {
+        self.run(signal_backs(timing)).await
+    }
+
+    /// 上限の範囲内でフォワードにサインを送る
+    pub async fn signal_forwards(&self, timing: &Timing) {
+        self.run(signal_forwards(timing)).await
+    }
+}
+
 /// 状況を総合的に判断して最適な戦術を決定
 ///
 /// # Arguments
@@ -265,6 +481,200 @@ pub async fn make_decision(_ball: String, defense: DefenseLine, teammates: Teamm
     }
 }
 
+// =============================================================================
+// 差し替え可能な攻撃パイプライン
+// =============================================================================
+
+/// `AttackPipeline`の各ステップが返すFuture
+pub type PipelineFuture<'a, T> =
+    std::pin::Pin<Box<dyn std::future::Future<Output = T> + Send + 'a>>;
+
+/// 判断の前に行う情報収集のステップ
+///
+/// トラッキングAPIを呼び出すディフェンス分析など、各ステップの実装を
+/// 差し替えられるようにするためのトレイトです。`&dyn AttackPipeline`として
+/// 扱えるよう、各メソッドは`PipelineFuture`を返します。
+pub trait AttackPipeline: Send + Sync {
+    /// スクラムハーフからのパスを待機
+    fn wait_for_ball(&self) -> PipelineFuture<'_, String>;
+
+    /// ディフェンスラインを分析
+    fn read_defense(&self) -> PipelineFuture<'_, DefenseLine>;
+
+    /// チームメイトのポジショニングを確認
+    fn check_teammates(&self) -> PipelineFuture<'_, Teammates>;
+}
+
+/// デモと同じ振る舞いをする標準のパイプライン
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DemoPipeline {
+    /// 各ステップの待ち時間
+    pub timing: Timing,
+}
+
+impl AttackPipeline for DemoPipeline {
+    fn wait_for_ball(&self) -> PipelineFuture<'_, String> {
+        Box::pin(wait_for_ball(&self.timing))
+    }
+
+    fn read_defense(&self) -> PipelineFuture<'_, DefenseLine> {
+        Box::pin(read_defense(&self.timing))
+    }
+
+    fn check_teammates(&self) -> PipelineFuture<'_, Teammates> {
+        Box::pin(check_teammates(&self.timing))
+    }
+}
+
+/// パイプラインの情報収集を並行実行し、サインを出してから判断する
+///
+/// # Arguments
+///
+/// * `pipeline` - 情報収集に使うパイプライン
+/// * `timing` - サイン出しの待ち時間
+///
+/// # Returns
+///
+/// 最適な攻撃判断
+pub async fn run_attack(pipeline: &dyn AttackPipeline, timing: &Timing) -> Decision {
+    // フェーズ1: 情報収集（すべて並行実行）
+    let (ball, defense, teammates) = tokio::join!(
+        pipeline.wait_for_ball(),
+        pipeline.read_defense(),
+        pipeline.check_teammates()
+    );
+
+    // フェーズ2: サイン出し（並行実行）
+    tokio::join!(signal_backs(timing), signal_forwards(timing));
+
+    // フェーズ3: 判断と実行
+    make_decision(ball, defense, teammates).await
+}
+
+// =============================================================================
+// 失敗を分離した情報収集
+// =============================================================================
+
+/// `gather_analyses`で失敗した分析
+#[derive(Debug)]
+pub enum GatherError {
+    /// 分析がパニックした
+    Panicked {
+        /// 失敗した分析の名前（`wait_for_ball`、`read_defense`、`check_teammates`）
+        analysis: &'static str,
+        /// パニックのメッセージ
+        message: String,
+    },
+    /// 分析が完了前にキャンセルされた
+    Cancelled { analysis: &'static str },
+}
+
+impl GatherError {
+    /// 失敗した分析の名前
+    pub fn analysis(&self) -> &'static str {
+        match self {
+            GatherError::Panicked { analysis, .. } | GatherError::Cancelled { analysis } => {
+                analysis
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for GatherError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GatherError::Panicked { analysis, message } => {
+                write!(
+                    f,
+                    "分析の失敗: {}がパニックしました ({})",
+                    analysis, message
+                )
+            }
+            GatherError::Cancelled { analysis } => {
+                write!(f, "分析の失敗: {}がキャンセルされました", analysis)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GatherError {}
+
+/// `JoinSet`の各タスクが返す分析結果
+enum Analysis {
+    Ball(String),
+    Defense(DefenseLine),
+    Teammates(Teammates),
+}
+
+/// デモの情報収集を`JoinSet`で実行し、失敗した分析を特定して返す
+///
+/// 詳細は`gather_analyses_from`を参照してください。
+pub async fn gather_analyses(
+    timing: &Timing,
+) -> Result<(String, DefenseLine, Teammates), GatherError> {
+    gather_analyses_from(std::sync::Arc::new(DemoPipeline { timing: *timing })).await
+}
+
+/// パイプラインの情報収集を`JoinSet`で並行実行する
+///
+/// 各分析は別々のタスクとして実行されるため、1つの分析がパニックしても
+/// 他の分析は中断されずに最後まで実行されます。すべての分析が終わった後、
+/// 失敗があれば`wait_for_ball`、`read_defense`、`check_teammates`の順で
+/// 最初に失敗した分析を`GatherError`として返します（成功した分析の結果は破棄されます）。
+pub async fn gather_analyses_from(
+    pipeline: std::sync::Arc<dyn AttackPipeline>,
+) -> Result<(String, DefenseLine, Teammates), GatherError> {
+    const ANALYSES: [&str; 3] = ["wait_for_ball", "read_defense", "check_teammates"];
+
+    let mut set = tokio::task::JoinSet::new();
+    let mut names = std::collections::HashMap::new();
+
+    let p = pipeline.clone();
+    let handle = set.spawn(async move { Analysis::Ball(p.wait_for_ball().await) });
+    names.insert(handle.id(), ANALYSES[0]);
+    let p = pipeline.clone();
+    let handle = set.spawn(async move { Analysis::Defense(p.read_defense().await) });
+    names.insert(handle.id(), ANALYSES[1]);
+    let handle = set.spawn(async move { Analysis::Teammates(pipeline.check_teammates().await) });
+    names.insert(handle.id(), ANALYSES[2]);
+
+    let (mut ball, mut defense, mut teammates) = (None, None, None);
+    let mut failures = Vec::new();
+
+    while let Some(joined) = set.join_next_with_id().await {
+        match joined {
+            Ok((_, Analysis::Ball(value))) => ball = Some(value),
+            Ok((_, Analysis::Defense(value))) => defense = Some(value),
+            Ok((_, Analysis::Teammates(value))) => teammates = Some(value),
+            Err(err) => {
+                let analysis = names[&err.id()];
+                let error = if err.is_panic() {
+                    let payload = err.into_panic();
+                    let message = payload
+                        .downcast_ref::<&str>()
+                        .map(|s| s.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| "不明なパニック".to_string());
+                    GatherError::Panicked { analysis, message }
+                } else {
+                    GatherError::Cancelled { analysis }
+                };
+                failures.push(error);
+            }
+        }
+    }
+
+    failures.sort_by_key(|e| ANALYSES.iter().position(|name| *name == e.analysis()));
+    if let Some(error) = failures.into_iter().next() {
+        return Err(error);
+    }
+
+    match (ball, defense, teammates) {
+        (Some(ball), Some(defense), Some(teammates)) => Ok((ball, defense, teammates)),
+        _ => unreachable!("失敗がなければすべての分析が結果を返す"),
+    }
+}
+
 // =============================================================================
 // Rust 2024: Async Closuresのデモ
 // =============================================================================
@@ -315,20 +725,20 @@ where
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== Rust 2024 Edition: ラグビー非同期戦術デモ ===\n");
 
+    // `--instant`で演出の待ち時間を省略
+    let timing = if std::env::args().skip(1).any(|arg| arg == "--instant") {
+        Timing::instant()
+    } else {
+        Timing::realistic()
+    };
+
     let start = std::time::Instant::now();
 
     println!("⚡ 攻撃開始！\n");
 
-    // フェーズ1: 情報収集（すべて並行実行）
-    // Rust 2024のpreludeにより、Futureトレイトは自動的にインポート済み
-    let (ball, defense, teammates) =
-        tokio::join!(wait_for_ball(), read_defense(), check_teammates());
-
-    // フェーズ2: サイン出し（並行実行）
-    tokio::join!(signal_backs(), signal_forwards());
-
-    // フェーズ3: 判断と実行
-    let decision = make_decision(ball, defense, teammates).await;
+    // 情報収集 → サイン出し → 判断（独自のパイプラインに差し替え可能）
+    let pipeline: &dyn AttackPipeline = &DemoPipeline { timing };
+    let decision = run_attack(pipeline, &timing).await;
 
     let duration = start.elapsed();
 
@@ -339,6 +749,41 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         duration.as_secs_f64()
     );
 
+    // スクランブル: 制限時間内に準備できた味方だけで判断
+    println!("\n\n=== スクランブル デモ ===\n");
+
+    let (defense, teammates) = tokio::join!(
+        read_defense(&timing),
+        check_teammates_within(Duration::from_millis(600), &timing)
+    );
+    let decision = make_decision("ボール受領".to_string(), defense, teammates).await;
+    println!("\n🎯 スクランブル時の決定: {}", decision);
+
+    // サインの同時実行数を1に制限すると、2つ目のサインは1つ目を待つ
+    println!("\n\n=== サイン制限 デモ ===\n");
+
+    let limiter = SignalLimiter::new(1);
+    let start = std::time::Instant::now();
+    tokio::join!(
+        limiter.signal_backs(&timing),
+        limiter.signal_forwards(&timing)
+    );
+    println!(
+        "⏱️  同時実行数1でのサイン: {:.1}秒",
+        start.elapsed().as_secs_f64()
+    );
+
+    // 分析ごとにタスクを分け、失敗した分析を特定する
+    println!("\n\n=== JoinSet 情報収集 デモ ===\n");
+
+    match gather_analyses(&timing).await {
+        Ok((ball, defense, teammates)) => {
+            let decision = make_decision(ball, defense, teammates).await;
+            println!("\n🎯 JoinSetでの決定: {}", decision);
+        }
+        Err(err) => println!("⚠️  {}", err),
+    }
+
     // Rust 2024: Async Closuresのデモ
     println!("\n\n=== Async Closures デモ ===\n");
 
@@ -347,7 +792,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     process_tasks_parallel(phases, |phase| {
         Box::pin(async move {
             println!("📋 {} を実行中...", phase);
-            sleep(Duration::from_millis(300)).await;
+            pause(timing.phase).await;
             println!("✓ {} 完了", phase);
         })
     })
@@ -357,3 +802,307 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+// =============================================================================
+// テスト
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn decision_error_exposes_wrapped_cause_as_source() {
+        let cause = std::io::Error::new(std::io::ErrorKind::TimedOut, "トラッキングAPIが無応答");
+        let error = GameError::decision_from("ディフェンスを読めない", cause);
+
+        let source = error.source().expect("原因が連鎖している");
+        assert_eq!(source.to_string(), "トラッキングAPIが無応答");
+        let io = source
+            .downcast_ref::<std::io::Error>()
+            .expect("元のエラー型のまま取り出せる");
+        assert_eq!(io.kind(), std::io::ErrorKind::TimedOut);
+
+        // 複製しても同じ原因を指す
+        let cloned = error.clone();
+        assert_eq!(
+            cloned.source().map(|source| source.to_string()),
+            Some("トラッキングAPIが無応答".to_string())
+        );
+    }
+
+    #[test]
+    fn timeout_and_plain_decision_error_have_no_source() {
+        let timeout = GameError::Timeout {
+            action: "wait_for_ball".to_string(),
+            limit_secs: 3,
+        };
+        let plain = GameError::DecisionError {
+            reason: "不明".to_string(),
+            source: None,
+        };
+        assert!(timeout.source().is_none());
+        assert!(plain.source().is_none());
+    }
+
+    #[test]
+    fn each_error_variant_has_an_actionable_hint() {
+        let ball = GameError::Timeout {
+            action: "wait_for_ball".to_string(),
+            limit_secs: 3,
+        };
+        assert_eq!(
+            ball.hint(),
+            "制限時間を延ばすか、スクラムハーフ（上流の処理）が止まっていないか確認してください"
+        );
+
+        let defense = GameError::Timeout {
+            action: "read_defense".to_string(),
+            limit_secs: 2,
+        };
+        assert_eq!(
+            defense.hint(),
+            "制限時間を延ばすか、read_defenseが完了しているか確認してください"
+        );
+
+        let decision = GameError::DecisionError {
+            reason: "不明".to_string(),
+            source: None,
+        };
+        assert_eq!(
+            decision.hint(),
+            "判断に渡した状態（ディフェンスライン、チームメイト）が正しいか確認してください"
+        );
+    }
+
+    #[test]
+    fn alternate_display_appends_hint_to_core_message() {
+        let error = GameError::Timeout {
+            action: "wait_for_ball".to_string(),
+            limit_secs: 3,
+        };
+        assert_eq!(error.to_string(), "タイムアウト: wait_for_ball (制限: 3秒)");
+        assert_eq!(
+            format!("{:#}", error),
+            format!(
+                "タイムアウト: wait_for_ball (制限: 3秒)\nヒント: {}",
+                error.hint()
+            )
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn short_deadline_leaves_forwards_unready() {
+        let teammates =
+            check_teammates_within(Duration::from_millis(600), &Timing::realistic()).await;
+        assert!(teammates.backs_ready);
+        assert!(!teammates.forwards_ready);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn long_deadline_readies_both_units() {
+        let start = tokio::time::Instant::now();
+        let teammates = check_teammates_within(Duration::from_secs(1), &Timing::realistic()).await;
+        assert!(teammates.backs_ready && teammates.forwards_ready);
+        assert_eq!(start.elapsed(), Duration::from_millis(900));
+    }
+
+    #[tokio::test]
+    async fn instant_timing_runs_every_step_without_waiting() {
+        // 実時間で計測し、どのステップもタイマーで待たないことを確認する
+        let timing = Timing::instant();
+        let start = std::time::Instant::now();
+
+        let decision = run_attack(&DemoPipeline { timing }, &timing).await;
+        let teammates = check_teammates_within(Duration::from_millis(600), &timing).await;
+        let limiter = SignalLimiter::new(1);
+        tokio::join!(
+            limiter.signal_backs(&timing),
+            limiter.signal_forwards(&timing)
+        );
+        gather_analyses(&timing)
+            .await
+            .expect("デモの分析は失敗しない");
+
+        assert!(start.elapsed() < Duration::from_millis(100));
+        assert_eq!(
+            decision,
+            Decision::Pass {
+                direction: Direction::Left
+            }
+        );
+        assert!(teammates.backs_ready && teammates.forwards_ready);
+    }
+
+    /// 固定の分析結果を返すパイプライン
+    struct CannedPipeline {
+        defense: DefenseLine,
+        teammates: Teammates,
+    }
+
+    impl AttackPipeline for CannedPipeline {
+        fn wait_for_ball(&self) -> PipelineFuture<'_, String> {
+            Box::pin(async { "ボール受領".to_string() })
+        }
+
+        fn read_defense(&self) -> PipelineFuture<'_, DefenseLine> {
+            Box::pin(async { self.defense.clone() })
+        }
+
+        fn check_teammates(&self) -> PipelineFuture<'_, Teammates> {
+            Box::pin(async { self.teammates.clone() })
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn mock_pipeline_gap_on_right_leads_to_right_pass() {
+        let pipeline = CannedPipeline {
+            defense: DefenseLine {
+                pressure: true,
+                gap_on_left: false,
+                gap_on_right: true,
+            },
+            teammates: Teammates {
+                backs_ready: true,
+                forwards_ready: false,
+            },
+        };
+        assert_eq!(
+            run_attack(&pipeline, &Timing::realistic()).await,
+            Decision::Pass {
+                direction: Direction::Right
+            }
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn mock_pipeline_under_pressure_without_gap_kicks() {
+        let pipeline = CannedPipeline {
+            defense: DefenseLine {
+                pressure: true,
+                gap_on_left: false,
+                gap_on_right: false,
+            },
+            teammates: Teammates {
+                backs_ready: true,
+                forwards_ready: true,
+            },
+        };
+        assert_eq!(
+            run_attack(&pipeline, &Timing::realistic()).await,
+            Decision::Kick
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limiter_of_one_makes_second_signal_wait_for_the_first() {
+        let timing = Timing::realistic();
+        let limiter = SignalLimiter::new(1);
+        let start = tokio::time::Instant::now();
+        tokio::join!(
+            limiter.signal_backs(&timing),
+            limiter.signal_forwards(&timing)
+        );
+        // 500msのサインが並行せず順に走る
+        assert_eq!(start.elapsed(), Duration::from_millis(1000));
+
+        let unlimited = SignalLimiter::new(2);
+        let start = tokio::time::Instant::now();
+        tokio::join!(
+            unlimited.signal_backs(&timing),
+            unlimited.signal_forwards(&timing)
+        );
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn second_signal_starts_only_after_first_releases_its_permit() {
+        let limiter = SignalLimiter::new(1);
+        let (release, released) = tokio::sync::oneshot::channel::<()>();
+        let first = tokio::spawn({
+            let limiter = limiter.clone();
+            async move {
+                limiter
+                    .run(async {
+                        released.await.expect("解放の合図を受け取る");
+                    })
+                    .await
+            }
+        });
+        tokio::task::yield_now().await;
+
+        let second_started = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let second = tokio::spawn({
+            let limiter = limiter.clone();
+            let second_started = second_started.clone();
+            async move {
+                limiter
+                    .run(async {
+                        second_started.store(true, std::sync::atomic::Ordering::SeqCst);
+                    })
+                    .await
+            }
+        });
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert!(!second_started.load(std::sync::atomic::Ordering::SeqCst));
+
+        release.send(()).expect("1つ目のサインが待っている");
+        first.await.expect("1つ目のサインが完了する");
+        second.await.expect("2つ目のサインが完了する");
+        assert!(second_started.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    /// `read_defense`だけがパニックし、他の分析の完了を数えるパイプライン
+    struct PanickingDefensePipeline {
+        completed: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl AttackPipeline for PanickingDefensePipeline {
+        fn wait_for_ball(&self) -> PipelineFuture<'_, String> {
+            Box::pin(async {
+                self.completed
+                    .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                "ボール受領".to_string()
+            })
+        }
+
+        fn read_defense(&self) -> PipelineFuture<'_, DefenseLine> {
+            Box::pin(async { panic!("トラッキングが途絶") })
+        }
+
+        fn check_teammates(&self) -> PipelineFuture<'_, Teammates> {
+            Box::pin(async {
+                tokio::task::yield_now().await;
+                self.completed
+                    .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                Teammates {
+                    backs_ready: true,
+                    forwards_ready: true,
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn panicking_analysis_is_isolated_and_named_in_the_error() {
+        let completed = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let pipeline = PanickingDefensePipeline {
+            completed: completed.clone(),
+        };
+
+        let error = gather_analyses_from(std::sync::Arc::new(pipeline))
+            .await
+            .expect_err("read_defenseの失敗が報告される");
+        assert_eq!(error.analysis(), "read_defense");
+        match &error {
+            GatherError::Panicked { message, .. } => assert_eq!(message, "トラッキングが途絶"),
+            other => panic!("パニックとして報告される: {:?}", other),
+        }
+        assert!(error.to_string().contains("read_defense"));
+        // 他の分析は中断されずに完了している
+        assert_eq!(completed.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+}
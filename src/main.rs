@@ -39,6 +39,54 @@ struct Teammates {
     forwards_ready: bool,
 }
 
+// =============================================================================
+// 演出のタイミング
+// =============================================================================
+
+/// 各ステップの待ち時間の設定
+///
+/// `Timing::instant()`では全て0になり、実際には待ちません。
+#[derive(Debug, Clone, Copy)]
+struct Timing {
+    /// スクラムハーフからのパスが届くまでの時間
+    ball: Duration,
+    /// ディフェンスラインの分析にかかる時間
+    defense: Duration,
+    /// 味方のポジショニング確認にかかる時間
+    teammates: Duration,
+    /// サイン1つにかかる時間
+    signal: Duration,
+}
+
+impl Timing {
+    /// デモ用の現実的な待ち時間
+    fn realistic() -> Self {
+        Timing {
+            ball: Duration::from_secs(2),
+            defense: Duration::from_secs(1),
+            teammates: Duration::from_millis(800),
+            signal: Duration::from_millis(500),
+        }
+    }
+
+    /// 待ち時間なし
+    fn instant() -> Self {
+        Timing {
+            ball: Duration::ZERO,
+            defense: Duration::ZERO,
+            teammates: Duration::ZERO,
+            signal: Duration::ZERO,
+        }
+    }
+}
+
+/// 指定時間だけ待つ（0の場合はタイマーを使わずに即座に戻る）
+async fn pause(duration: Duration) {
+    if !duration.is_zero() {
+        sleep(duration).await;
+    }
+}
+
 // =============================================================================
 // 非同期関数
 // =============================================================================
@@ -48,9 +96,9 @@ struct Teammates {
 /// # Returns
 ///
 /// ボール受領完了のメッセージ
-async fn wait_for_ball() -> String {
+async fn wait_for_ball(timing: &Timing) -> String {
     println!("🏉 スクラムハーフからのパスを待機...");
-    sleep(Duration::from_secs(2)).await;
+    pause(timing.ball).await;
     println!("✓ ボール受け取り完了");
     "ボール受領".to_string()
 }
@@ -62,9 +110,9 @@ async fn wait_for_ball() -> String {
 /// # Returns
 ///
 /// 分析されたディフェンスラインの状態
-async fn read_defense() -> DefenseLine {
+async fn read_defense(timing: &Timing) -> DefenseLine {
     println!("👀 ディフェンスラインを読む...");
-    sleep(Duration::from_secs(1)).await;
+    pause(timing.defense).await;
 
     let defense = DefenseLine {
         pressure: false,
@@ -83,9 +131,9 @@ async fn read_defense() -> DefenseLine {
 /// # Returns
 ///
 /// チームメイトの準備状態
-async fn check_teammates() -> Teammates {
+async fn check_teammates(timing: &Timing) -> Teammates {
     println!("👥 味方のポジショニング確認...");
-    sleep(Duration::from_millis(800)).await;
+    pause(timing.teammates).await;
 
     let teammates = Teammates {
         backs_ready: true,
@@ -99,18 +147,18 @@ async fn check_teammates() -> Teammates {
 /// バックスに展開のサインを送る
 ///
 /// 非同期でバックスに指示を出し、準備完了を待ちます。
-async fn signal_backs() {
+async fn signal_backs(timing: &Timing) {
     println!("📢 バックスに展開のサイン...");
-    sleep(Duration::from_millis(500)).await;
+    pause(timing.signal).await;
     println!("✓ バックス準備完了");
 }
 
 /// フォワードにサポートのサインを送る
 ///
 /// 非同期でフォワードに指示を出し、準備完了を待ちます。
-async fn signal_forwards() {
+async fn signal_forwards(timing: &Timing) {
     println!("📢 フォワードにサポートのサイン...");
-    sleep(Duration::from_millis(500)).await;
+    pause(timing.signal).await;
     println!("✓ フォワード準備完了");
 }
 
@@ -134,6 +182,7 @@ async fn signal_forwards() {
 /// 1. 左にギャップがあり、バックスが準備完了 → 左サイドへパス展開
 /// 2. プレッシャーがなく、フォワードが準備完了 → クラッシュボール
 /// 3. それ以外 → ハイパントキック
+#[must_use]
 async fn make_decision(_ball: String, defense: DefenseLine, teammates: Teammates) -> String {
     println!("\n🧠 状況を統合して判断...");
 
@@ -148,16 +197,26 @@ async fn make_decision(_ball: String, defense: DefenseLine, teammates: Teammates
 
 #[tokio::main]
 async fn main() {
+    // `--instant`で演出の待ち時間を省略
+    let timing = if std::env::args().skip(1).any(|arg| arg == "--instant") {
+        Timing::instant()
+    } else {
+        Timing::realistic()
+    };
+
     let start = std::time::Instant::now();
 
     println!("=== 攻撃開始 ===\n");
 
     // フェーズ1: 情報収集（すべて並行実行）
-    let (ball, defense, teammates) =
-        tokio::join!(wait_for_ball(), read_defense(), check_teammates());
+    let (ball, defense, teammates) = tokio::join!(
+        wait_for_ball(&timing),
+        read_defense(&timing),
+        check_teammates(&timing)
+    );
 
     // フェーズ2: サイン出し（並行実行）
-    tokio::join!(signal_backs(), signal_forwards());
+    tokio::join!(signal_backs(&timing), signal_forwards(&timing));
 
     // フェーズ3: 判断と実行
     let decision = make_decision(ball, defense, teammates).await;